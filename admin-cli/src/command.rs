@@ -1,4 +1,5 @@
 use clap::Subcommand;
+use cli_common::constant::DEFAULT_FILLS_CAPACITY;
 
 #[derive(Subcommand, Debug)]
 pub enum Commands {
@@ -6,17 +7,40 @@ pub enum Commands {
     Initializes the system by creating the configuration registry, deny list registry
     fills registry, and program state account.Init, Change the configs of the system
     **/
-    Init,
+    Init {
+        /// Number of fills the fills registry has room for.
+        #[arg(long = "fills-capacity", default_value_t = DEFAULT_FILLS_CAPACITY)]
+        fills_capacity: u64,
+    },
     /// Displays current configuration registry contents.
     ViewConfig,
 
     /// Updates configuration parameters using values from root directory config.json.
     UpdateConfig,
 
+    /// Queues a timelocked configuration change using values from root directory config.json.
+    QueueConfig,
+
+    /// Executes a queued configuration change once its delay has passed.
+    ExecuteConfig {
+        #[arg(short = 'i', required = true)]
+        change_id: u64,
+    },
+
+    /// Cancels a queued configuration change.
+    CancelConfig {
+        #[arg(short = 'i', required = true)]
+        change_id: u64,
+    },
+
+    /// Displays queued configuration changes.
+    ViewPendingConfigs,
+
     /// View current system state.
     ViewSystemState,
 
     /// Toggles system between active and paused states.
+    /// With feature flags, only the selected features are activated or paused.
     ToggleSystemState {
         /// Flag to activate
         #[arg(long, action, required = false)]
@@ -25,6 +49,37 @@ pub enum Commands {
         /// Flag to pause
         #[arg(long, action, required = false)]
         pause: bool,
+
+        /// Applies to buys only
+        #[arg(long, action, required = false)]
+        buys: bool,
+
+        /// Applies to fills dequeues only
+        #[arg(long, action, required = false)]
+        dequeues: bool,
+
+        /// Applies to price quotes only
+        #[arg(long, action, required = false)]
+        quotes: bool,
+    },
+
+    /// Halts the system. Works with the admin or the guardian keypair, unhalting stays with the admin.
+    Halt,
+
+    /// Schedules an oracle key rotation and shows the rotation status.
+    /// Without a new key only the current status is shown.
+    RotateOracleKey {
+        /// Oracle public key taking over from the current one
+        #[arg(short = 'k', long, requires = "retire_timestamp")]
+        next_oracle_pubkey: Option<String>,
+
+        /// Unix timestamp from which the new key is accepted. Defaults to now plus the config change delay.
+        #[arg(long)]
+        activation_timestamp: Option<i64>,
+
+        /// Unix timestamp from which the current key is no longer accepted
+        #[arg(long)]
+        retire_timestamp: Option<i64>,
     },
 
     /// Sets Fills Consumer in the configuration Registry.
@@ -60,6 +115,36 @@ pub enum Commands {
         authority: String,
     },
 
+    /// Sets the guardian, who can halt but not unhalt the system.
+    SetGuardian {
+        #[arg(short = 'a', required = true)]
+        guardian: String,
+    },
+
+    /// Proposes a new admin, who has to accept before taking over.
+    ProposeAdmin {
+        #[arg(short = 'a', required = true)]
+        admin: String,
+    },
+
+    /// Accepts the pending admin transfer. Must be signed by the proposed admin.
+    AcceptAdmin,
+
+    /// Cancels the pending admin transfer.
+    CancelAdminTransfer,
+
+    /// Proposes a new deny list authority, who has to accept before taking over.
+    ProposeDenyAuthority {
+        #[arg(short = 'a', required = true)]
+        authority: String,
+    },
+
+    /// Accepts the pending deny list authority transfer. Must be signed by the proposed authority.
+    AcceptDenyAuthority,
+
+    /// Cancels the pending deny list authority transfer.
+    CancelDenyAuthorityTransfer,
+
     /// Initializes mock transfer program accounts.
     InitMockProgram,
 
@@ -85,5 +170,55 @@ pub enum Commands {
     },
 
     /// View fills registry, which tracks individual fill records and overall aggregate statistics.
-    ViewFills
+    ViewFills {
+        /// Number of pending fills skipped from the head.
+        #[arg(long, default_value_t = 0)]
+        offset: u64,
+
+        /// Maximum number of fills listed, all pending fills if not set.
+        #[arg(long)]
+        limit: Option<u64>,
+    },
+
+    /// Moves the fills of a legacy registry into a new registry using the current fill layout.
+    UpgradeFills {
+        /// Number of fills moved per transaction.
+        #[arg(short = 'b', default_value_t = 500)]
+        batch_size: u32,
+
+        /// Number of fills the new registry has room for.
+        #[arg(long = "fills-capacity", default_value_t = DEFAULT_FILLS_CAPACITY)]
+        fills_capacity: u64,
+    },
+
+    /// Moves the fills into a new registry, then closes the previous one.
+    MigrateFills {
+        /// Number of fills moved per transaction.
+        #[arg(short = 'b', default_value_t = 500)]
+        batch_size: u32,
+
+        /// Receives the lamports of the previous registry.
+        #[arg(short = 'r', required = true)]
+        recipient: String,
+
+        /// Number of fills the new registry has room for.
+        #[arg(long = "fills-capacity", default_value_t = DEFAULT_FILLS_CAPACITY)]
+        fills_capacity: u64,
+    },
+
+    /// Closes an empty fills registry the program no longer uses.
+    CloseFills {
+        #[arg(short = 'a', required = true)]
+        fills_registry: String,
+
+        /// Receives the lamports of the closed registry.
+        #[arg(short = 'r', required = true)]
+        recipient: String,
+    },
+
+    /// Grows the configuration registry to the layout of the upgraded program.
+    MigrateConfigRegistry,
+
+    /// Grows the program state to the layout of the upgraded program.
+    MigrateProgramState,
 }
\ No newline at end of file
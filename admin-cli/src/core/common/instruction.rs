@@ -5,7 +5,27 @@ pub const REMOVE_FROM_DENY_LIST_INSTRUCTION: &[u8] = b"global:remove_from_deny_l
 pub const UPDATE_CONFIGURATION_REGISTRY_INSTRUCTION: &[u8] = b"global:update_configuration_registry";
 pub const SET_ADMIN_INSTRUCTION: &[u8] = b"global:set_admin";
 pub const TOGGLE_SYSTEM_STATE_INSTRUCTION: &[u8] = b"global:toggle_system_state";
+pub const HALT_SYSTEM_INSTRUCTION: &[u8] = b"global:halt_system";
+pub const SET_PAUSE_FLAGS_INSTRUCTION: &[u8] = b"global:set_pause_flags";
+pub const SET_GUARDIAN_INSTRUCTION: &[u8] = b"global:set_guardian";
 pub const SET_DENY_LIST_AUTHORITY_INSTRUCTION: &[u8] = b"global:set_deny_list_authority";
+pub const ROTATE_ORACLE_KEY_INSTRUCTION: &[u8] = b"global:rotate_oracle_key";
+pub const QUEUE_CONFIG_CHANGE_INSTRUCTION: &[u8] = b"global:queue_config_change";
+pub const EXECUTE_CONFIG_CHANGE_INSTRUCTION: &[u8] = b"global:execute_config_change";
+pub const CANCEL_CONFIG_CHANGE_INSTRUCTION: &[u8] = b"global:cancel_config_change";
+pub const PROPOSE_ADMIN_INSTRUCTION: &[u8] = b"global:propose_admin";
+pub const ACCEPT_ADMIN_INSTRUCTION: &[u8] = b"global:accept_admin";
+pub const CANCEL_ADMIN_TRANSFER_INSTRUCTION: &[u8] = b"global:cancel_admin_transfer";
+pub const PROPOSE_DENY_LIST_AUTHORITY_INSTRUCTION: &[u8] = b"global:propose_deny_list_authority";
+pub const ACCEPT_DENY_LIST_AUTHORITY_INSTRUCTION: &[u8] = b"global:accept_deny_list_authority";
+pub const CANCEL_DENY_LIST_AUTHORITY_TRANSFER_INSTRUCTION: &[u8] = b"global:cancel_deny_list_authority_transfer";
+pub const BEGIN_FILLS_UPGRADE_INSTRUCTION: &[u8] = b"global:begin_fills_upgrade";
+pub const UPGRADE_FILLS_INSTRUCTION: &[u8] = b"global:upgrade_fills";
+pub const BEGIN_FILLS_MIGRATION_INSTRUCTION: &[u8] = b"global:begin_fills_migration";
+pub const MIGRATE_FILLS_INSTRUCTION: &[u8] = b"global:migrate_fills";
+pub const CLOSE_FILLS_REGISTRY_INSTRUCTION: &[u8] = b"global:close_fills_registry";
+pub const MIGRATE_CONFIGURATION_REGISTRY_INSTRUCTION: &[u8] = b"global:migrate_configuration_registry";
+pub const MIGRATE_PROGRAM_STATE_INSTRUCTION: &[u8] = b"global:migrate_program_state";
 
 
 
@@ -2,6 +2,7 @@ use anchor_client::{
     anchor_lang::prelude::*,
     solana_sdk::pubkey::Pubkey,
 };
+use cli_common::structs::{DiscountCurve, DiscountClock};
 
 #[derive(AnchorSerialize, AnchorDeserialize, Debug)]
 pub struct ConfigurationRegistryInput {
@@ -11,4 +12,44 @@ pub struct ConfigurationRegistryInput {
     pub coefficient: Option<u64>,
     pub max_discount_rate: Option<u64>,
     pub min_discount_rate: Option<u64>,
+    pub min_sol_quantity: Option<u64>,
+    pub max_sol_quantity: Option<u64>,
+    pub oracle_pubkeys: Option<Vec<Pubkey>>,
+    pub oracle_quorum_threshold: Option<u8>,
+    pub disable_v1_attestations: Option<bool>,
+    pub attestation_timestamp_tolerance: Option<u32>,
+    pub max_trades_per_wallet_per_epoch: Option<u64>,
+    pub max_sol_per_wallet_per_epoch: Option<u64>,
+    pub epoch_sol_cap: Option<u64>,
+    pub config_change_delay: Option<u32>,
+    pub fills_high_watermark_bps: Option<u16>,
+    pub fills_low_watermark_bps: Option<u16>,
+    pub stop_buys_at_high_watermark: Option<bool>,
+    pub discount_curve: Option<DiscountCurve>,
+    pub discount_clock: Option<DiscountClock>,
+    pub discount_memory_bps: Option<u16>,
+    pub min_swap_rate: Option<u64>,
+    pub max_swap_rate: Option<u64>,
+    pub max_swap_rate_deviation_bps: Option<u16>,
+    pub reference_swap_rate_max_age: Option<u32>,
+    pub max_confidence_bps: Option<u16>,
+    pub confidence_widening_bps: Option<u16>,
+}
+
+#[derive(AnchorDeserialize, Debug)]
+pub struct PendingConfigChange {
+    pub change_id: u64,
+    pub queued_by: Pubkey,
+    pub executable_after: i64,
+    pub input: ConfigurationRegistryInput,
+}
+
+impl AccountDeserialize for PendingConfigChange {
+    fn try_deserialize(buf: &mut &[u8]) -> Result<Self> {
+        *buf = &buf[8..];
+        PendingConfigChange::try_deserialize_unchecked(buf)
+    }
+    fn try_deserialize_unchecked(buf: &mut &[u8]) -> Result<Self> {
+        PendingConfigChange::deserialize(buf).map_err(Into::into)
+    }
 }
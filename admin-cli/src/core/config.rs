@@ -3,7 +3,7 @@ use std::{
     str::FromStr
 };
 use anchor_client::anchor_lang::prelude::Pubkey;
-use cli_common::config::Config;
+use cli_common::{config::Config, structs::{DiscountCurve, DiscountClock}};
 pub struct AdminConfig {
     pub rpc_url: String,
     pub program_id: String,
@@ -14,12 +14,38 @@ pub struct AdminConfig {
     pub coefficient: u64,
     pub max_discount_rate: u64,
     pub min_discount_rate: u64,
+    pub min_sol_quantity: u64,
+    pub max_sol_quantity: u64,
+    pub oracle_pubkeys: Option<Vec<Pubkey>>,
+    pub oracle_quorum_threshold: Option<u8>,
+    pub disable_v1_attestations: Option<bool>,
+    pub attestation_timestamp_tolerance: Option<u32>,
+    pub max_trades_per_wallet_per_epoch: Option<u64>,
+    pub max_sol_per_wallet_per_epoch: Option<u64>,
+    pub epoch_sol_cap: Option<u64>,
+    pub config_change_delay: Option<u32>,
+    pub fills_high_watermark_bps: Option<u16>,
+    pub fills_low_watermark_bps: Option<u16>,
+    pub stop_buys_at_high_watermark: Option<bool>,
+    pub discount_curve: Option<DiscountCurve>,
+    pub discount_clock: Option<DiscountClock>,
+    pub discount_memory_bps: Option<u16>,
+    pub min_swap_rate: Option<u64>,
+    pub max_swap_rate: Option<u64>,
+    pub max_swap_rate_deviation_bps: Option<u16>,
+    pub reference_swap_rate_max_age: Option<u32>,
+    pub max_confidence_bps: Option<u16>,
+    pub confidence_widening_bps: Option<u16>,
 }
 
 impl AdminConfig {
     pub fn load_admin_config() -> Result<Self, Box<dyn Error>> {
         let raw_config = Config::load()?;
         let raw_pub_key = raw_config.oracle_pubkey.ok_or("Missing oracle_pubkey in config file")?;
+        // Oracle quorum is optional, the registry keeps its current set when absent.
+        let oracle_pubkeys = raw_config.oracle_pubkeys
+            .map(|keys| keys.iter().map(|key| Pubkey::from_str(key)).collect::<Result<Vec<_>, _>>())
+            .transpose()?;
         Ok(AdminConfig {
             rpc_url: raw_config.rpc_url,
             program_id: raw_config.program_id,
@@ -30,6 +56,28 @@ impl AdminConfig {
             coefficient: raw_config.coefficient.ok_or("Missing coefficient in config file")?,
             max_discount_rate: raw_config.max_discount_rate.ok_or("Missing max_discount_rate in config file")?,
             min_discount_rate: raw_config.min_discount_rate.ok_or("Missing min_discount_rate in config file")?,
+            min_sol_quantity: raw_config.min_sol_quantity.ok_or("Missing min_sol_quantity in config file")?,
+            max_sol_quantity: raw_config.max_sol_quantity.ok_or("Missing max_sol_quantity in config file")?,
+            oracle_pubkeys,
+            oracle_quorum_threshold: raw_config.oracle_quorum_threshold,
+            disable_v1_attestations: raw_config.disable_v1_attestations,
+            attestation_timestamp_tolerance: raw_config.attestation_timestamp_tolerance,
+            max_trades_per_wallet_per_epoch: raw_config.max_trades_per_wallet_per_epoch,
+            max_sol_per_wallet_per_epoch: raw_config.max_sol_per_wallet_per_epoch,
+            epoch_sol_cap: raw_config.epoch_sol_cap,
+            config_change_delay: raw_config.config_change_delay,
+            fills_high_watermark_bps: raw_config.fills_high_watermark_bps,
+            fills_low_watermark_bps: raw_config.fills_low_watermark_bps,
+            stop_buys_at_high_watermark: raw_config.stop_buys_at_high_watermark,
+            discount_curve: raw_config.discount_curve,
+            discount_clock: raw_config.discount_clock,
+            discount_memory_bps: raw_config.discount_memory_bps,
+            min_swap_rate: raw_config.min_swap_rate,
+            max_swap_rate: raw_config.max_swap_rate,
+            max_swap_rate_deviation_bps: raw_config.max_swap_rate_deviation_bps,
+            reference_swap_rate_max_age: raw_config.reference_swap_rate_max_age,
+            max_confidence_bps: raw_config.max_confidence_bps,
+            confidence_widening_bps: raw_config.confidence_widening_bps,
         })
     }
 }
\ No newline at end of file
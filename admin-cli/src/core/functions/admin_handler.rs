@@ -2,7 +2,15 @@ use std::{error::Error, str::FromStr};
 
 use anchor_client::{anchor_lang::prelude::AccountMeta, solana_sdk::{hash::hash, instruction::Instruction, pubkey::Pubkey, signer::Signer}};
 use cli_common::{transaction_executor::send_batch_instructions, utils::{env_var::load_payer_from_env, pda_helper, ui}};
-use crate::core::{common::instruction::{SET_ADMIN_INSTRUCTION, SET_DENY_LIST_AUTHORITY_INSTRUCTION}, config::AdminConfig};
+use crate::core::{
+    common::instruction::{
+        ACCEPT_ADMIN_INSTRUCTION, ACCEPT_DENY_LIST_AUTHORITY_INSTRUCTION,
+        CANCEL_ADMIN_TRANSFER_INSTRUCTION, CANCEL_DENY_LIST_AUTHORITY_TRANSFER_INSTRUCTION,
+        PROPOSE_ADMIN_INSTRUCTION, PROPOSE_DENY_LIST_AUTHORITY_INSTRUCTION, SET_ADMIN_INSTRUCTION,
+        SET_DENY_LIST_AUTHORITY_INSTRUCTION, SET_GUARDIAN_INSTRUCTION,
+    },
+    config::AdminConfig,
+};
 
 pub fn set_admin(admin: String) -> Result<(), Box<dyn Error>> {
     let payer = load_payer_from_env()?;
@@ -78,4 +86,126 @@ pub fn set_deny_authority(authority: String) -> Result<(), Box<dyn Error>> {
 
     println!("{} Deny list authority has been set successfully", ui::OK);
     Ok(())
-}
\ No newline at end of file
+}
+
+pub fn set_guardian(guardian: String) -> Result<(), Box<dyn Error>> {
+    let payer = load_payer_from_env()?;
+    let admin_config = AdminConfig::load_admin_config()?;
+    let program_id = Pubkey::from_str(&admin_config.program_id)?;
+
+    let program_state_pda = pda_helper::get_program_state_pda(program_id).0;
+    let guardian_pubkey = Pubkey::from_str(&guardian)?;
+
+    println!("Program state PDA: {}", program_state_pda);
+    println!("Setting guardian to {}", guardian_pubkey);
+
+    let mut data = hash(SET_GUARDIAN_INSTRUCTION).to_bytes()[..8].to_vec();
+    data = [
+        data,
+        guardian_pubkey.to_bytes().to_vec(),
+    ].concat();
+
+    let accounts = vec![
+        AccountMeta::new(payer.pubkey(), true),
+        AccountMeta::new(program_state_pda, false),
+    ];
+
+    let ix = Instruction {
+        program_id,
+        accounts,
+        data,
+    };
+
+    send_batch_instructions(vec![ix])?;
+
+    println!("{} Guardian has been set successfully", ui::OK);
+    Ok(())
+}
+
+pub fn propose_admin(admin: String) -> Result<(), Box<dyn Error>> {
+    let admin_pubkey = Pubkey::from_str(&admin)?;
+    println!("Proposing {} as the new admin", admin_pubkey);
+
+    send_authority_transfer_instruction(PROPOSE_ADMIN_INSTRUCTION, Some(admin_pubkey))?;
+
+    println!("{} Admin transfer proposed. {} has to accept it", ui::OK, admin_pubkey);
+    Ok(())
+}
+
+pub fn accept_admin() -> Result<(), Box<dyn Error>> {
+    println!("Accepting the pending admin transfer");
+
+    send_authority_transfer_instruction(ACCEPT_ADMIN_INSTRUCTION, None)?;
+
+    println!("{} Admin transfer accepted", ui::OK);
+    Ok(())
+}
+
+pub fn cancel_admin_transfer() -> Result<(), Box<dyn Error>> {
+    println!("Cancelling the pending admin transfer");
+
+    send_authority_transfer_instruction(CANCEL_ADMIN_TRANSFER_INSTRUCTION, None)?;
+
+    println!("{} Admin transfer cancelled", ui::OK);
+    Ok(())
+}
+
+pub fn propose_deny_authority(authority: String) -> Result<(), Box<dyn Error>> {
+    let authority_pubkey = Pubkey::from_str(&authority)?;
+    println!("Proposing {} as the new deny list authority", authority_pubkey);
+
+    send_authority_transfer_instruction(PROPOSE_DENY_LIST_AUTHORITY_INSTRUCTION, Some(authority_pubkey))?;
+
+    println!("{} Deny list authority transfer proposed. {} has to accept it", ui::OK, authority_pubkey);
+    Ok(())
+}
+
+pub fn accept_deny_authority() -> Result<(), Box<dyn Error>> {
+    println!("Accepting the pending deny list authority transfer");
+
+    send_authority_transfer_instruction(ACCEPT_DENY_LIST_AUTHORITY_INSTRUCTION, None)?;
+
+    println!("{} Deny list authority transfer accepted", ui::OK);
+    Ok(())
+}
+
+pub fn cancel_deny_authority_transfer() -> Result<(), Box<dyn Error>> {
+    println!("Cancelling the pending deny list authority transfer");
+
+    send_authority_transfer_instruction(CANCEL_DENY_LIST_AUTHORITY_TRANSFER_INSTRUCTION, None)?;
+
+    println!("{} Deny list authority transfer cancelled", ui::OK);
+    Ok(())
+}
+
+/// Sends one of the propose/accept/cancel instructions, signed by the payer.
+fn send_authority_transfer_instruction(
+    instruction: &[u8],
+    new_authority: Option<Pubkey>,
+) -> Result<(), Box<dyn Error>> {
+    let payer = load_payer_from_env()?;
+    let admin_config = AdminConfig::load_admin_config()?;
+    let program_id = Pubkey::from_str(&admin_config.program_id)?;
+
+    let program_state_pda = pda_helper::get_program_state_pda(program_id).0;
+    println!("Program state PDA: {}", program_state_pda);
+
+    let mut data = hash(instruction).to_bytes()[..8].to_vec();
+    if let Some(new_authority) = new_authority {
+        data = [data, new_authority.to_bytes().to_vec()].concat();
+    }
+
+    let accounts = vec![
+        AccountMeta::new(payer.pubkey(), true),
+        AccountMeta::new(program_state_pda, false),
+    ];
+
+    let ix = Instruction {
+        program_id,
+        accounts,
+        data,
+    };
+
+    send_batch_instructions(vec![ix])?;
+    Ok(())
+}
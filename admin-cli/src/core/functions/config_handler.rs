@@ -1,7 +1,12 @@
-use std::{error::Error, str::FromStr};
+use std::{
+    error::Error,
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
 
 use anchor_client::{
-    anchor_lang::{prelude::AccountMeta, AnchorSerialize},
+    anchor_lang::{prelude::AccountMeta, system_program, AccountDeserialize, AnchorSerialize},
+    solana_client::rpc_client::RpcClient,
     solana_sdk::{
         hash::hash, instruction::Instruction, pubkey::Pubkey, signer::Signer,
     },
@@ -13,11 +18,18 @@ use cli_common::{
 };
 use crate::core::{
     common::{
-        instruction::UPDATE_CONFIGURATION_REGISTRY_INSTRUCTION, structs::ConfigurationRegistryInput,
+        instruction::{
+            CANCEL_CONFIG_CHANGE_INSTRUCTION, EXECUTE_CONFIG_CHANGE_INSTRUCTION,
+            QUEUE_CONFIG_CHANGE_INSTRUCTION, UPDATE_CONFIGURATION_REGISTRY_INSTRUCTION,
+        },
+        structs::{ConfigurationRegistryInput, PendingConfigChange},
     },
     config::AdminConfig,
 };
 
+// Maximum accounts fetched per getMultipleAccounts request.
+const MAX_ACCOUNTS_PER_REQUEST: usize = 100;
+
 pub fn view_config() -> Result<(), Box<dyn Error>> {
     let admin_config = AdminConfig::load_admin_config()?;
     let program_id = Pubkey::from_str(&admin_config.program_id)?;
@@ -37,14 +49,7 @@ pub fn update_config() -> Result<(), Box<dyn Error>> {
     let payer = load_payer_from_env()?;
 
     let mut account_data = hash(UPDATE_CONFIGURATION_REGISTRY_INSTRUCTION).to_bytes()[..8].to_vec();
-    let input = ConfigurationRegistryInput {
-        oracle_pubkey: Some(admin_config.oracle_pubkey),
-        sol_quantity: Some(admin_config.sol_quantity),
-        price_maximum_age: Some(admin_config.price_maximum_age),
-        coefficient: Some(admin_config.coefficient),
-        max_discount_rate: Some(admin_config.max_discount_rate),
-        min_discount_rate: Some(admin_config.min_discount_rate),
-    };
+    let input = configuration_input(admin_config);
     account_data = [account_data, input.try_to_vec()?].concat();
 
     let configuration_registry_pda = pda_helper::get_configuration_registry_pda(program_id).0;
@@ -68,4 +73,201 @@ pub fn update_config() -> Result<(), Box<dyn Error>> {
     send_batch_instructions(vec![ix])?;
     println!("{} Configuration registry updated", ui::OK);
     Ok(())
-}
\ No newline at end of file
+}
+
+pub fn queue_config() -> Result<(), Box<dyn Error>> {
+    println!("{} Queue configuration change", ui::LABEL);
+
+    let admin_config = AdminConfig::load_admin_config()?;
+    let program_id = Pubkey::from_str(&admin_config.program_id)?;
+
+    let payer = load_payer_from_env()?;
+
+    let configuration_registry_pda = pda_helper::get_configuration_registry_pda(program_id).0;
+    let program_state_pda = pda_helper::get_program_state_pda(program_id).0;
+    let config_registry: ConfigurationRegistry =
+        get_account_data(admin_config.rpc_url.clone(), configuration_registry_pda)?;
+    let change_id = config_registry.next_config_change_id;
+    let pending_config_change_pda = pda_helper::get_pending_config_change_pda(program_id, change_id).0;
+
+    println!("Configuration registry PDA: {}", configuration_registry_pda);
+    println!("Pending configuration change PDA: {}", pending_config_change_pda);
+
+    let mut account_data = hash(QUEUE_CONFIG_CHANGE_INSTRUCTION).to_bytes()[..8].to_vec();
+    let input = configuration_input(admin_config);
+    account_data = [account_data, input.try_to_vec()?].concat();
+
+    let accounts = vec![
+        AccountMeta::new(configuration_registry_pda, false),
+        AccountMeta::new_readonly(program_state_pda, false),
+        AccountMeta::new(pending_config_change_pda, false),
+        AccountMeta::new(payer.pubkey(), true),
+        AccountMeta::new_readonly(system_program::ID, false),
+    ];
+
+    let ix = Instruction {
+        program_id,
+        accounts,
+        data: account_data,
+    };
+
+    send_batch_instructions(vec![ix])?;
+    println!(
+        "{} Configuration change {} queued, executable after a delay of {} seconds",
+        ui::OK,
+        change_id,
+        config_registry.config_change_delay
+    );
+    Ok(())
+}
+
+pub fn execute_config(change_id: u64) -> Result<(), Box<dyn Error>> {
+    println!("{} Execute configuration change {}", ui::LABEL, change_id);
+
+    let admin_config = AdminConfig::load_admin_config()?;
+    let program_id = Pubkey::from_str(&admin_config.program_id)?;
+
+    let payer = load_payer_from_env()?;
+
+    let configuration_registry_pda = pda_helper::get_configuration_registry_pda(program_id).0;
+    let program_state_pda = pda_helper::get_program_state_pda(program_id).0;
+    let pending_config_change_pda = pda_helper::get_pending_config_change_pda(program_id, change_id).0;
+    let pending_config_change: PendingConfigChange =
+        get_account_data(admin_config.rpc_url, pending_config_change_pda)?;
+
+    let mut account_data = hash(EXECUTE_CONFIG_CHANGE_INSTRUCTION).to_bytes()[..8].to_vec();
+    account_data = [account_data, change_id.to_le_bytes().to_vec()].concat();
+
+    let accounts = vec![
+        AccountMeta::new(configuration_registry_pda, false),
+        AccountMeta::new_readonly(program_state_pda, false),
+        AccountMeta::new(pending_config_change_pda, false),
+        AccountMeta::new(pending_config_change.queued_by, false),
+        AccountMeta::new(payer.pubkey(), true),
+    ];
+
+    let ix = Instruction {
+        program_id,
+        accounts,
+        data: account_data,
+    };
+
+    send_batch_instructions(vec![ix])?;
+    println!("{} Configuration change {} executed", ui::OK, change_id);
+    Ok(())
+}
+
+pub fn cancel_config(change_id: u64) -> Result<(), Box<dyn Error>> {
+    println!("{} Cancel configuration change {}", ui::LABEL, change_id);
+
+    let admin_config = AdminConfig::load_admin_config()?;
+    let program_id = Pubkey::from_str(&admin_config.program_id)?;
+
+    let payer = load_payer_from_env()?;
+
+    let program_state_pda = pda_helper::get_program_state_pda(program_id).0;
+    let pending_config_change_pda = pda_helper::get_pending_config_change_pda(program_id, change_id).0;
+    let pending_config_change: PendingConfigChange =
+        get_account_data(admin_config.rpc_url, pending_config_change_pda)?;
+
+    let mut account_data = hash(CANCEL_CONFIG_CHANGE_INSTRUCTION).to_bytes()[..8].to_vec();
+    account_data = [account_data, change_id.to_le_bytes().to_vec()].concat();
+
+    let accounts = vec![
+        AccountMeta::new_readonly(program_state_pda, false),
+        AccountMeta::new(pending_config_change_pda, false),
+        AccountMeta::new(pending_config_change.queued_by, false),
+        AccountMeta::new(payer.pubkey(), true),
+    ];
+
+    let ix = Instruction {
+        program_id,
+        accounts,
+        data: account_data,
+    };
+
+    send_batch_instructions(vec![ix])?;
+    println!("{} Configuration change {} cancelled", ui::OK, change_id);
+    Ok(())
+}
+
+pub fn view_pending_configs() -> Result<(), Box<dyn Error>> {
+    println!("{} Pending configuration changes", ui::LABEL);
+
+    let admin_config = AdminConfig::load_admin_config()?;
+    let program_id = Pubkey::from_str(&admin_config.program_id)?;
+
+    let configuration_registry_pda = pda_helper::get_configuration_registry_pda(program_id).0;
+    let config_registry: ConfigurationRegistry =
+        get_account_data(admin_config.rpc_url.clone(), configuration_registry_pda)?;
+
+    // Executed and cancelled changes are closed, only pending ones still exist.
+    let pending_config_change_pdas: Vec<Pubkey> = (0..config_registry.next_config_change_id)
+        .map(|change_id| pda_helper::get_pending_config_change_pda(program_id, change_id).0)
+        .collect();
+
+    let client = RpcClient::new(admin_config.rpc_url);
+    let mut pending_changes = Vec::new();
+    for pdas in pending_config_change_pdas.chunks(MAX_ACCOUNTS_PER_REQUEST) {
+        for account in client.get_multiple_accounts(pdas)?.into_iter().flatten() {
+            pending_changes.push(PendingConfigChange::try_deserialize(&mut account.data.as_slice())?);
+        }
+    }
+
+    if pending_changes.is_empty() {
+        println!("{} No pending configuration changes", ui::OK);
+        return Ok(());
+    }
+
+    let current_timestamp = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs() as i64;
+    for pending_change in pending_changes {
+        let status = if current_timestamp >= pending_change.executable_after {
+            "executable".to_string()
+        } else {
+            format!("executable in {} seconds", pending_change.executable_after - current_timestamp)
+        };
+        println!(
+            "{} Change {} queued by {}, executable after {} ({})",
+            ui::BULLET,
+            pending_change.change_id,
+            pending_change.queued_by,
+            pending_change.executable_after,
+            status
+        );
+        println!("{:#?}", pending_change.input);
+    }
+    Ok(())
+}
+
+fn configuration_input(admin_config: AdminConfig) -> ConfigurationRegistryInput {
+    ConfigurationRegistryInput {
+        oracle_pubkey: Some(admin_config.oracle_pubkey),
+        sol_quantity: Some(admin_config.sol_quantity),
+        price_maximum_age: Some(admin_config.price_maximum_age),
+        coefficient: Some(admin_config.coefficient),
+        max_discount_rate: Some(admin_config.max_discount_rate),
+        min_discount_rate: Some(admin_config.min_discount_rate),
+        min_sol_quantity: Some(admin_config.min_sol_quantity),
+        max_sol_quantity: Some(admin_config.max_sol_quantity),
+        oracle_pubkeys: admin_config.oracle_pubkeys,
+        oracle_quorum_threshold: admin_config.oracle_quorum_threshold,
+        disable_v1_attestations: admin_config.disable_v1_attestations,
+        attestation_timestamp_tolerance: admin_config.attestation_timestamp_tolerance,
+        max_trades_per_wallet_per_epoch: admin_config.max_trades_per_wallet_per_epoch,
+        max_sol_per_wallet_per_epoch: admin_config.max_sol_per_wallet_per_epoch,
+        epoch_sol_cap: admin_config.epoch_sol_cap,
+        config_change_delay: admin_config.config_change_delay,
+        fills_high_watermark_bps: admin_config.fills_high_watermark_bps,
+        fills_low_watermark_bps: admin_config.fills_low_watermark_bps,
+        stop_buys_at_high_watermark: admin_config.stop_buys_at_high_watermark,
+        discount_curve: admin_config.discount_curve,
+        discount_clock: admin_config.discount_clock,
+        discount_memory_bps: admin_config.discount_memory_bps,
+        min_swap_rate: admin_config.min_swap_rate,
+        max_swap_rate: admin_config.max_swap_rate,
+        max_swap_rate_deviation_bps: admin_config.max_swap_rate_deviation_bps,
+        reference_swap_rate_max_age: admin_config.reference_swap_rate_max_age,
+        max_confidence_bps: admin_config.max_confidence_bps,
+        confidence_widening_bps: admin_config.confidence_widening_bps,
+    }
+}
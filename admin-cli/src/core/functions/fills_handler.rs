@@ -0,0 +1,155 @@
+use std::{error::Error, str::FromStr};
+use anchor_client::{
+    solana_sdk::{
+        hash::hash,
+        instruction::{AccountMeta, Instruction},
+        pubkey::Pubkey,
+        signature::{Signer, Keypair},
+    },
+    solana_client::rpc_client::RpcClient
+};
+use cli_common::{
+    structs::{FillsRegistry, ProgramStateAccount},
+    transaction_executor::{get_account_data, send_batch_instructions, send_batch_instructions_with_signers},
+    utils::{env_var::load_payer_from_env, pda_helper, ui},
+};
+use crate::core::{
+    common::instruction::{
+        BEGIN_FILLS_UPGRADE_INSTRUCTION, UPGRADE_FILLS_INSTRUCTION,
+        BEGIN_FILLS_MIGRATION_INSTRUCTION, MIGRATE_FILLS_INSTRUCTION,
+        CLOSE_FILLS_REGISTRY_INSTRUCTION,
+    },
+    config::AdminConfig,
+};
+use solana_commitment_config::CommitmentConfig;
+use solana_system_interface::instruction::create_account;
+
+/// Moves the pending fills of a legacy (v1) registry into a fresh v2 registry,
+/// `batch_size` fills per transaction. An interrupted upgrade resumes where it stopped.
+pub fn upgrade_fills(batch_size: u32, fills_capacity: u64) -> Result<(), Box<dyn Error>> {
+    println!("{} Upgrade fills registry", ui::LABEL);
+    move_fills(BEGIN_FILLS_UPGRADE_INSTRUCTION, UPGRADE_FILLS_INSTRUCTION, batch_size, fills_capacity, None)?;
+    println!("{} Fills registry has been successfully upgraded", ui::OK);
+    Ok(())
+}
+
+/// Moves the pending fills into a fresh registry, `batch_size` fills per transaction,
+/// then closes the previous registry and sends its lamports to the recipient.
+pub fn migrate_fills(batch_size: u32, recipient: String, fills_capacity: u64) -> Result<(), Box<dyn Error>> {
+    println!("{} Migrate fills registry", ui::LABEL);
+    let recipient = Pubkey::from_str(&recipient)?;
+    move_fills(BEGIN_FILLS_MIGRATION_INSTRUCTION, MIGRATE_FILLS_INSTRUCTION, batch_size, fills_capacity, Some(recipient))?;
+    println!("{} Fills registry has been successfully migrated", ui::OK);
+    Ok(())
+}
+
+/// Closes an empty fills registry the program no longer uses.
+pub fn close_fills(fills_registry: String, recipient: String) -> Result<(), Box<dyn Error>> {
+    println!("{} Close fills registry", ui::LABEL);
+
+    let admin_config = AdminConfig::load_admin_config()?;
+    let program_id = Pubkey::from_str(&admin_config.program_id)?;
+    let payer = load_payer_from_env()?;
+    let fills_registry = Pubkey::from_str(&fills_registry)?;
+    let recipient = Pubkey::from_str(&recipient)?;
+
+    let close_ix = Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(payer.pubkey(), true),
+            AccountMeta::new_readonly(pda_helper::get_program_state_pda(program_id).0, false),
+            AccountMeta::new(fills_registry, false),
+            AccountMeta::new(recipient, false),
+        ],
+        data: hash(CLOSE_FILLS_REGISTRY_INSTRUCTION).to_bytes()[..8].to_vec(),
+    };
+    send_batch_instructions(vec![close_ix])?;
+
+    println!("{} Fills registry {} has been closed, lamports sent to {}", ui::OK, fills_registry, recipient);
+    Ok(())
+}
+
+/// Creates the new registry unless a move is already in progress, then moves fills
+/// until the program points at the new registry.
+fn move_fills(
+    begin_instruction: &[u8],
+    move_instruction: &[u8],
+    batch_size: u32,
+    fills_capacity: u64,
+    recipient: Option<Pubkey>,
+) -> Result<(), Box<dyn Error>> {
+    FillsRegistry::validate_capacity(fills_capacity)?;
+    let admin_config = AdminConfig::load_admin_config()?;
+    let program_id = Pubkey::from_str(&admin_config.program_id)?;
+    let payer = load_payer_from_env()?;
+
+    let program_state_pda = pda_helper::get_program_state_pda(program_id).0;
+    let program_state: ProgramStateAccount =
+        get_account_data(admin_config.rpc_url.clone(), program_state_pda)?;
+    let previous_fills_registry = program_state.fills_registry_address;
+
+    let fills_registry = if program_state.pending_fills_registry == Pubkey::default() {
+        let fills_account = Keypair::new();
+
+        let space = FillsRegistry::account_space(fills_capacity);
+        let rpc_client = RpcClient::new_with_commitment(admin_config.rpc_url.clone(), CommitmentConfig::confirmed());
+        let rent = rpc_client.get_minimum_balance_for_rent_exemption(space)?;
+
+        let create_fills_account_instruction = create_account(
+            &payer.pubkey(),
+            &fills_account.pubkey(),
+            rent,
+            space as u64,
+            &program_id,
+        );
+
+        let begin_ix = Instruction {
+            program_id,
+            accounts: vec![
+                AccountMeta::new_readonly(payer.pubkey(), true),
+                AccountMeta::new(program_state_pda, false),
+                AccountMeta::new_readonly(previous_fills_registry, false),
+                AccountMeta::new(fills_account.pubkey(), false),
+            ],
+            data: hash(begin_instruction).to_bytes()[..8].to_vec(),
+        };
+
+        send_batch_instructions_with_signers(
+            vec![create_fills_account_instruction, begin_ix],
+            &[&fills_account]
+        )?;
+        fills_account.pubkey()
+    } else {
+        println!("{} Resuming the move in progress", ui::BULLET);
+        program_state.pending_fills_registry
+    };
+    println!("{} New Fills Registry Address: {}", ui::BULLET, fills_registry);
+
+    let mut data = hash(move_instruction).to_bytes()[..8].to_vec();
+    data.extend_from_slice(&batch_size.to_le_bytes());
+
+    let mut accounts = vec![
+        AccountMeta::new_readonly(payer.pubkey(), true),
+        AccountMeta::new(program_state_pda, false),
+        AccountMeta::new(previous_fills_registry, false),
+        AccountMeta::new(fills_registry, false),
+    ];
+    if let Some(recipient) = recipient {
+        accounts.push(AccountMeta::new(recipient, false));
+    }
+
+    loop {
+        let move_ix = Instruction {
+            program_id,
+            accounts: accounts.clone(),
+            data: data.clone(),
+        };
+        send_batch_instructions(vec![move_ix])?;
+
+        let program_state: ProgramStateAccount =
+            get_account_data(admin_config.rpc_url.clone(), program_state_pda)?;
+        if program_state.fills_registry_address == fills_registry {
+            return Ok(());
+        }
+    }
+}
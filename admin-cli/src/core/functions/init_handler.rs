@@ -10,6 +10,7 @@ use anchor_client::{
     solana_client::rpc_client::RpcClient
 };
 use cli_common::{
+    structs::FillsRegistry,
     utils::ui,
     transaction_executor,
     utils::{env_var::load_payer_from_env, pda_helper},
@@ -21,14 +22,15 @@ use crate::core::{
 use solana_commitment_config::CommitmentConfig;
 use solana_system_interface::instruction::create_account;
 
-pub fn init() -> Result<(), Box<dyn Error>> {
+pub fn init(fills_capacity: u64) -> Result<(), Box<dyn Error>> {
+    FillsRegistry::validate_capacity(fills_capacity)?;
     let admin_config = AdminConfig::load_admin_config()?;
     let program_id = Pubkey::from_str(&admin_config.program_id)?;
 
     let payer = load_payer_from_env()?;
     let fills_account = Keypair::new();
 
-    let space = 10_485_760; // max size
+    let space = FillsRegistry::account_space(fills_capacity);
     let rpc_client = RpcClient::new_with_commitment(admin_config.rpc_url, CommitmentConfig::confirmed());
     let rent = rpc_client.get_minimum_balance_for_rent_exemption(space)?;
 
@@ -40,7 +42,7 @@ pub fn init() -> Result<(), Box<dyn Error>> {
         &program_id, // program id
     );
 
-    // Building instruction data
+    // Building instruction data, the fields of InitializeSystemInput in order
     let mut data_initialize = hash(INITIALIZE_SYSTEM_INSTRUCTION).to_bytes()[..8].to_vec();
     data_initialize = [
         data_initialize,
@@ -50,6 +52,8 @@ pub fn init() -> Result<(), Box<dyn Error>> {
         admin_config.coefficient.to_le_bytes().to_vec(),
         admin_config.max_discount_rate.to_le_bytes().to_vec(),
         admin_config.min_discount_rate.to_le_bytes().to_vec(),
+        admin_config.min_sol_quantity.to_le_bytes().to_vec(),
+        admin_config.max_sol_quantity.to_le_bytes().to_vec(),
     ].concat();
 
     // Getting necessary accounts
@@ -85,6 +89,7 @@ pub fn init() -> Result<(), Box<dyn Error>> {
         &[&fills_account]
     )?;
     println!("Fills Registry Address: {}", fills_account.pubkey());
+    println!("Fills Registry Capacity: {}", fills_capacity);
     println!("Configuration Registry PDA: {}", configuration_registry_pda);
     println!("Program State PDA: {}", program_state_pda);
     println!("Deny List Registry PDA: {}", deny_list_registry_pda);
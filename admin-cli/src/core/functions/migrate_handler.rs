@@ -0,0 +1,74 @@
+use std::{error::Error, str::FromStr};
+
+use anchor_client::{
+    anchor_lang::system_program,
+    solana_sdk::{
+        hash::hash,
+        instruction::{AccountMeta, Instruction},
+        pubkey::Pubkey,
+        signature::Signer,
+    },
+};
+use cli_common::{
+    utils::ui,
+    transaction_executor,
+    utils::{env_var::load_payer_from_env, pda_helper},
+};
+use crate::core::{
+    common::instruction::{
+        MIGRATE_CONFIGURATION_REGISTRY_INSTRUCTION,
+        MIGRATE_PROGRAM_STATE_INSTRUCTION
+    },
+    config::AdminConfig,
+};
+
+/// Grows the configuration registry to the layout of the upgraded program.
+pub fn migrate_configuration_registry() -> Result<(), Box<dyn Error>> {
+    println!("{} Migrate configuration registry", ui::LABEL);
+
+    let admin_config = AdminConfig::load_admin_config()?;
+    let program_id = Pubkey::from_str(&admin_config.program_id)?;
+    let payer = load_payer_from_env()?;
+
+    let configuration_registry_pda = pda_helper::get_configuration_registry_pda(program_id).0;
+    let program_state_pda = pda_helper::get_program_state_pda(program_id).0;
+
+    println!("Configuration registry PDA: {}", configuration_registry_pda);
+
+    let data = hash(MIGRATE_CONFIGURATION_REGISTRY_INSTRUCTION).to_bytes()[..8].to_vec();
+    let accounts = vec![
+        AccountMeta::new(configuration_registry_pda, false),
+        AccountMeta::new_readonly(program_state_pda, false),
+        AccountMeta::new(payer.pubkey(), true),
+        AccountMeta::new_readonly(system_program::ID, false),
+    ];
+
+    transaction_executor::send_batch_instructions(vec![Instruction { program_id, data, accounts }])?;
+    println!("{} Configuration registry has been migrated", ui::OK);
+    Ok(())
+}
+
+/// Grows the program state to the layout of the upgraded program. Runs before
+/// the configuration registry migration, which reads the program state.
+pub fn migrate_program_state() -> Result<(), Box<dyn Error>> {
+    println!("{} Migrate program state", ui::LABEL);
+
+    let admin_config = AdminConfig::load_admin_config()?;
+    let program_id = Pubkey::from_str(&admin_config.program_id)?;
+    let payer = load_payer_from_env()?;
+
+    let program_state_pda = pda_helper::get_program_state_pda(program_id).0;
+
+    println!("Program state PDA: {}", program_state_pda);
+
+    let data = hash(MIGRATE_PROGRAM_STATE_INSTRUCTION).to_bytes()[..8].to_vec();
+    let accounts = vec![
+        AccountMeta::new(program_state_pda, false),
+        AccountMeta::new(payer.pubkey(), true),
+        AccountMeta::new_readonly(system_program::ID, false),
+    ];
+
+    transaction_executor::send_batch_instructions(vec![Instruction { program_id, data, accounts }])?;
+    println!("{} Program state has been migrated", ui::OK);
+    Ok(())
+}
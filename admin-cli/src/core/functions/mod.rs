@@ -4,4 +4,8 @@ pub mod init_handler;
 pub mod set_fills_consumer;
 pub mod deny_list;
 pub mod admin_handler;
-pub mod mock_token_handler;
\ No newline at end of file
+pub mod mock_token_handler;
+pub mod oracle_rotation;
+pub mod fills_handler;
+pub mod migrate_handler;
+
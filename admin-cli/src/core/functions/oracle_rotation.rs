@@ -0,0 +1,97 @@
+use std::{
+    error::Error,
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH}
+};
+
+use anchor_client::solana_sdk::{
+    hash::hash,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Signer,
+};
+
+use cli_common::{
+    structs::ConfigurationRegistry,
+    transaction_executor::{self, get_account_data},
+    utils::{env_var::load_payer_from_env, pda_helper, ui},
+};
+use crate::core::{
+    common::instruction::ROTATE_ORACLE_KEY_INSTRUCTION,
+    config::AdminConfig,
+};
+
+pub fn rotate_oracle_key(
+    next_oracle_pubkey: Option<String>,
+    activation_timestamp: Option<i64>,
+    retire_timestamp: Option<i64>,
+) -> Result<(), Box<dyn Error>> {
+    let admin_config: AdminConfig = AdminConfig::load_admin_config()?;
+    let program_id = Pubkey::from_str(&admin_config.program_id)?;
+    let configuration_registry_pda = pda_helper::get_configuration_registry_pda(program_id).0;
+    let current_timestamp = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs() as i64;
+    let config_registry: ConfigurationRegistry =
+        get_account_data(admin_config.rpc_url.clone(), configuration_registry_pda)?;
+
+    if let Some(next_oracle_pubkey) = next_oracle_pubkey {
+        let payer = load_payer_from_env()?;
+        let next_oracle_pub_key = Pubkey::from_str(&next_oracle_pubkey)?;
+        // The next key cannot activate before the config change delay has passed.
+        let activation_timestamp = activation_timestamp
+            .unwrap_or(current_timestamp + config_registry.config_change_delay as i64);
+        let retire_timestamp = retire_timestamp.ok_or("Missing retire timestamp")?;
+
+        // Building instruction data
+        let mut data = hash(ROTATE_ORACLE_KEY_INSTRUCTION).to_bytes()[..8].to_vec();
+        data.extend_from_slice(next_oracle_pub_key.as_ref());
+        data.extend_from_slice(&activation_timestamp.to_le_bytes());
+        data.extend_from_slice(&retire_timestamp.to_le_bytes());
+
+        let program_state_pda = pda_helper::get_program_state_pda(program_id).0;
+
+        println!("Configuration registry PDA: {}", configuration_registry_pda);
+        println!("Program state PDA: {}", program_state_pda);
+
+        let accounts = vec![
+            AccountMeta::new(configuration_registry_pda, false),
+            AccountMeta::new_readonly(program_state_pda, false),
+            AccountMeta::new(payer.pubkey(), true),
+        ];
+
+        let rotate_oracle_key_ix = Instruction {
+            program_id,
+            data,
+            accounts,
+        };
+
+        transaction_executor::send_batch_instructions(vec![rotate_oracle_key_ix])?;
+        println!("{} Oracle key rotation has been scheduled", ui::OK);
+    }
+
+    let config_registry: ConfigurationRegistry =
+        get_account_data(admin_config.rpc_url, configuration_registry_pda)?;
+    print_rotation_status(&config_registry, current_timestamp);
+    Ok(())
+}
+
+fn print_rotation_status(config_registry: &ConfigurationRegistry, current_timestamp: i64) {
+    println!("{} Current oracle key: {}", ui::LABEL, config_registry.oracle_pubkey);
+
+    if config_registry.next_oracle_pubkey == Pubkey::default() {
+        println!("{} No oracle key rotation scheduled", ui::BULLET);
+        return;
+    }
+
+    let status = if current_timestamp < config_registry.next_oracle_activation_timestamp {
+        "Scheduled, only the current key is accepted"
+    } else if current_timestamp < config_registry.oracle_retire_timestamp {
+        "Overlap, both keys are accepted"
+    } else {
+        "Completed, only the next key is accepted"
+    };
+
+    println!("{} Next oracle key: {}", ui::LABEL, config_registry.next_oracle_pubkey);
+    println!("{} Activation timestamp: {}", ui::LABEL, config_registry.next_oracle_activation_timestamp);
+    println!("{} Retire timestamp: {}", ui::LABEL, config_registry.oracle_retire_timestamp);
+    println!("{} Rotation status: {}", ui::BULLET, status);
+}
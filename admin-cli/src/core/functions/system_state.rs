@@ -5,17 +5,50 @@ use anchor_client::{
     },
 };
 use cli_common::{
-    structs::ProgramStateAccount,
+    common_functions::{
+        epoch_capacity::print_epoch_sol_capacity,
+        fills_utilization::print_fills_utilization,
+    },
+    constant::{PAUSE_BUYS, PAUSE_DEQUEUES, PAUSE_QUOTES},
+    structs::{OraclePriceAccount, ProgramStateAccount},
     transaction_executor::{get_account_data, send_batch_instructions},
     utils::{env_var::load_payer_from_env, pda_helper, ui},
 };
 use std::{error::Error, str::FromStr};
 
 use crate::core::{
-    common::{error::INVALID_ARGUMENTS, instruction::TOGGLE_SYSTEM_STATE_INSTRUCTION},
+    common::{
+        error::INVALID_ARGUMENTS,
+        instruction::{
+            HALT_SYSTEM_INSTRUCTION, SET_PAUSE_FLAGS_INSTRUCTION, TOGGLE_SYSTEM_STATE_INSTRUCTION,
+        },
+    },
     config::AdminConfig,
 };
 
+/// Features selected on the command line, none selected means the whole system.
+pub struct PauseFeatures {
+    pub buys: bool,
+    pub dequeues: bool,
+    pub quotes: bool,
+}
+
+impl PauseFeatures {
+    fn flags(&self) -> u8 {
+        let mut flags = 0;
+        if self.buys {
+            flags |= PAUSE_BUYS;
+        }
+        if self.dequeues {
+            flags |= PAUSE_DEQUEUES;
+        }
+        if self.quotes {
+            flags |= PAUSE_QUOTES;
+        }
+        flags
+    }
+}
+
 pub fn view_system_state() -> Result<(), Box<dyn Error>> {
     println!("{} View system state", ui::LABEL);
 
@@ -24,7 +57,7 @@ pub fn view_system_state() -> Result<(), Box<dyn Error>> {
 
     let program_state_pda = pda_helper::get_program_state_pda(program_id).0;
     let program_state: ProgramStateAccount =
-        get_account_data(admin_config.rpc_url, program_state_pda)?;
+        get_account_data(admin_config.rpc_url.clone(), program_state_pda)?;
 
     println!(
         "{} Current system state: {}",
@@ -35,14 +68,62 @@ pub fn view_system_state() -> Result<(), Box<dyn Error>> {
             "🟢 Active"
         }
     );
+    for (feature, flag) in [("Buys", PAUSE_BUYS), ("Dequeues", PAUSE_DEQUEUES), ("Quotes", PAUSE_QUOTES)] {
+        println!(
+            "{} {}: {}",
+            ui::BULLET,
+            feature,
+            if program_state.pause_flags & flag != 0 {
+                "⏸ Paused"
+            } else {
+                "🟢 Active"
+            }
+        );
+    }
+    if program_state.guardian == Pubkey::default() {
+        println!("{} Guardian: not set", ui::BULLET);
+    } else {
+        println!("{} Guardian: {}", ui::BULLET, program_state.guardian);
+    }
+    if program_state.pending_fills_registry != Pubkey::default() {
+        println!("{} Fills registry migration in progress to: {}", ui::BULLET, program_state.pending_fills_registry);
+    }
+    if program_state.reference_swap_rate == 0 {
+        println!("{} Reference swap rate: not set until the first trade", ui::BULLET);
+    } else {
+        println!("{} Reference swap rate: {}", ui::BULLET, program_state.reference_swap_rate);
+    }
+    // The oracle price account is created by the first posted price.
+    let oracle_price_pda = pda_helper::get_oracle_price_pda(program_id).0;
+    match get_account_data::<OraclePriceAccount>(admin_config.rpc_url.clone(), oracle_price_pda) {
+        Ok(oracle_price) => println!(
+            "{} Posted swap rate: {} (timestamp {}, confidence {}, by {})",
+            ui::BULLET,
+            oracle_price.swap_rate,
+            oracle_price.timestamp,
+            oracle_price.confidence,
+            oracle_price.oracle_pubkey
+        ),
+        Err(_) => println!("{} Posted swap rate: none", ui::BULLET),
+    }
+    print_epoch_sol_capacity(admin_config.rpc_url.clone(), program_id)?;
+    print_fills_utilization(admin_config.rpc_url, program_id)?;
 
     Ok(())
 }
 
-pub fn toggle_system_state(activate: bool, pause: bool) -> Result<(), Box<dyn Error>> {
+pub fn toggle_system_state(
+    activate: bool,
+    pause: bool,
+    features: PauseFeatures,
+) -> Result<(), Box<dyn Error>> {
     println!("{} Toggle system state", ui::LABEL);
 
     let set_to = validate_and_extract_user_input(activate, pause)?;
+    let feature_flags = features.flags();
+    if feature_flags != 0 {
+        return set_pause_flags(set_to, feature_flags);
+    }
 
     let admin = load_payer_from_env()?;
 
@@ -78,6 +159,81 @@ pub fn toggle_system_state(activate: bool, pause: bool) -> Result<(), Box<dyn Er
     Ok(())
 }
 
+pub fn halt_system() -> Result<(), Box<dyn Error>> {
+    println!("{} Halt system", ui::LABEL);
+
+    // Either the admin or the guardian keypair can be loaded here.
+    let signer = load_payer_from_env()?;
+
+    let admin_config = AdminConfig::load_admin_config()?;
+    let program_id = Pubkey::from_str(&admin_config.program_id)?;
+
+    let program_state_pda = pda_helper::get_program_state_pda(program_id).0;
+
+    println!("Program state PDA: {}", program_state_pda);
+
+    let data = hash(HALT_SYSTEM_INSTRUCTION).to_bytes()[..8].to_vec();
+
+    let accounts = vec![
+        AccountMeta::new(signer.pubkey(), true),
+        AccountMeta::new(program_state_pda, false),
+    ];
+
+    let ix = Instruction {
+        program_id,
+        accounts,
+        data,
+    };
+
+    send_batch_instructions(vec![ix])?;
+
+    println!("{} System state set to \"⏸ Paused\"", ui::OK);
+    Ok(())
+}
+
+fn set_pause_flags(pause: bool, feature_flags: u8) -> Result<(), Box<dyn Error>> {
+    let admin = load_payer_from_env()?;
+
+    let admin_config = AdminConfig::load_admin_config()?;
+    let program_id = Pubkey::from_str(&admin_config.program_id)?;
+
+    let program_state_pda = pda_helper::get_program_state_pda(program_id).0;
+    let program_state: ProgramStateAccount =
+        get_account_data(admin_config.rpc_url, program_state_pda)?;
+
+    let pause_flags = if pause {
+        program_state.pause_flags | feature_flags
+    } else {
+        program_state.pause_flags & !feature_flags
+    };
+
+    println!("Program state PDA: {}", program_state_pda);
+    println!("Setting pause flags from {:#05b} to {:#05b}", program_state.pause_flags, pause_flags);
+
+    let mut data = hash(SET_PAUSE_FLAGS_INSTRUCTION).to_bytes()[..8].to_vec();
+    data = [data, vec![pause_flags]].concat();
+
+    let accounts = vec![
+        AccountMeta::new(admin.pubkey(), true),
+        AccountMeta::new(program_state_pda, false),
+    ];
+
+    let ix = Instruction {
+        program_id,
+        accounts,
+        data,
+    };
+
+    send_batch_instructions(vec![ix])?;
+
+    println!(
+        "{} Selected features set to {:?}",
+        ui::OK,
+        if pause { "⏸ Paused" } else { "🟢 Active" }
+    );
+    Ok(())
+}
+
 fn validate_and_extract_user_input(activate: bool, pause: bool) -> Result<bool, Box<dyn Error>> {
     // checking whether user has provided both active and pause as input.
     if activate && pause {
@@ -13,7 +13,10 @@ use crate::{
             init_handler,
             system_state,
             set_fills_consumer,
-            mock_token_handler
+            mock_token_handler,
+            oracle_rotation,
+            fills_handler,
+            migrate_handler
         },
     },
 };
@@ -30,8 +33,8 @@ pub fn handle() -> Result<(), Box<dyn Error>> {
     match cli.command {
 
         // Initializing the system
-        Some(Commands::Init) => {
-            init_handler::init()
+        Some(Commands::Init { fills_capacity }) => {
+            init_handler::init(fills_capacity)
         }
 
         // Displays current configuration registry contents.
@@ -44,14 +47,45 @@ pub fn handle() -> Result<(), Box<dyn Error>> {
             config_handler::update_config()
         }
 
+        // Queues a timelocked configuration change using values from root directory config.json.
+        Some(Commands::QueueConfig) => {
+            config_handler::queue_config()
+        }
+
+        // Executes a queued configuration change
+        Some(Commands::ExecuteConfig { change_id }) => {
+            config_handler::execute_config(change_id)
+        }
+
+        // Cancels a queued configuration change
+        Some(Commands::CancelConfig { change_id }) => {
+            config_handler::cancel_config(change_id)
+        }
+
+        // Displays queued configuration changes
+        Some(Commands::ViewPendingConfigs) => {
+            config_handler::view_pending_configs()
+        }
+
         // Viewing current system state
         Some(Commands::ViewSystemState) => {
             system_state::view_system_state()
         }
 
         // Changing system state between active and pause
-        Some(Commands::ToggleSystemState { activate, pause}) => {
-            system_state::toggle_system_state(activate, pause)
+        Some(Commands::ToggleSystemState { activate, pause, buys, dequeues, quotes }) => {
+            let features = system_state::PauseFeatures { buys, dequeues, quotes };
+            system_state::toggle_system_state(activate, pause, features)
+        }
+
+        // Halting the system with the admin or guardian keypair
+        Some(Commands::Halt) => {
+            system_state::halt_system()
+        }
+
+        // Scheduling an oracle key rotation
+        Some(Commands::RotateOracleKey { next_oracle_pubkey, activation_timestamp, retire_timestamp }) => {
+            oracle_rotation::rotate_oracle_key(next_oracle_pubkey, activation_timestamp, retire_timestamp)
         }
 
         Some(Commands::SetFillsConsumer { fills_consumer}) => {
@@ -83,6 +117,41 @@ pub fn handle() -> Result<(), Box<dyn Error>> {
             admin_handler::set_deny_authority(authority)
         }
 
+        // Setting the guardian of the system
+        Some(Commands::SetGuardian { guardian }) => {
+            admin_handler::set_guardian(guardian)
+        }
+
+        // Proposing a new admin
+        Some(Commands::ProposeAdmin { admin }) => {
+            admin_handler::propose_admin(admin)
+        }
+
+        // Accepting the pending admin transfer
+        Some(Commands::AcceptAdmin) => {
+            admin_handler::accept_admin()
+        }
+
+        // Cancelling the pending admin transfer
+        Some(Commands::CancelAdminTransfer) => {
+            admin_handler::cancel_admin_transfer()
+        }
+
+        // Proposing a new deny list authority
+        Some(Commands::ProposeDenyAuthority { authority }) => {
+            admin_handler::propose_deny_authority(authority)
+        }
+
+        // Accepting the pending deny list authority transfer
+        Some(Commands::AcceptDenyAuthority) => {
+            admin_handler::accept_deny_authority()
+        }
+
+        // Cancelling the pending deny list authority transfer
+        Some(Commands::CancelDenyAuthorityTransfer) => {
+            admin_handler::cancel_deny_authority_transfer()
+        }
+
         // Initializing the mock transfer program
         Some(Commands::InitMockProgram) => {
             mock_token_handler::init()
@@ -104,8 +173,33 @@ pub fn handle() -> Result<(), Box<dyn Error>> {
         }
 
         // View Fills Registry
-        Some(Commands::ViewFills) => {
-            view_fills_registry()
+        Some(Commands::ViewFills { offset, limit }) => {
+            view_fills_registry(offset, limit)
+        }
+
+        // Upgrade a legacy fills registry
+        Some(Commands::UpgradeFills { batch_size, fills_capacity }) => {
+            fills_handler::upgrade_fills(batch_size, fills_capacity)
+        }
+
+        // Migrate the fills to a new registry
+        Some(Commands::MigrateFills { batch_size, recipient, fills_capacity }) => {
+            fills_handler::migrate_fills(batch_size, recipient, fills_capacity)
+        }
+
+        // Close an unused fills registry
+        Some(Commands::CloseFills { fills_registry, recipient }) => {
+            fills_handler::close_fills(fills_registry, recipient)
+        }
+
+        // Grow the configuration registry after a program upgrade
+        Some(Commands::MigrateConfigRegistry) => {
+            migrate_handler::migrate_configuration_registry()
+        }
+
+        // Grow the program state after a program upgrade
+        Some(Commands::MigrateProgramState) => {
+            migrate_handler::migrate_program_state()
         }
 
         None => {
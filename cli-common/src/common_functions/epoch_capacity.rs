@@ -0,0 +1,48 @@
+use std::error::Error;
+use anchor_client::{
+    anchor_lang::prelude::Pubkey,
+    solana_client::rpc_client::RpcClient
+};
+use crate::{
+    structs::{ConfigurationRegistry, ProgramStateAccount},
+    transaction_executor::get_account_data,
+    utils::{pda_helper, ui, fixed_point_utils::convert_sol_value},
+};
+use solana_commitment_config::CommitmentConfig;
+
+/// Prints the SOL sold in the current epoch against the configured epoch cap.
+pub fn print_epoch_sol_capacity(rpc_url: String, program_id: Pubkey) -> Result<(), Box<dyn Error>> {
+    let config_registry: ConfigurationRegistry = get_account_data(
+        rpc_url.clone(),
+        pda_helper::get_configuration_registry_pda(program_id).0
+    )?;
+    let program_state: ProgramStateAccount = get_account_data(
+        rpc_url.clone(),
+        pda_helper::get_program_state_pda(program_id).0
+    )?;
+
+    let client = RpcClient::new_with_commitment(rpc_url, CommitmentConfig::confirmed());
+    let epoch = client.get_epoch_info()?.epoch;
+
+    // The on-chain counter restarts with the first trade of a new epoch.
+    let sol_sold = if program_state.current_epoch == epoch { program_state.epoch_sol_sold } else { 0 };
+
+    if config_registry.epoch_sol_cap == 0 {
+        println!(
+            "{} SOL sold in epoch {}: {} SOL (no epoch cap)",
+            ui::OK,
+            epoch,
+            convert_sol_value(sol_sold)
+        );
+    } else {
+        println!(
+            "{} SOL sold in epoch {} \n Sold: {} SOL \n Cap: {} SOL \n Remaining: {} SOL",
+            ui::OK,
+            epoch,
+            convert_sol_value(sol_sold),
+            convert_sol_value(config_registry.epoch_sol_cap),
+            convert_sol_value(config_registry.epoch_sol_cap.saturating_sub(sol_sold))
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,57 @@
+use std::error::Error;
+use anchor_client::{
+    anchor_lang::prelude::Pubkey,
+    solana_client::rpc_client::RpcClient
+};
+use crate::{
+    common_functions::view_fills::fetch_fills_registry_header,
+    structs::{ConfigurationRegistry, ProgramStateAccount},
+    transaction_executor::get_account_data,
+    utils::{pda_helper, ui},
+};
+use solana_commitment_config::CommitmentConfig;
+
+/// Prints the fills registry utilization against the configured watermarks.
+pub fn print_fills_utilization(rpc_url: String, program_id: Pubkey) -> Result<(), Box<dyn Error>> {
+    let config_registry: ConfigurationRegistry = get_account_data(
+        rpc_url.clone(),
+        pda_helper::get_configuration_registry_pda(program_id).0
+    )?;
+    let program_state: ProgramStateAccount = get_account_data(
+        rpc_url.clone(),
+        pda_helper::get_program_state_pda(program_id).0
+    )?;
+
+    let client = RpcClient::new_with_commitment(rpc_url, CommitmentConfig::confirmed());
+    let fills_registry = fetch_fills_registry_header(&client, &program_state.fills_registry_address)?;
+    let utilization = fills_registry.count as f64 * 100.0 / fills_registry.capacity.max(1) as f64;
+
+    if config_registry.fills_high_watermark_bps == 0 {
+        println!(
+            "{} Fills registry utilization: {:.2}% ({} of {} fills, no watermarks)",
+            ui::OK,
+            utilization,
+            fills_registry.count,
+            fills_registry.capacity
+        );
+        return Ok(());
+    }
+
+    let status = match (program_state.fills_above_high_watermark, config_registry.stop_buys_at_high_watermark) {
+        (false, _) => "🟢 Below high watermark",
+        (true, false) => "⚠ Above high watermark, buys continue",
+        (true, true) => "⏸ Above high watermark, buys stopped",
+    };
+    println!(
+        "{} Fills registry utilization: {:.2}% ({} of {} fills) \n High watermark: {:.2}% \n Low watermark: {:.2}% \n Stop buys at high watermark: {} \n Status: {}",
+        ui::OK,
+        utilization,
+        fills_registry.count,
+        fills_registry.capacity,
+        config_registry.fills_high_watermark_bps as f64 / 100.0,
+        config_registry.fills_low_watermark_bps as f64 / 100.0,
+        config_registry.stop_buys_at_high_watermark,
+        status
+    );
+    Ok(())
+}
@@ -1 +1,3 @@
-pub mod view_fills;
\ No newline at end of file
+pub mod view_fills;
+pub mod fills_utilization;
+pub mod epoch_capacity;
\ No newline at end of file
@@ -1,40 +1,43 @@
 use std::{error::Error, str::FromStr};
 use anchor_client::{
     anchor_lang::prelude::Pubkey,
-    solana_client::rpc_client::RpcClient
+    solana_account_decoder::{UiAccountEncoding, UiDataSliceConfig},
+    solana_client::{rpc_client::RpcClient, rpc_config::RpcAccountInfoConfig}
 };
 use crate::{
-    constant::{MAX_FILLS_QUEUE_SIZE},
-    structs::FillsRegistry,
+    common_functions::fills_utilization::print_fills_utilization,
+    constant::LEGACY_FILLS_REGISTRY_DISCRIMINATOR,
+    structs::{FillsRegistry, Fill},
     utils::{pda_helper, ui, fixed_point_utils::{convert_sol_value, convert_token_value}},
     config::Config
 };
 use solana_commitment_config::CommitmentConfig;
 
-pub fn view_fills_registry() -> Result<(), Box<dyn Error>> {
+const DISCRIMINATOR_SIZE: usize = 8;
+
+pub fn view_fills_registry(offset: u64, limit: Option<u64>) -> Result<(), Box<dyn Error>> {
     let config = Config::load()?;
     let program_id = Pubkey::from_str(&config.program_id)?;
 
     let fills_registry_address = pda_helper::get_fills_registry_address(program_id, config.rpc_url.clone())?;
     println!("Fills Registry Address: {}", fills_registry_address);
 
-    // Fetch the raw account data
-    let client = RpcClient::new_with_commitment(config.rpc_url, CommitmentConfig::confirmed());
-    let account_data = client.get_account_data(&fills_registry_address)?;
-    let account_data_slice = &account_data[8..];
-    let fills_registry_size = size_of::<FillsRegistry>();
-    let fills_registry_bytes = &account_data_slice[..fills_registry_size];
-    let fills_registry: &FillsRegistry = bytemuck::from_bytes(fills_registry_bytes);
+    // Fetch the header only, the fills are fetched page by page below
+    let client = RpcClient::new_with_commitment(config.rpc_url.clone(), CommitmentConfig::confirmed());
+    let fills_registry = fetch_fills_registry_header(&client, &fills_registry_address)?;
 
     println!("{} Successfully Fetched Fill Registry", ui::OK);
     println!("{} Fill Registry Statistics", ui::LABEL);
     println!("{} Total Unprocessed Fills {}", ui::BULLET, fills_registry.count);
     println!("{} Head: {}", ui::BULLET, fills_registry.head);
     println!("{} Tail: {}", ui::BULLET, fills_registry.tail);
+    println!("{} Capacity: {}", ui::BULLET, fills_registry.capacity);
+    println!("{} Next Fill Sequence: {}", ui::BULLET, fills_registry.next_fill_sequence);
     println!("{} Total Unprocessed SOL Volume {}, In Lamports {}",
              ui::BULLET, convert_sol_value(fills_registry.total_sol_pending), fills_registry.total_sol_pending);
     println!("{} Total Unprocessed 2Z Volume {}, With Decimals {}",
              ui::BULLET, convert_token_value(fills_registry.total_2z_pending), fills_registry.total_2z_pending);
+    print_fills_utilization(config.rpc_url, program_id)?;
     println!("\n");
     println!("{} Pending Fills", ui::LABEL);
 
@@ -42,13 +45,83 @@ pub fn view_fills_registry() -> Result<(), Box<dyn Error>> {
         println!("{} No Pending Fills found", ui::BULLET);
         return Ok(());
     }
-    // Iterate over pending fills
-    for i in 0..fills_registry.count as usize {
-        // Circular queue indexing
-        let idx = (fills_registry.head as usize + i) % MAX_FILLS_QUEUE_SIZE;
-        let fill = &fills_registry.fills[idx];
+
+    let start = offset.min(fills_registry.count);
+    let end = match limit {
+        Some(limit) => start.saturating_add(limit).min(fills_registry.count),
+        None => fills_registry.count,
+    };
+    if start == end {
+        println!("{} No Pending Fills at offset {}", ui::BULLET, offset);
+        return Ok(());
+    }
+
+    // Circular queue indexing, a page wraps around the end of the account at most once
+    let first = (fills_registry.head + start) % fills_registry.capacity;
+    let page_len = end - start;
+    let before_wrap = page_len.min(fills_registry.capacity - first);
+    let mut fills = fetch_fills(&client, &fills_registry_address, first, before_wrap)?;
+    if before_wrap < page_len {
+        fills.extend(fetch_fills(&client, &fills_registry_address, 0, page_len - before_wrap)?);
+    }
+
+    for (i, fill) in (start..end).zip(fills.iter()) {
         let fill_quantity = convert_sol_value(fill.sol_in);
-        println!("Fill {}: sol_in:{} token_2z_out:{}", i, fill_quantity, fill.token_2z_out);
+        println!(
+            "Fill {}: sequence:{} sol_in:{} token_2z_out:{} ask_price:{} buyer:{} slot:{} timestamp:{}",
+            i, fill.sequence, fill_quantity, fill.token_2z_out, fill.ask_price, fill.buyer, fill.slot, fill.timestamp
+        );
+    }
+    if end < fills_registry.count {
+        println!("{} Showing fills {} to {} of {}, use --offset {} for the next page",
+                 ui::BULLET, start, end - 1, fills_registry.count, end);
     }
     Ok(())
-}
\ No newline at end of file
+}
+
+/// Fetches the registry header without the fills.
+pub fn fetch_fills_registry_header(
+    client: &RpcClient,
+    fills_registry_address: &Pubkey,
+) -> Result<FillsRegistry, Box<dyn Error>> {
+    let header_data = fetch_data_slice(client, fills_registry_address, 0, DISCRIMINATOR_SIZE + size_of::<FillsRegistry>())?;
+    if header_data[..DISCRIMINATOR_SIZE] == LEGACY_FILLS_REGISTRY_DISCRIMINATOR {
+        return Err("Fills registry still uses the legacy layout, run admin-cli upgrade-fills first".into());
+    }
+    Ok(bytemuck::pod_read_unaligned(&header_data[DISCRIMINATOR_SIZE..]))
+}
+
+/// Fetches `count` fills starting at index `first` of the fills array.
+fn fetch_fills(
+    client: &RpcClient,
+    fills_registry_address: &Pubkey,
+    first: u64,
+    count: u64,
+) -> Result<Vec<Fill>, Box<dyn Error>> {
+    let offset = DISCRIMINATOR_SIZE + size_of::<FillsRegistry>() + first as usize * size_of::<Fill>();
+    let data = fetch_data_slice(client, fills_registry_address, offset, count as usize * size_of::<Fill>())?;
+    Ok(data.chunks_exact(size_of::<Fill>()).map(bytemuck::pod_read_unaligned).collect())
+}
+
+/// Fetches `length` bytes of the account data from `offset`, instead of the whole account.
+fn fetch_data_slice(
+    client: &RpcClient,
+    address: &Pubkey,
+    offset: usize,
+    length: usize,
+) -> Result<Vec<u8>, Box<dyn Error>> {
+    let account_config = RpcAccountInfoConfig {
+        encoding: Some(UiAccountEncoding::Base64),
+        data_slice: Some(UiDataSliceConfig { offset, length }),
+        commitment: Some(CommitmentConfig::confirmed()),
+        min_context_slot: None,
+    };
+    let account = client
+        .get_account_with_config(address, account_config)?
+        .value
+        .ok_or("Fills registry account not found")?;
+    if account.data.len() != length {
+        return Err("Fills registry account is smaller than expected".into());
+    }
+    Ok(account.data)
+}
@@ -4,7 +4,7 @@ use std::{
     path::Path,
     error::Error
 };
-use crate::{constant::CONFIG_FILE_PATH, utils::env_var::load_config_path_from_env};
+use crate::{constant::CONFIG_FILE_PATH, structs::{DiscountCurve, DiscountClock}, utils::env_var::load_config_path_from_env};
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct Config {
@@ -16,9 +16,32 @@ pub struct Config {
     pub sol_quantity: Option<u64>,
     pub price_maximum_age: Option<i64>,
     pub price_oracle_end_point: Option<String>,
+    pub oracle_pubkeys: Option<Vec<String>>,
+    pub oracle_quorum_threshold: Option<u8>,
+    pub price_oracle_end_points: Option<Vec<String>>,
+    pub disable_v1_attestations: Option<bool>,
+    pub attestation_timestamp_tolerance: Option<u32>,
+    pub max_trades_per_wallet_per_epoch: Option<u64>,
+    pub max_sol_per_wallet_per_epoch: Option<u64>,
+    pub epoch_sol_cap: Option<u64>,
+    pub config_change_delay: Option<u32>,
+    pub fills_high_watermark_bps: Option<u16>,
+    pub fills_low_watermark_bps: Option<u16>,
+    pub stop_buys_at_high_watermark: Option<bool>,
+    pub discount_curve: Option<DiscountCurve>,
+    pub discount_clock: Option<DiscountClock>,
+    pub discount_memory_bps: Option<u16>,
+    pub min_swap_rate: Option<u64>,
+    pub max_swap_rate: Option<u64>,
+    pub max_swap_rate_deviation_bps: Option<u16>,
+    pub reference_swap_rate_max_age: Option<u32>,
+    pub max_confidence_bps: Option<u16>,
+    pub confidence_widening_bps: Option<u16>,
     pub coefficient: Option<u64>,
     pub max_discount_rate: Option<u64>,
     pub min_discount_rate: Option<u64>,
+    pub min_sol_quantity: Option<u64>,
+    pub max_sol_quantity: Option<u64>,
 }
 
 impl Config {
@@ -7,4 +7,15 @@ pub const RETRY_DELAY: u64 = 2;
 pub const DECIMAL_PRECISION: u64 = 100;
 
 pub const TOKEN_UNITS: u64 = 100_000_000;
-pub const MAX_FILLS_QUEUE_SIZE: usize = 650000;
\ No newline at end of file
+pub const DEFAULT_FILLS_CAPACITY: u64 = 130_000;
+pub const MAX_ACCOUNT_SPACE: usize = 10_485_760;
+pub const LEGACY_FILLS_REGISTRY_DISCRIMINATOR: [u8; 8] = [13, 155, 1, 212, 199, 69, 251, 112];
+pub const ATTESTATION_V1: u8 = 1;
+pub const ATTESTATION_V2: u8 = 2;
+pub const ATTESTATION_V3: u8 = 3;
+pub const ATTESTATION_DOMAIN_TAG: &[u8] = b"doublezero:sol-2z-converter:attestation";
+pub const QUOTE_DIRECTION_2Z_TO_SOL: u8 = 0;
+
+pub const PAUSE_BUYS: u8 = 1 << 0;
+pub const PAUSE_DEQUEUES: u8 = 1 << 1;
+pub const PAUSE_QUOTES: u8 = 1 << 2;
@@ -2,6 +2,9 @@ pub const CONFIGURATION_REGISTRY_SEEDS: &[u8] = b"system_config";
 pub const PROGRAM_STATE_SEEDS: &[u8] = b"state";
 pub const WITHDRAW_SOL_AUTHORITY_SEEDS: &[u8] = b"withdraw_sol";
 pub const DENY_LIST_REGISTRY_SEEDS: &[u8] = b"deny_list";
+pub const TRADE_HISTORY_SEEDS: &[u8] = b"trade_history";
+pub const PENDING_CONFIG_CHANGE_SEEDS: &[u8] = b"pending_config";
+pub const ORACLE_PRICE_SEEDS: &[u8] = b"oracle_price";
 pub const MOCK_2Z_TOKEN_MINT_SEED: &[u8] = b"double_zero_mint";
 pub const MOCK_PROTOCOL_TREASURY_SEED: &[u8] = b"protocol_treasury";
 pub const MOCK_CONFIG_ACCOUNT: &[u8] = b"config";
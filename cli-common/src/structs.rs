@@ -3,7 +3,27 @@ use anchor_client::{
     solana_sdk::pubkey::Pubkey,
 };
 use bytemuck::{Pod, Zeroable};
-use crate::constant::MAX_FILLS_QUEUE_SIZE;
+use serde::{Deserialize, Serialize};
+use crate::constant::MAX_ACCOUNT_SPACE;
+
+/// Shape of the discount ramp, written in config.json as `"linear"` or for example
+/// `{"stepped": {"step_length": 100, "step_rate": 500}}`.
+#[derive(Debug, Clone, Copy, AnchorSerialize, AnchorDeserialize, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DiscountCurve {
+    Linear,
+    LinearWithGrace { grace_length: u64 },
+    Exponential { half_life: u64 },
+    Stepped { step_length: u64, step_rate: u64 },
+}
+
+/// Time base of the discount ramp, written in config.json as `"slot"` or `"unix_timestamp"`.
+#[derive(Debug, Clone, Copy, AnchorSerialize, AnchorDeserialize, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DiscountClock {
+    Slot,
+    UnixTimestamp,
+}
 
 #[derive(Debug, AnchorDeserialize)]
 pub struct ConfigurationRegistry {
@@ -14,6 +34,32 @@ pub struct ConfigurationRegistry {
     pub coefficient: u64,
     pub max_discount_rate: u64,
     pub min_discount_rate: u64,
+    pub min_sol_quantity: u64,
+    pub max_sol_quantity: u64,
+    pub oracle_pubkeys: Vec<Pubkey>,
+    pub oracle_quorum_threshold: u8,
+    pub next_oracle_pubkey: Pubkey,
+    pub next_oracle_activation_timestamp: i64,
+    pub oracle_retire_timestamp: i64,
+    pub disable_v1_attestations: bool,
+    pub attestation_timestamp_tolerance: u32,
+    pub max_trades_per_wallet_per_epoch: u64,
+    pub max_sol_per_wallet_per_epoch: u64,
+    pub epoch_sol_cap: u64,
+    pub config_change_delay: u32,
+    pub next_config_change_id: u64,
+    pub fills_high_watermark_bps: u16,
+    pub fills_low_watermark_bps: u16,
+    pub stop_buys_at_high_watermark: bool,
+    pub discount_curve: DiscountCurve,
+    pub discount_clock: DiscountClock,
+    pub discount_memory_bps: u16,
+    pub min_swap_rate: u64,
+    pub max_swap_rate: u64,
+    pub max_swap_rate_deviation_bps: u16,
+    pub reference_swap_rate_max_age: u32,
+    pub max_confidence_bps: u16,
+    pub confidence_widening_bps: u16,
 }
 
 impl AccountDeserialize for ConfigurationRegistry {
@@ -31,17 +77,47 @@ impl AccountDeserialize for ConfigurationRegistry {
 pub struct FillsRegistry {
     pub total_sol_pending: u64,      // Total SOL in not dequeued fills
     pub total_2z_pending: u64,       // Total 2Z in not dequeued fills
-    pub fills: [Fill; MAX_FILLS_QUEUE_SIZE],
     pub head: u64,   // index of oldest element
     pub tail: u64,   // index to insert next element
     pub count: u64,  // number of valid elements
+    pub next_fill_sequence: u64, // sequence number given to the next enqueued fill
+    pub capacity: u64, // number of fills following the header
+}
+
+impl FillsRegistry {
+    /// Account space of a registry holding `capacity` fills, discriminator included.
+    pub fn account_space(capacity: u64) -> usize {
+        8 + size_of::<FillsRegistry>() + capacity as usize * size_of::<Fill>()
+    }
+
+    /// Largest capacity whose registry fits in an account.
+    pub fn max_capacity() -> u64 {
+        ((MAX_ACCOUNT_SPACE - 8 - size_of::<FillsRegistry>()) / size_of::<Fill>()) as u64
+    }
+
+    /// Refuses a capacity whose registry would exceed the account size limit.
+    pub fn validate_capacity(capacity: u64) -> std::result::Result<(), Box<dyn std::error::Error>> {
+        if capacity > Self::max_capacity() {
+            return Err(format!(
+                "Fills capacity {} exceeds the maximum of {} fills that fit in an account",
+                capacity,
+                Self::max_capacity()
+            ).into());
+        }
+        Ok(())
+    }
 }
 
 #[derive(Clone, Copy, Pod, Zeroable)]
 #[repr(C)]
 pub struct Fill {
     pub sol_in: u64,
-    pub token_2z_out: u64
+    pub token_2z_out: u64,
+    pub ask_price: u64,
+    pub sequence: u64,
+    pub slot: u64,
+    pub timestamp: i64,
+    pub buyer: Pubkey,
 }
 
 #[derive(Debug, AnchorDeserialize)]
@@ -52,6 +128,18 @@ pub struct ProgramStateAccount {
     pub bump_registry: BumpRegistry,
     pub last_trade_slot: u64,
     pub deny_list_authority: Pubkey,
+    pub last_attestation_timestamp: i64,
+    pub current_epoch: u64,
+    pub epoch_sol_sold: u64,
+    pub pending_admin: Pubkey,
+    pub pending_deny_list_authority: Pubkey,
+    pub guardian: Pubkey,
+    pub pause_flags: u8,
+    pub pending_fills_registry: Pubkey,
+    pub fills_above_high_watermark: bool,
+    pub last_trade_timestamp: i64,
+    pub carried_discount_rate: u64,
+    pub reference_swap_rate: u64,
 }
 
 impl AccountDeserialize for ProgramStateAccount {
@@ -68,6 +156,37 @@ impl AccountDeserialize for ProgramStateAccount {
 pub struct TradeHistory {
     pub epoch: u64,
     pub num_of_trades: u64,
+    pub sol_bought: u64,
+}
+
+impl AccountDeserialize for TradeHistory {
+    fn try_deserialize(buf: &mut &[u8]) -> Result<Self> {
+        *buf = &buf[8..];
+        TradeHistory::try_deserialize_unchecked(buf)
+    }
+    fn try_deserialize_unchecked(buf: &mut &[u8]) -> Result<Self> {
+        TradeHistory::deserialize(buf).map_err(Into::into)
+    }
+}
+
+#[derive(Debug, AnchorDeserialize)]
+pub struct OraclePriceAccount {
+    pub swap_rate: u64,
+    pub timestamp: i64,
+    pub confidence: u64,
+    pub oracle_pubkey: Pubkey,
+    pub posted_slot: u64,
+    pub bump: u8,
+}
+
+impl AccountDeserialize for OraclePriceAccount {
+    fn try_deserialize(buf: &mut &[u8]) -> Result<Self> {
+        *buf = &buf[8..];
+        OraclePriceAccount::try_deserialize_unchecked(buf)
+    }
+    fn try_deserialize_unchecked(buf: &mut &[u8]) -> Result<Self> {
+        OraclePriceAccount::deserialize(buf).map_err(Into::into)
+    }
 }
 
 #[derive(Debug, AnchorDeserialize)]
@@ -75,4 +194,5 @@ pub struct BumpRegistry {
     pub configuration_registry_bump: u8,
     pub program_state_bump: u8,
     pub deny_list_registry_bump: u8,
+    pub withdraw_authority_bump: u8,
 }
\ No newline at end of file
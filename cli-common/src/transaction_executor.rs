@@ -100,6 +100,13 @@ pub fn send_batch_instructions_with_signers(
 
 pub fn send_instruction_with_return_data<T: ReturnData<T>>(
     instruction: Instruction,
+) -> Result<T, Box<dyn Error>> {
+    send_batch_instructions_with_return_data(vec![instruction])
+}
+
+/// Return data is read from the last instruction of the batch.
+pub fn send_batch_instructions_with_return_data<T: ReturnData<T>>(
+    instructions: Vec<Instruction>,
 ) -> Result<T, Box<dyn Error>> {
     let payer = load_payer_from_env()?;
 
@@ -117,7 +124,7 @@ pub fn send_instruction_with_return_data<T: ReturnData<T>>(
         .map_err(|_| "Error when getting latest block hash")?;
 
     let transaction = Transaction::new_signed_with_payer(
-        &[instruction],
+        &instructions,
         Some(&payer.pubkey()),
         &[&payer],
         recent_block_hash,
@@ -6,7 +6,8 @@ use crate::{
         CONFIGURATION_REGISTRY_SEEDS, DENY_LIST_REGISTRY_SEEDS,
         MOCK_2Z_TOKEN_MINT_SEED, MOCK_PROTOCOL_TREASURY_SEED,
         PROGRAM_STATE_SEEDS, MOCK_CONFIG_ACCOUNT,
-        MOCK_REVENUE_DISTRIBUTION_JOURNAL, WITHDRAW_SOL_AUTHORITY_SEEDS
+        MOCK_REVENUE_DISTRIBUTION_JOURNAL, WITHDRAW_SOL_AUTHORITY_SEEDS,
+        TRADE_HISTORY_SEEDS, PENDING_CONFIG_CHANGE_SEEDS, ORACLE_PRICE_SEEDS
     },
     transaction_executor::get_account_data,
 };
@@ -37,6 +38,27 @@ pub fn get_deny_list_registry_pda(program_id: Pubkey) -> (Pubkey, u8) {
     )
 }
 
+pub fn get_trade_history_pda(program_id: Pubkey, buyer: Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[TRADE_HISTORY_SEEDS, buyer.as_ref()],
+        &program_id,
+    )
+}
+
+pub fn get_pending_config_change_pda(program_id: Pubkey, change_id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[PENDING_CONFIG_CHANGE_SEEDS, &change_id.to_le_bytes()],
+        &program_id,
+    )
+}
+
+pub fn get_oracle_price_pda(program_id: Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[ORACLE_PRICE_SEEDS],
+        &program_id,
+    )
+}
+
 pub fn get_withdraw_authority_pda(program_id: Pubkey) -> (Pubkey, u8) {
     Pubkey::find_program_address(
         &[WITHDRAW_SOL_AUTHORITY_SEEDS],
@@ -9,6 +9,8 @@ export interface SystemConfig {
     coefficient: BN,
     maxDiscountRate: BN,
     minDiscountRate: BN,
+    minSolQuantity: BN,
+    maxSolQuantity: BN,
 }
 
 export interface SystemState {
@@ -8,6 +8,8 @@ export interface Config {
     sol_quantity: number;
     max_discount_rate: number;
     min_discount_rate: number;
+    min_sol_quantity: number;
+    max_sol_quantity: number;
     coefficient: number;
     price_maximum_age: number;
     price_oracle_end_point: string;
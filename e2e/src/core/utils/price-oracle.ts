@@ -1,8 +1,11 @@
+import {BN} from "@coral-xyz/anchor";
 import {getConfig} from "./config-util";
 
 export interface OraclePriceData {
+    version?: number; // absent for services signing legacy v1 attestations
     swapRate: number;
     timestamp: number;
+    confidence?: number; // absent for services signing v1 and v2 attestations
     signature: string;
     // solPriceUsd?: string;
     // twozPriceUsd?: string;
@@ -19,4 +22,17 @@ export const getOraclePriceData = async (): Promise<OraclePriceData> => {
         throw new Error(`Failed to fetch price oracle data: ${response.statusText}`);
     }
     return await response.json() as OraclePriceData;
-}
\ No newline at end of file
+}
+
+// Program instruction argument, signatures are passed as fixed 64 bytes (malformed ones zero padded).
+export const toOraclePriceInput = (oraclePriceData: OraclePriceData) => {
+    const signature = new Uint8Array(64);
+    signature.set(Buffer.from(oraclePriceData.signature, "base64").subarray(0, 64));
+    return {
+        version: oraclePriceData.version ?? 1,
+        swapRate: new BN(oraclePriceData.swapRate),
+        timestamp: new BN(oraclePriceData.timestamp),
+        confidence: new BN(oraclePriceData.confidence ?? 0),
+        signature: Array.from(signature),
+    };
+}
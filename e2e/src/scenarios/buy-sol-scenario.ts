@@ -13,7 +13,7 @@ import {CommonScenario} from "./common-scenario";
 import {assert, expect} from "chai";
 import {getConfig} from "../core/utils/config-util";
 import {getFillsRegistry, getFillsRegistryAccountAddress} from "../core/utils/fills-registry";
-import {OraclePriceData} from "../core/utils/price-oracle";
+import {OraclePriceData, toOraclePriceInput} from "../core/utils/price-oracle";
 import {AnchorError, BN} from "@coral-xyz/anchor";
 import {TOKEN_PROGRAM_ID} from "@solana/spl-token";
 
@@ -141,11 +141,8 @@ export class BuySolScenario extends CommonScenario {
         // manually call the buy sol command with the attestation
         const buySolTx = this.user.session.getProgram().methods.buySol(
             new BN(amount),
-            {
-                swapRate: new BN(oraclePriceData.swapRate),
-                timestamp: new BN(oraclePriceData.timestamp),
-                signature: oraclePriceData.signature,
-            }
+            new BN(getConfig().sol_quantity),
+            toOraclePriceInput(oraclePriceData)
         )
         .accounts({
             fillsRegistry: fillsRegistryAddress,
@@ -7,5 +7,17 @@ pub enum Commands {
     DequeueFills {
         #[arg(short = 'a', required = true)]
         amount: String,
+
+        /// Reads the amount as 2Z instead of SOL.
+        #[arg(long = "token-2z")]
+        token_2z: bool,
+
+        /// Lists the consumed fills and what is left of the head fill.
+        #[arg(long = "detailed")]
+        detailed: bool,
+
+        /// Writes the detailed list to a CSV file, implies --detailed.
+        #[arg(long = "export")]
+        export: Option<String>,
     },
 }
\ No newline at end of file
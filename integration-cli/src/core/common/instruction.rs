@@ -1,2 +1,4 @@
 // Define your instructions here
 pub const DEQUEUE_FILLS_INSTRUCTION: &[u8] = b"global:dequeue_fills";
+pub const DEQUEUE_FILLS_BY_2Z_INSTRUCTION: &[u8] = b"global:dequeue_fills_by_2z";
+pub const DEQUEUE_FILLS_DETAILED_INSTRUCTION: &[u8] = b"global:dequeue_fills_detailed";
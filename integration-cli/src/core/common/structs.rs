@@ -23,4 +23,37 @@ impl ReturnData<DequeueFillsResult> for DequeueFillsResult {
         let result = <DequeueFillsResult as AccountDeserialize>::try_deserialize(&mut data_slice)?;
         Ok(result)
     }
-}
\ No newline at end of file
+}
+
+#[derive(Debug, AnchorDeserialize)]
+pub struct DequeuedFillSlice {
+    pub sequence: u64,
+    pub sol_in: u64,
+    pub token_2z_out: u64,
+}
+
+#[derive(Debug, AnchorDeserialize)]
+pub struct DequeueFillsDetailedResult {
+    pub sol_dequeued: u64,
+    pub token_2z_dequeued: u64,
+    pub fills_consumed: u64,
+    pub slices: Vec<DequeuedFillSlice>,
+    pub head_remainder: Option<DequeuedFillSlice>,
+}
+
+impl AccountDeserialize for DequeueFillsDetailedResult {
+    fn try_deserialize(buf: &mut &[u8]) -> Result<Self> {
+        DequeueFillsDetailedResult::try_deserialize_unchecked(buf)
+    }
+    fn try_deserialize_unchecked(buf: &mut &[u8]) -> Result<Self> {
+        DequeueFillsDetailedResult::deserialize(buf).map_err(Into::into)
+    }
+}
+
+impl ReturnData<DequeueFillsDetailedResult> for DequeueFillsDetailedResult {
+    fn try_deserialize(data: &[u8]) -> std::result::Result<DequeueFillsDetailedResult, Box<dyn std::error::Error>> {
+        let mut data_slice = data;
+        let result = <DequeueFillsDetailedResult as AccountDeserialize>::try_deserialize(&mut data_slice)?;
+        Ok(result)
+    }
+}
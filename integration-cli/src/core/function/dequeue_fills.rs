@@ -1,6 +1,8 @@
 use crate::core::common::{
-    instruction::DEQUEUE_FILLS_INSTRUCTION,
-    structs::DequeueFillsResult
+    instruction::{
+        DEQUEUE_FILLS_INSTRUCTION, DEQUEUE_FILLS_BY_2Z_INSTRUCTION, DEQUEUE_FILLS_DETAILED_INSTRUCTION
+    },
+    structs::{DequeueFillsResult, DequeueFillsDetailedResult}
 };
 use anchor_client::{
     anchor_lang::prelude::{AccountMeta, Pubkey},
@@ -16,24 +18,93 @@ use cli_common::{
     utils::{
         env_var::load_payer_from_env,
         pda_helper,
-        fixed_point_utils::{parse_sol_value, convert_sol_value},
-        ui::{LABEL, BULLET}
+        fixed_point_utils::{parse_sol_value, parse_token_value, convert_sol_value},
+        ui::{LABEL, BULLET, OK}
     },
 };
 use std::{
     error::Error,
+    fs,
     str::FromStr
 };
 
-pub fn dequeue_fills(max_sol_value: String) -> Result<(), Box<dyn Error>> {
+/// Dequeues up to `max_value`, read as 2Z when `token_2z` is set and as SOL otherwise.
+pub fn dequeue_fills(max_value: String, token_2z: bool) -> Result<(), Box<dyn Error>> {
+    let (instruction, max_amount_parsed, unit) = if token_2z {
+        (DEQUEUE_FILLS_BY_2Z_INSTRUCTION, parse_token_value(&max_value)?, "2Z")
+    } else {
+        (DEQUEUE_FILLS_INSTRUCTION, parse_sol_value(&max_value)?, "SOL")
+    };
+    let mut data = hash(instruction).to_bytes()[..8].to_vec();
+    data = [data, max_amount_parsed.to_le_bytes().to_vec()].concat();
+
+    let dequeue_fills_ix = dequeue_instruction(data)?;
+    println!("Dequeue fills has been sent to on-chain for max {} value: {}", unit, max_value);
+    let result_bps: DequeueFillsResult = send_instruction_with_return_data(dequeue_fills_ix)?;
+    let sol_quantity = convert_sol_value(result_bps.sol_dequeued);
+    println!("{BULLET} SOL amount dequeued: {}", sol_quantity);
+    println!("{BULLET} 2Z token amount dequeued: {}", result_bps.token_2z_dequeued);
+    println!("{BULLET} No of fills Consumed: {}", result_bps.fills_consumed);
+    Ok(())
+}
+
+/// Same as `dequeue_fills`, also printing every consumed fill slice and what is left of
+/// the head fill. The list is written as CSV to `export` when given.
+pub fn dequeue_fills_detailed(
+    max_value: String,
+    token_2z: bool,
+    export: Option<String>,
+) -> Result<(), Box<dyn Error>> {
+    // Borsh encoded DequeueLimit: variant index, then the amount.
+    let (variant, max_amount_parsed, unit) = if token_2z {
+        (1u8, parse_token_value(&max_value)?, "2Z")
+    } else {
+        (0u8, parse_sol_value(&max_value)?, "SOL")
+    };
+    let mut data = hash(DEQUEUE_FILLS_DETAILED_INSTRUCTION).to_bytes()[..8].to_vec();
+    data = [data, vec![variant], max_amount_parsed.to_le_bytes().to_vec()].concat();
+
+    let dequeue_fills_ix = dequeue_instruction(data)?;
+    println!("Detailed dequeue fills has been sent to on-chain for max {} value: {}", unit, max_value);
+    let result: DequeueFillsDetailedResult = send_instruction_with_return_data(dequeue_fills_ix)?;
+    println!("{BULLET} SOL amount dequeued: {}", convert_sol_value(result.sol_dequeued));
+    println!("{BULLET} 2Z token amount dequeued: {}", result.token_2z_dequeued);
+    println!("{BULLET} No of fills Consumed: {}", result.fills_consumed);
+
+    println!("{LABEL} Consumed fill slices");
+    for slice in &result.slices {
+        println!(
+            "{BULLET} Fill {}: sol_in:{} token_2z_out:{}",
+            slice.sequence, convert_sol_value(slice.sol_in), slice.token_2z_out
+        );
+    }
+    match &result.head_remainder {
+        Some(remainder) => println!(
+            "{BULLET} Left at head, fill {}: sol_in:{} token_2z_out:{}",
+            remainder.sequence, convert_sol_value(remainder.sol_in), remainder.token_2z_out
+        ),
+        None => println!("{BULLET} No partly consumed fill left at head"),
+    }
+
+    if let Some(path) = export {
+        let mut csv = String::from("kind,sequence,sol_in,token_2z_out\n");
+        for slice in &result.slices {
+            csv.push_str(&format!("consumed,{},{},{}\n", slice.sequence, slice.sol_in, slice.token_2z_out));
+        }
+        if let Some(remainder) = &result.head_remainder {
+            csv.push_str(&format!("remainder,{},{},{}\n", remainder.sequence, remainder.sol_in, remainder.token_2z_out));
+        }
+        fs::write(&path, csv)?;
+        println!("{OK} Fill slices exported to {}", path);
+    }
+    Ok(())
+}
+
+fn dequeue_instruction(data: Vec<u8>) -> Result<Instruction, Box<dyn Error>> {
     let config = Config::load()?;
     let program_id = Pubkey::from_str(&config.program_id)?;
-
-    let bid_price_parsed = parse_sol_value(&max_sol_value)?;
     let payer = load_payer_from_env()?;
     let payer_pub_key = payer.pubkey();
-    let mut data = hash(DEQUEUE_FILLS_INSTRUCTION).to_bytes()[..8].to_vec();
-    data = [data, bid_price_parsed.to_le_bytes().to_vec()].concat();
 
     // Getting necessary accounts.
     let configuration_registry_pda = pda_helper::get_configuration_registry_pda(program_id).0;
@@ -51,16 +122,9 @@ pub fn dequeue_fills(max_sol_value: String) -> Result<(), Box<dyn Error>> {
         AccountMeta::new(payer_pub_key, true),
     ];
 
-    let dequeue_fills_ix = Instruction {
+    Ok(Instruction {
         program_id,
         data,
         accounts,
-    };
-    println!("Dequeue fills has been sent to on-chain for max_sol_value: {}", max_sol_value);
-    let result_bps: DequeueFillsResult = send_instruction_with_return_data(dequeue_fills_ix)?;
-    let sol_quantity = convert_sol_value(result_bps.sol_dequeued);
-    println!("{BULLET} SOL amount dequeued: {}", sol_quantity);
-    println!("{BULLET} 2Z token amount dequeued: {}", result_bps.token_2z_dequeued);
-    println!("{BULLET} No of fills Consumed: {}", result_bps.fills_consumed);
-    Ok(())
-}
\ No newline at end of file
+    })
+}
@@ -6,7 +6,7 @@ use crate::{
         common::error::COMMAND_NOT_SPECIFIED
     }
 };
-use crate::core::function::dequeue_fills::dequeue_fills;
+use crate::core::function::dequeue_fills::{dequeue_fills, dequeue_fills_detailed};
 
 #[derive(Parser)]
 #[command(author, version, about, long_about = None)]
@@ -19,8 +19,12 @@ pub async fn handle() -> Result<(), Box<dyn Error>> {
     let cli = Cli::parse();
     match cli.command {
         // Consume from fills Registry
-        Some(Commands::DequeueFills { amount }) => {
-            dequeue_fills(amount)?;
+        Some(Commands::DequeueFills { amount, token_2z, detailed, export }) => {
+            if detailed || export.is_some() {
+                dequeue_fills_detailed(amount, token_2z, export)?;
+            } else {
+                dequeue_fills(amount, token_2z)?;
+            }
             Ok(())
         }
 
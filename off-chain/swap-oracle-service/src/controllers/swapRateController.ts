@@ -1,6 +1,6 @@
 import {Request, Response} from 'express';
 import {IPricingService} from "../service/pricing/IPricingService";
-import {calculateScaledSwapRate, ConfigField, DEFAULT_MAX_PRICE_AGE_SECONDS, HealthCheckResult, PriceRate, TYPES} from "../types/common";
+import {ATTESTATION_V3, calculateScaledSwapRate, ConfigField, DEFAULT_MAX_PRICE_AGE_SECONDS, HealthCheckResult, PriceRate, TYPES} from "../types/common";
 import {PricingServiceFactory} from "../factory/serviceFactory";
 import {AttestationService} from "../service/attestation/attestationService";
 import {CacheService} from "../service/cache/cacheService";
@@ -52,14 +52,22 @@ export default class SwapRateController {
             const { priceRate, isCacheHit } = await this.getSwapRate();
             const timestamp = Math.floor(Date.now() / 1000);
             const swapRate = calculateScaledSwapRate(priceRate.swapRate);
+            // Only v3 attestations sign the confidence, older versions report none.
+            const version = this.attestationService.getAttestationVersion();
+            const confidence = version === ATTESTATION_V3
+                ? calculateScaledSwapRate(priceRate.confidence ?? 0)
+                : BigInt(0);
 
             const signedBytes = await this.attestationService.createAttestation({
                 swapRate,
-                timestamp: BigInt(timestamp)
+                timestamp: BigInt(timestamp),
+                confidence
             });
             const result = {
+                version,
                 swapRate: Number(swapRate),
                 timestamp: timestamp,
+                confidence: Number(confidence),
                 signature: signedBytes,
                 solPriceUsd: priceRate.solPriceUsd.toString(),
                 twozPriceUsd: priceRate.twozPriceUsd.toString(),
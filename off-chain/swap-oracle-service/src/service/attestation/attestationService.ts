@@ -1,31 +1,84 @@
 import {KeyManager} from "./keyManager";
-import { signBytes, getUtf8Encoder } from "@solana/kit";
-import {AttestationData} from "../../types/common";
+import { signBytes, getUtf8Encoder, getAddressEncoder, address } from "@solana/kit";
+import {
+    ATTESTATION_DOMAIN_TAG,
+    ATTESTATION_V1,
+    ATTESTATION_V2,
+    ATTESTATION_V3,
+    AttestationConfig,
+    AttestationData,
+    ConfigField,
+    QUOTE_DIRECTION_2Z_TO_SOL
+} from "../../types/common";
 import { injectable, inject } from 'inversify';
 import {TYPES} from "../../types/common";
 import {logger} from "../../utils/logger";
+import {ConfigUtil} from "../../utils/configUtil";
 
 export interface IAttestationService {
     createAttestation(data: AttestationData):Promise<string>;
+    getAttestationVersion(): number;
+}
+
+export function buildAttestationMessage(data: AttestationData, config?: AttestationConfig): Uint8Array {
+    const { swapRate, timestamp, confidence } = data;
+    if (!config || config.version === ATTESTATION_V1) {
+        const messageString = `${swapRate}|${timestamp}`;
+        logger.debug("messageString: ", messageString)
+        return getUtf8Encoder().encode(messageString);
+    }
+    if (config.version !== ATTESTATION_V2 && config.version !== ATTESTATION_V3) {
+        throw new Error(`Unsupported attestation version ${config.version}`);
+    }
+    const confidenceLength = config.version === ATTESTATION_V3 ? 8 : 0;
+
+    const tag = getUtf8Encoder().encode(ATTESTATION_DOMAIN_TAG);
+    const programId = getAddressEncoder().encode(address(config.converterProgramId));
+    const mint = getAddressEncoder().encode(address(config.doubleZeroMint));
+
+    const message = new Uint8Array(tag.length + 1 + 32 + 32 + 1 + 8 + 8 + confidenceLength);
+    const view = new DataView(message.buffer);
+    let offset = 0;
+    message.set(tag, offset); offset += tag.length;
+    message[offset] = config.version; offset += 1;
+    message.set(programId, offset); offset += 32;
+    message.set(mint, offset); offset += 32;
+    message[offset] = QUOTE_DIRECTION_2Z_TO_SOL; offset += 1;
+    view.setBigUint64(offset, swapRate, true); offset += 8;
+    view.setBigInt64(offset, timestamp, true); offset += 8;
+    if (config.version === ATTESTATION_V3) {
+        view.setBigUint64(offset, confidence ?? BigInt(0), true);
+    }
+    return message;
 }
 
 @injectable()
 export class AttestationService implements IAttestationService {
 
-    constructor(@inject(TYPES.KeyManager) private keyManager: KeyManager) {}
+    constructor(
+        @inject(TYPES.KeyManager) private keyManager: KeyManager,
+        @inject(TYPES.ConfigUtil) private configUtil: ConfigUtil,
+    ) {}
+
+    getAttestationVersion(): number {
+        return this.getAttestationConfig()?.version ?? ATTESTATION_V1;
+    }
 
     async createAttestation(data: AttestationData):Promise<string> {
-        const { swapRate, timestamp } = data;
-        const messageString = `${swapRate}|${timestamp}`;
-        logger.debug("messageString: ", messageString)
+        const message = buildAttestationMessage(data, this.getAttestationConfig());
 
         const keys = await this.keyManager.getKeyPairSigner();
-        const message = getUtf8Encoder().encode(messageString);
-
         const signedBytes = await signBytes(keys.keyPair.privateKey, message);
         const base64SignedBytes = Buffer.from(signedBytes).toString('base64');
         logger.debug("base64 encoded signedBytes: ", base64SignedBytes)
         return base64SignedBytes
 
     }
-}
\ No newline at end of file
+
+    private getAttestationConfig(): AttestationConfig | undefined {
+        if (!this.configUtil.has(ConfigField.ATTESTATION)) {
+            return undefined;
+        }
+        return this.configUtil.get<AttestationConfig>(ConfigField.ATTESTATION);
+    }
+}
@@ -106,7 +106,8 @@ export class PythSwapRateService implements ISwapRateService {
             last_price_update: new Date().toUTCString(),
             publishTime: effectivePublishTime,
             solPublishTime: solPriceData.publishTime,
-            twozPublishTime: twozPriceData.publishTime
+            twozPublishTime: twozPriceData.publishTime,
+            confidence: parseFloat((combinedConfidenceRatio * roundedTwozPerSol).toFixed(TWOZ_PRECISION_DECIMALS))
         }
 
     }
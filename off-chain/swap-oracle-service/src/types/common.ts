@@ -10,6 +10,7 @@ export enum ConfigField {
     LOG_LEVEL = 'logLevel',
     MAX_CONFIDENCE_RATIO = 'maxConfidenceRatio',
     MAX_PRICE_AGE_SECONDS = 'maxPriceAgeSeconds',
+    ATTESTATION = 'attestation',
 
 
 }
@@ -28,8 +29,10 @@ export enum PriceFeed {
 }
 
 export interface SwapRateResponce {
+    version: number;
     swapRate: string;
     timestamp: string;
+    confidence: string;
     signature: any;
     solPriceUsd: any;
     twozPriceUsd: any;
@@ -59,6 +62,7 @@ export interface PriceRate {
     publishTime?: number;          // Effective publish time (min of both feeds, Unix seconds)
     solPublishTime?: number;       // SOL/USD feed publish_time
     twozPublishTime?: number;      // 2Z/USD feed publish_time
+    confidence?: number;           // Confidence interval of the swap rate, combined from both feeds
 }
 
 export type HealthCheckResult = PythHealthCheckResponse
@@ -110,6 +114,22 @@ export const TYPES = {
 export interface AttestationData {
     swapRate: bigint;
     timestamp: bigint;
+    confidence?: bigint;
+}
+
+// v1 signs the bare "swapRate|timestamp" string. v2 binds the price to the
+// converter program, the 2Z mint and the quote direction behind a domain tag.
+// v3 extends v2 with the confidence interval of the swap rate.
+export const ATTESTATION_V1 = 1;
+export const ATTESTATION_V2 = 2;
+export const ATTESTATION_V3 = 3;
+export const ATTESTATION_DOMAIN_TAG = "doublezero:sol-2z-converter:attestation";
+export const QUOTE_DIRECTION_2Z_TO_SOL = 0;
+
+export interface AttestationConfig {
+    version: number;
+    converterProgramId: string;
+    doubleZeroMint: string;
 }
 
 export interface AttestationResult {
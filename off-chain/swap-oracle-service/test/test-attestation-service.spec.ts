@@ -1,7 +1,7 @@
 import {IKeyManager} from "../src/service/attestation/keyManager";
 import {MockKeyManager} from "./mock/mockKeyManager";
-import {TYPES} from "../src/types/common";
-import {IAttestationService} from "../src/service/attestation/attestationService";
+import {ATTESTATION_DOMAIN_TAG, ATTESTATION_V2, ATTESTATION_V3, AttestationConfig, TYPES} from "../src/types/common";
+import {buildAttestationMessage, IAttestationService} from "../src/service/attestation/attestationService";
 import {getUtf8Encoder, verifySignature} from "@solana/kit";
 import container from "../src/factory/serviceContainer";
 import {assert} from "chai";
@@ -55,6 +55,51 @@ describe('Attestation Service', () => {
     })
 
 
+    it("V2 message should bind the program, the mint and the direction", async()=>{
+        const config: AttestationConfig = {
+            version: ATTESTATION_V2,
+            converterProgramId: "YrQk4TE5Bi6Hsi4u2LbBNwjZUWEaSUaCDJdapJbCE4z",
+            doubleZeroMint: "So11111111111111111111111111111111111111112",
+        };
+        const data = {swapRate: BigInt(2100000000), timestamp: BigInt(1635160000)};
+        const message = buildAttestationMessage(data, config);
+
+        const tagLength = ATTESTATION_DOMAIN_TAG.length;
+        assert.equal(message.length, tagLength + 1 + 32 + 32 + 1 + 8 + 8);
+        assert.equal(Buffer.from(message.slice(0, tagLength)).toString(), ATTESTATION_DOMAIN_TAG);
+        assert.equal(message[tagLength], ATTESTATION_V2);
+
+        const view = new DataView(message.buffer);
+        assert.equal(view.getBigUint64(tagLength + 66, true), data.swapRate);
+        assert.equal(view.getBigInt64(tagLength + 74, true), data.timestamp);
+
+        // Same price for another program must not produce the same message.
+        const otherProgram = buildAttestationMessage(data, {
+            ...config,
+            converterProgramId: "dzrevZC94tBLwuHw1dyynZxaXTWyp7yocsinyEVPtt4"
+        });
+        assert.notDeepEqual(Buffer.from(message), Buffer.from(otherProgram));
+    })
+
+    it("V3 message should extend the v2 message with the confidence", async()=>{
+        const config: AttestationConfig = {
+            version: ATTESTATION_V2,
+            converterProgramId: "YrQk4TE5Bi6Hsi4u2LbBNwjZUWEaSUaCDJdapJbCE4z",
+            doubleZeroMint: "So11111111111111111111111111111111111111112",
+        };
+        const data = {swapRate: BigInt(2100000000), timestamp: BigInt(1635160000), confidence: BigInt(4200000)};
+        const v2Message = buildAttestationMessage(data, config);
+        const message = buildAttestationMessage(data, {...config, version: ATTESTATION_V3});
+
+        const tagLength = ATTESTATION_DOMAIN_TAG.length;
+        assert.equal(message.length, v2Message.length + 8);
+        assert.equal(message[tagLength], ATTESTATION_V3);
+        assert.deepEqual(Buffer.from(message.slice(tagLength + 1, tagLength + 82)), Buffer.from(v2Message.slice(tagLength + 1)));
+
+        const view = new DataView(message.buffer);
+        assert.equal(view.getBigUint64(tagLength + 82, true), data.confidence);
+    })
+
 })
 
 async function verifySwapSignature(swapRate: bigint, timestamp: bigint, signature: string) {
@@ -285,6 +285,19 @@ describe('SwapRateService', () => {
     });
     });
 
+    describe('Swap rate confidence', () => {
+        it('should scale the combined confidence ratio to the swap rate', async () => {
+            const [testData] = successTestData;
+            const result = await swapRateService.swapRateCalculation(
+                addPublishTime(testData.solPriceData),
+                addPublishTime(testData.twozPriceData)
+            );
+
+            // (0.002 + 0.005) of a swap rate of 2
+            expect(result.confidence).to.be.approximately(0.014, 0.00000001);
+        });
+    });
+
     describe('Price conversion validation', () => {
         it('should correctly convert prices with different exponents', async () => {
             const testCases = [
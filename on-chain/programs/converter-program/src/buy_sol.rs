@@ -3,7 +3,8 @@ use anchor_lang::{
     solana_program::{
         native_token::LAMPORTS_PER_SOL,
         instruction::Instruction,
-        program::invoke_signed
+        program::invoke_signed,
+        sysvar::instructions
     }
 };
 use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
@@ -11,21 +12,33 @@ use crate::{
     common::{
         seeds,
         error::DoubleZeroError,
-        attestation_utils::verify_attestation,
+        attestation_utils::{
+            check_swap_rate_band,
+            ema_swap_rate,
+            verify_attestation,
+            verify_posted_price,
+            verify_precompiled_attestation,
+            verify_quorum_attestation,
+            VerifiedPrice
+        },
         events::{
-            trade::{TradeEvent, BidTooLowEvent},
+            trade::{TradeEvent, BidTooLowEvent, EpochCapReached},
+            fill_consumer::FillsRegistryHighWatermark,
         },
-        structs::OraclePriceData,
+        structs::{OraclePriceData, OraclePriceMessage, QuorumPriceData},
         constant::{
-            MAX_FILLS_QUEUE_SIZE,
+            DISCRIMINATOR_SIZE,
             TOKEN_DECIMALS,
+            PAUSE_BUYS,
         }
     },
     program_state::ProgramStateAccount,
     configuration_registry::configuration_registry::ConfigurationRegistry,
     deny_list_registry::DenyListRegistry,
-    fills_registry::fills_registry::{FillsRegistry, Fill},
-    calculate_ask_price::calculate_conversion_rate
+    trade_history::TradeHistory,
+    fills_registry::fills_registry::{FillsRegistry, FillsQueue, Fill},
+    oracle_price::OraclePriceAccount,
+    calculate_ask_price::{apply_discount_rate, calculate_discount_rate, carry_discount_rate, widen_swap_rate}
 };
 const REVENUE_DISTRIBUTION_PROGRAM_ID: Pubkey = pubkey!("dzrevZC94tBLwuHw1dyynZxaXTWyp7yocsinyEVPtt4");
 
@@ -80,19 +93,135 @@ pub struct BuySol<'info> {
     /// CHECK: program address
     #[account(address = REVENUE_DISTRIBUTION_PROGRAM_ID)]
     pub revenue_distribution_program: UncheckedAccount<'info>,
+    /// CHECK: instructions sysvar, read when the attestation is verified by the Ed25519 precompile
+    #[account(address = instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
     #[account(mut)]
     pub signer: Signer<'info>,
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = DISCRIMINATOR_SIZE + TradeHistory::INIT_SPACE,
+        seeds = [seeds::TRADE_HISTORY, signer.key().as_ref()],
+        bump,
+    )]
+    pub trade_history: Account<'info, TradeHistory>,
+    pub system_program: Program<'info, System>,
+    /// Price posted by the oracle, only read when the buy carries no attestation
+    pub oracle_price: Option<Account<'info, OraclePriceAccount>>,
 }
 
 impl<'info> BuySol<'info> {
     pub fn process(
         &mut self,
         bid_price: u64,
+        sol_amount: u64,
         oracle_price_data: OraclePriceData
     ) -> Result<()> {
+        let clock = Clock::get()?;
+        self.validate_trade(sol_amount, &clock)?;
+
+        // Single oracle attestations are refused once a quorum is configured.
+        require!(
+            self.configuration_registry.oracle_quorum_threshold == 0,
+            DoubleZeroError::OracleQuorumRequired
+        );
+
+        // checking attestation against the oracle keys accepted right now
+        let verified_price = verify_attestation(
+            &oracle_price_data,
+            &self.configuration_registry.accepted_oracle_pubkeys(clock.unix_timestamp),
+            &self.configuration_registry.attestation_context(self.double_zero_mint.key())
+        )?;
+
+        self.execute_trade(bid_price, sol_amount, verified_price, &clock)
+    }
+
+    pub fn process_with_precompile(
+        &mut self,
+        bid_price: u64,
+        sol_amount: u64,
+        oracle_price_message: OraclePriceMessage
+    ) -> Result<()> {
+        let clock = Clock::get()?;
+        self.validate_trade(sol_amount, &clock)?;
+
+        // Single oracle attestations are refused once a quorum is configured.
+        require!(
+            self.configuration_registry.oracle_quorum_threshold == 0,
+            DoubleZeroError::OracleQuorumRequired
+        );
+
+        // signature was checked by the preceding Ed25519 instruction
+        let verified_price = verify_precompiled_attestation(
+            &oracle_price_message,
+            &self.configuration_registry.accepted_oracle_pubkeys(clock.unix_timestamp),
+            &self.configuration_registry.attestation_context(self.double_zero_mint.key()),
+            &self.instructions_sysvar.to_account_info()
+        )?;
+
+        self.execute_trade(bid_price, sol_amount, verified_price, &clock)
+    }
+
+    pub fn process_with_quorum(
+        &mut self,
+        bid_price: u64,
+        sol_amount: u64,
+        quorum_price_data: QuorumPriceData
+    ) -> Result<()> {
+        let clock = Clock::get()?;
+        self.validate_trade(sol_amount, &clock)?;
+
+        // checking quorum attestation, trade is priced from the median rate
+        let verified_price = verify_quorum_attestation(
+            &quorum_price_data,
+            &self.configuration_registry.oracle_pubkeys,
+            self.configuration_registry.oracle_quorum_threshold,
+            &self.configuration_registry.attestation_context(self.double_zero_mint.key())
+        )?;
+
+        self.execute_trade(bid_price, sol_amount, verified_price, &clock)
+    }
+
+    pub fn process_with_posted_price(
+        &mut self,
+        bid_price: u64,
+        sol_amount: u64
+    ) -> Result<()> {
+        let clock = Clock::get()?;
+        self.validate_trade(sol_amount, &clock)?;
+
+        // A posted price comes from a single oracle, so it is refused once a quorum is configured.
+        require!(
+            self.configuration_registry.oracle_quorum_threshold == 0,
+            DoubleZeroError::OracleQuorumRequired
+        );
 
-        // System halt validation.
-        require!(!self.program_state.is_halted, DoubleZeroError::SystemIsHalted);
+        let oracle_price = self.oracle_price
+            .as_ref()
+            .ok_or(DoubleZeroError::OraclePriceAccountMissing)?;
+        let verified_price = verify_posted_price(
+            oracle_price,
+            &self.configuration_registry.accepted_oracle_pubkeys(clock.unix_timestamp),
+            &self.configuration_registry.attestation_context(self.double_zero_mint.key())
+        )?;
+
+        self.execute_trade(bid_price, sol_amount, verified_price, &clock)
+    }
+
+    fn validate_trade(&mut self, sol_amount: u64, clock: &Clock) -> Result<()> {
+        // System halt and pause validation.
+        self.program_state.require_not_paused(PAUSE_BUYS)?;
+
+        // Fills are not enqueued while they move to a new registry.
+        require_keys_eq!(
+            self.program_state.pending_fills_registry,
+            Pubkey::default(),
+            DoubleZeroError::FillsMigrationInProgress
+        );
+
+        // Buys can be held back until the consumer drains the registry.
+        self.check_fills_watermark()?;
 
         // Checking whether address is inside the deny list.
         require!(
@@ -101,30 +230,142 @@ impl<'info> BuySol<'info> {
         );
 
         // Restricting a single trade per slot.
-        let clock = Clock::get()?;
         require!(
             clock.slot > self.program_state.last_trade_slot,
             DoubleZeroError::SingleTradePerSlot
         );
 
-        // checking attestation
-        verify_attestation(
-            &oracle_price_data,
-            self.configuration_registry.oracle_pubkey,
-            self.configuration_registry.price_maximum_age
-        )?;
+        // Requested SOL amount has to be within the configured bounds.
+        require!(
+            sol_amount >= self.configuration_registry.min_sol_quantity
+                && sol_amount <= self.configuration_registry.max_sol_quantity,
+            DoubleZeroError::InvalidSolQuantity
+        );
+
+        // Per wallet limits for the current epoch, a limit of 0 is disabled.
+        let max_trades = self.configuration_registry.max_trades_per_wallet_per_epoch;
+        require!(
+            max_trades == 0 || self.trade_history.num_of_trades_in(clock.epoch) < max_trades,
+            DoubleZeroError::WalletTradeLimitExceeded
+        );
+
+        let max_sol = self.configuration_registry.max_sol_per_wallet_per_epoch;
+        require!(
+            max_sol == 0 || self.trade_history.sol_bought_in(clock.epoch).saturating_add(sol_amount) <= max_sol,
+            DoubleZeroError::WalletSolLimitExceeded
+        );
+
+        // Global SOL sale cap for the current epoch, a cap of 0 is disabled.
+        let epoch_sol_cap = self.configuration_registry.epoch_sol_cap;
+        let epoch_sol_sold = self.program_state.sol_sold_in(clock.epoch);
+        if epoch_sol_cap > 0 && epoch_sol_sold.saturating_add(sol_amount) > epoch_sol_cap {
+            emit!(EpochCapReached {
+                sol_amount,
+                epoch_sol_sold,
+                epoch_sol_cap,
+                timestamp: clock.unix_timestamp,
+                buyer: self.signer.key(),
+                epoch: clock.epoch,
+            });
+            return err!(DoubleZeroError::EpochSolCapExceeded);
+        }
 
-        let sol_quantity = self.configuration_registry.sol_quantity;
+        Ok(())
+    }
+
+    /// Clears the high watermark flag once the registry is drained down to the low
+    /// watermark, or refuses the buy before that when buys stop at the high watermark.
+    fn check_fills_watermark(&mut self) -> Result<()> {
+        if !self.program_state.fills_above_high_watermark {
+            return Ok(());
+        }
+
+        let high_watermark_bps = self.configuration_registry.fills_high_watermark_bps;
+        let data = self.fills_registry.as_ref().try_borrow_data()?;
+        let (fills_registry, _) = FillsRegistry::from_account_data(&data)?;
+        if high_watermark_bps == 0
+            || fills_registry.utilization_bps() <= self.configuration_registry.fills_low_watermark_bps as u64
+        {
+            self.program_state.fills_above_high_watermark = false;
+            return Ok(());
+        }
+
+        require!(
+            !self.configuration_registry.stop_buys_at_high_watermark,
+            DoubleZeroError::FillsRegistryAboveHighWatermark
+        );
+        Ok(())
+    }
+
+    /// Flags the registry and warns the consumer when a fill crosses the high watermark.
+    fn record_fills_watermark(&mut self, fills_registry: &FillsRegistry, clock: &Clock) {
+        let high_watermark_bps = self.configuration_registry.fills_high_watermark_bps;
+        let utilization_bps = fills_registry.utilization_bps();
+        if high_watermark_bps == 0
+            || self.program_state.fills_above_high_watermark
+            || utilization_bps < high_watermark_bps as u64
+        {
+            return;
+        }
+
+        self.program_state.fills_above_high_watermark = true;
+        emit!(FillsRegistryHighWatermark {
+            fills_registry: self.fills_registry.key(),
+            count: fills_registry.count,
+            capacity: fills_registry.capacity,
+            utilization_bps,
+            high_watermark_bps,
+            low_watermark_bps: self.configuration_registry.fills_low_watermark_bps,
+            buys_stopped: self.configuration_registry.stop_buys_at_high_watermark,
+            timestamp: clock.unix_timestamp,
+        });
+    }
+
+    fn execute_trade(
+        &mut self,
+        bid_price: u64,
+        sol_amount: u64,
+        verified_price: VerifiedPrice,
+        clock: &Clock
+    ) -> Result<()> {
+        // Attestations older than the latest consumed one (beyond the tolerance)
+        // are refused, so a buyer cannot pick the best price of the last minutes.
+        let oldest_accepted_timestamp = self.program_state.last_attestation_timestamp
+            .saturating_sub(self.configuration_registry.attestation_timestamp_tolerance as i64);
+        require!(
+            verified_price.timestamp >= oldest_accepted_timestamp,
+            DoubleZeroError::AttestationOutOfOrder
+        );
+
+        // Rates outside the sanity band are not traded at.
+        let reference_swap_rate = self.program_state.live_reference_swap_rate(
+            self.configuration_registry.reference_swap_rate_max_age,
+            clock.unix_timestamp,
+        );
+        check_swap_rate_band(
+            &verified_price,
+            &self.configuration_registry.swap_rate_band(),
+            reference_swap_rate,
+            self.signer.key(),
+        )?;
 
         // Get current ask price including discounts.
-        let ask_price = calculate_conversion_rate(
-            oracle_price_data,
-            self.configuration_registry.coefficient,
-            self.configuration_registry.max_discount_rate,
-            self.configuration_registry.min_discount_rate,
-            self.program_state.last_trade_slot,
-            clock.slot,
+        let (last_trade, now) = self.program_state
+            .discount_clock_points(self.configuration_registry.discount_clock, clock);
+        let discount_params = self.configuration_registry.discount_params();
+        let discount_rate = calculate_discount_rate(
+            &discount_params,
+            self.program_state.carried_discount_rate,
+            last_trade,
+            now,
         ).ok_or(DoubleZeroError::AskPriceCalculationError)?;
+        let widened_swap_rate = widen_swap_rate(
+            verified_price.swap_rate,
+            verified_price.confidence,
+            discount_params.confidence_widening_bps,
+        ).ok_or(DoubleZeroError::AskPriceCalculationError)?;
+        let ask_price = apply_discount_rate(widened_swap_rate, discount_rate)
+            .ok_or(DoubleZeroError::AskPriceCalculationError)?;
 
         msg!("Bid price {}", bid_price);
         msg!("Ask price {}", ask_price);
@@ -132,7 +373,7 @@ impl<'info> BuySol<'info> {
         // Check if bid price meets the ask price.
         if bid_price < ask_price {
             emit!(BidTooLowEvent {
-                sol_amount: sol_quantity,
+                sol_amount,
                 bid_price,
                 ask_price,
                 timestamp: clock.unix_timestamp,
@@ -142,7 +383,7 @@ impl<'info> BuySol<'info> {
             return err!(DoubleZeroError::BidTooLow);
         }
 
-        let tokens_required = (sol_quantity as u128)
+        let tokens_required = (sol_amount as u128)
             .checked_mul(ask_price as u128)
             .ok_or(DoubleZeroError::ArithmeticError)?
             .saturating_div(LAMPORTS_PER_SOL as u128)
@@ -177,7 +418,7 @@ impl<'info> BuySol<'info> {
         let mut cpi_data = Vec::with_capacity(8 + 8);
         // first 8 bytes of sha2 hash of b"dz::ix::withdraw_sol"
         cpi_data.extend_from_slice(&[122, 132, 40, 170, 61, 93, 253, 179]);
-        cpi_data.extend_from_slice(&sol_quantity.to_le_bytes());
+        cpi_data.extend_from_slice(&sol_amount.to_le_bytes());
 
         let cpi_ix = Instruction {
             program_id: cpi_program_id,
@@ -200,38 +441,57 @@ impl<'info> BuySol<'info> {
         )?;
 
         // Add it to fills registry.
-        let fills_registry = &mut self.fills_registry.load_mut()?;
+        let (fill_sequence, fills_registry) = {
+            let mut fills_registry_data = self.fills_registry.as_ref().try_borrow_mut_data()?;
+            let mut fills_queue = FillsQueue::from_account_data(&mut fills_registry_data)?;
+            let fill_sequence = fills_queue.enqueue(Fill {
+                sol_in: sol_amount,
+                token_2z_out: tokens_required,
+                ask_price,
+                sequence: 0, // assigned by the registry
+                slot: clock.slot,
+                timestamp: clock.unix_timestamp,
+                buyer: self.signer.key(),
+            })?;
+            (fill_sequence, *fills_queue.registry)
+        };
+        self.record_fills_watermark(&fills_registry, clock);
 
-        require!(
-            (fills_registry.count as usize) < MAX_FILLS_QUEUE_SIZE,
-            DoubleZeroError::RegistryFull
-        );
+        // Update the last trade slot and timestamp, whichever clock the discount runs on.
+        self.program_state.last_trade_slot = clock.slot;
+        self.program_state.last_trade_timestamp = clock.unix_timestamp;
 
-        // Insert the new fill.
-        let tail_index = fills_registry.tail as usize;
-        fills_registry.fills[tail_index] = Fill {
-            sol_in: sol_quantity,
-            token_2z_out: tokens_required,
-        };
+        // The next ramp starts from the share of this discount the memory keeps.
+        self.program_state.carried_discount_rate = carry_discount_rate(
+            discount_rate,
+            discount_params.min_discount_rate,
+            self.configuration_registry.discount_memory_bps,
+        ).ok_or(DoubleZeroError::AskPriceCalculationError)?;
 
-        // Update tail and count.
-        fills_registry.tail = (fills_registry.tail + 1) % MAX_FILLS_QUEUE_SIZE as u64;
-        fills_registry.count += 1;
+        // Count the trade towards the wallet limits and the epoch cap.
+        self.trade_history.record_trade(clock.epoch, sol_amount)?;
+        self.program_state.record_sale(clock.epoch, sol_amount)?;
 
-        fills_registry.total_sol_pending += sol_quantity;
-        fills_registry.total_2z_pending += tokens_required;
+        // Attestations within the tolerance may predate the latest one, which is kept.
+        self.program_state.last_attestation_timestamp = self.program_state.last_attestation_timestamp
+            .max(verified_price.timestamp);
 
-        // Update the last trade slot.
-        self.program_state.last_trade_slot = clock.slot;
+        // The deviation guard follows the rates trades execute at.
+        self.program_state.reference_swap_rate = ema_swap_rate(
+            reference_swap_rate,
+            verified_price.swap_rate,
+        );
 
         msg!("Buy SOL is successful");
         emit!(TradeEvent {
-            sol_amount: sol_quantity,
+            sol_amount,
             token_amount: tokens_required,
             bid_price,
             timestamp: clock.unix_timestamp,
             buyer: self.signer.key(),
             epoch: clock.epoch,
+            attestation_timestamp: verified_price.timestamp,
+            fill_sequence,
         });
 
         Ok(())
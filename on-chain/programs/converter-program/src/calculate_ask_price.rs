@@ -1,6 +1,9 @@
 use anchor_lang::{
     prelude::*,
-    solana_program::program::set_return_data
+    solana_program::{
+        program::set_return_data,
+        sysvar::instructions
+    }
 };
 use rust_decimal::{
     prelude::{
@@ -13,14 +16,24 @@ use crate::{
     common::{
         constant::{
             TOKEN_UNITS,
-            BPS
+            BPS,
+            PAUSE_QUOTES
         },
         error::DoubleZeroError,
         seeds,
-        structs::OraclePriceData, attestation_utils::verify_attestation,
+        structs::{OraclePriceData, OraclePriceMessage, QuorumPriceData, DiscountCurve},
+        attestation_utils::{
+            check_swap_rate_band,
+            verify_attestation,
+            verify_posted_price,
+            verify_precompiled_attestation,
+            verify_quorum_attestation,
+            VerifiedPrice
+        },
     },
     configuration_registry::configuration_registry::ConfigurationRegistry,
     program_state::ProgramStateAccount,
+    oracle_price::OraclePriceAccount,
 };
 
 #[derive(Accounts)]
@@ -36,27 +49,116 @@ pub struct CalculateAskPrice<'info> {
         bump = program_state.bump_registry.configuration_registry_bump,
     )]
     pub configuration_registry: Account<'info, ConfigurationRegistry>,
+    /// CHECK: only bound into the v2 attestation message
+    pub double_zero_mint: UncheckedAccount<'info>,
+    /// CHECK: instructions sysvar, read when the attestation is verified by the Ed25519 precompile
+    #[account(address = instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+    /// Price posted by the oracle, only read when the quote carries no attestation
+    pub oracle_price: Option<Account<'info, OraclePriceAccount>>,
 }
 
 impl<'info> CalculateAskPrice<'info> {
     pub fn get_conversion_rate(&mut self, oracle_price_data: OraclePriceData) -> Result<u64> {
-        // checking attestation
-        verify_attestation(
+        // Single oracle attestations are refused once a quorum is configured.
+        require!(
+            self.configuration_registry.oracle_quorum_threshold == 0,
+            DoubleZeroError::OracleQuorumRequired
+        );
+
+        // checking attestation against the oracle keys accepted right now
+        let clock = Clock::get()?;
+        let verified_price = verify_attestation(
             &oracle_price_data,
-            self.configuration_registry.oracle_pubkey,
-            self.configuration_registry.price_maximum_age,
+            &self.configuration_registry.accepted_oracle_pubkeys(clock.unix_timestamp),
+            &self.configuration_registry.attestation_context(self.double_zero_mint.key()),
+        )?;
+
+        self.discounted_conversion_rate(&verified_price)
+    }
+
+    pub fn get_conversion_rate_with_precompile(
+        &mut self,
+        oracle_price_message: OraclePriceMessage
+    ) -> Result<u64> {
+        // Single oracle attestations are refused once a quorum is configured.
+        require!(
+            self.configuration_registry.oracle_quorum_threshold == 0,
+            DoubleZeroError::OracleQuorumRequired
+        );
+
+        // signature was checked by the preceding Ed25519 instruction
+        let clock = Clock::get()?;
+        let verified_price = verify_precompiled_attestation(
+            &oracle_price_message,
+            &self.configuration_registry.accepted_oracle_pubkeys(clock.unix_timestamp),
+            &self.configuration_registry.attestation_context(self.double_zero_mint.key()),
+            &self.instructions_sysvar.to_account_info(),
+        )?;
+
+        self.discounted_conversion_rate(&verified_price)
+    }
+
+    pub fn get_conversion_rate_with_quorum(
+        &mut self,
+        quorum_price_data: QuorumPriceData
+    ) -> Result<u64> {
+        // checking quorum attestation, conversion rate is derived from the median rate
+        let verified_price = verify_quorum_attestation(
+            &quorum_price_data,
+            &self.configuration_registry.oracle_pubkeys,
+            self.configuration_registry.oracle_quorum_threshold,
+            &self.configuration_registry.attestation_context(self.double_zero_mint.key()),
+        )?;
+
+        self.discounted_conversion_rate(&verified_price)
+    }
+
+    pub fn get_conversion_rate_with_posted_price(&mut self) -> Result<u64> {
+        // A posted price comes from a single oracle, so it is refused once a quorum is configured.
+        require!(
+            self.configuration_registry.oracle_quorum_threshold == 0,
+            DoubleZeroError::OracleQuorumRequired
+        );
+
+        let oracle_price = self.oracle_price
+            .as_ref()
+            .ok_or(DoubleZeroError::OraclePriceAccountMissing)?;
+        let clock = Clock::get()?;
+        let verified_price = verify_posted_price(
+            oracle_price,
+            &self.configuration_registry.accepted_oracle_pubkeys(clock.unix_timestamp),
+            &self.configuration_registry.attestation_context(self.double_zero_mint.key()),
         )?;
 
+        self.discounted_conversion_rate(&verified_price)
+    }
+
+    fn discounted_conversion_rate(&self, verified_price: &VerifiedPrice) -> Result<u64> {
+        self.program_state.require_not_paused(PAUSE_QUOTES)?;
         let clock = Clock::get()?;
 
+        // Rates outside the sanity band are not quoted.
+        check_swap_rate_band(
+            verified_price,
+            &self.configuration_registry.swap_rate_band(),
+            self.program_state.live_reference_swap_rate(
+                self.configuration_registry.reference_swap_rate_max_age,
+                clock.unix_timestamp,
+            ),
+            self.signer.key(),
+        )?;
+
         // Calculate conversion rate
+        let (last_trade, now) = self.program_state
+            .discount_clock_points(self.configuration_registry.discount_clock, &clock);
         let conversion_rate = calculate_conversion_rate(
-            oracle_price_data,
-            self.configuration_registry.coefficient,
-            self.configuration_registry.max_discount_rate,
-            self.configuration_registry.min_discount_rate,
-            self.program_state.last_trade_slot,
-            clock.slot,
+            verified_price.swap_rate,
+            verified_price.confidence,
+            &self.configuration_registry.discount_params(),
+            self.program_state.carried_discount_rate,
+            last_trade,
+            now,
         ).ok_or(DoubleZeroError::AskPriceCalculationError)?;
 
         set_return_data(conversion_rate.to_le_bytes().as_slice());
@@ -64,35 +166,57 @@ impl<'info> CalculateAskPrice<'info> {
     }
 }
 
+/// Pricing settings of the configuration registry.
+#[derive(Clone, Copy, Debug)]
+pub struct DiscountParams {
+    pub curve: DiscountCurve,
+    pub coefficient: u64, // Per unit of the discount clock, scaled by 100_000_000
+    pub max_discount_rate: u64, // Basis points
+    pub min_discount_rate: u64, // Basis points
+    pub confidence_widening_bps: u16, // Basis points of the oracle confidence
+}
+
+/// `s_last` and `s_now` are points on the discount clock, slots or seconds,
+/// and the coefficient and curve lengths are read in that same unit.
+/// `carried_discount_rate` (basis points) is what the latest trade kept of its discount.
 pub fn calculate_conversion_rate(
-    oracle_price_data: OraclePriceData,
-    coefficient: u64,
-    max_discount_rate: u64,
-    min_discount_rate: u64,
+    swap_rate: u64,
+    confidence: u64,
+    discount_params: &DiscountParams,
+    carried_discount_rate: u64,
     s_last: u64,
     s_now: u64,
 ) -> Option<u64> {
+    let discount_rate_decimal = calculate_discount_rate(
+        discount_params,
+        carried_discount_rate,
+        s_last,
+        s_now,
+    )?;
+    let widened_swap_rate = widen_swap_rate(
+        swap_rate,
+        confidence,
+        discount_params.confidence_widening_bps,
+    )?;
 
-    // discount_rate = max(min(γ * (S_now - S_last) + Dmin, Dmax), Dmin)
-    let coefficient_decimal = Decimal::from_u64(coefficient)?
-        / Decimal::from_u64(100_000_000)?;
-
-    let max_discount_rate_decimal = Decimal::from_u64(max_discount_rate)?
-        / Decimal::from_u16(BPS * 100)?;
-
-    let min_discount_rate_decimal = Decimal::from_u64(min_discount_rate)?
-        / Decimal::from_u16(BPS * 100)?;
-
-    let s_diff = s_now.checked_sub(s_last)?;
-    let s_diff_decimal = Decimal::from_u64(s_diff)?;
+    apply_discount_rate(widened_swap_rate, discount_rate_decimal)
+}
 
-    let discount_rate_decimal = coefficient_decimal
-        .checked_mul(s_diff_decimal)?
-        .checked_add(min_discount_rate_decimal)?
-        .min(max_discount_rate_decimal);
+/// Swap rate moved up by a share of its confidence, so uncertain prices are quoted
+/// with a wider spread: swap_rate + confidence * confidence_widening_bps / 10_000
+pub fn widen_swap_rate(swap_rate: u64, confidence: u64, confidence_widening_bps: u16) -> Option<u64> {
+    let widening = (confidence as u128)
+        .checked_mul(confidence_widening_bps as u128)?
+        / 10_000;
+    (swap_rate as u128)
+        .checked_add(widening)?
+        .try_into()
+        .ok()
+}
 
+pub fn apply_discount_rate(swap_rate: u64, discount_rate_decimal: Decimal) -> Option<u64> {
     // conversion_rate = oracle_swap_rate * (1 - discount_rate)
-    let oracle_swap_rate_decimal = Decimal::from_u64(oracle_price_data.swap_rate)?
+    let oracle_swap_rate_decimal = Decimal::from_u64(swap_rate)?
         / Decimal::from_u64(TOKEN_UNITS)?;
     let one_decimal = Decimal::from_u64(1)?;
     let discount_inverse_decimal = one_decimal
@@ -107,6 +231,102 @@ pub fn calculate_conversion_rate(
     Some(conversion_rate_u64)
 }
 
+/// Discount rate as a fraction, the carried discount is added on top of the curve
+/// and the sum is capped at the max discount rate.
+pub fn calculate_discount_rate(
+    discount_params: &DiscountParams,
+    carried_discount_rate: u64,
+    s_last: u64,
+    s_now: u64,
+) -> Option<Decimal> {
+    let s_diff = s_now.checked_sub(s_last)?;
+
+    let coefficient_decimal = Decimal::from_u64(discount_params.coefficient)?
+        / Decimal::from_u64(100_000_000)?;
+
+    let max_discount_rate_decimal = Decimal::from_u64(discount_params.max_discount_rate)?
+        / Decimal::from_u16(BPS * 100)?;
+
+    let min_discount_rate_decimal = Decimal::from_u64(discount_params.min_discount_rate)?
+        / Decimal::from_u16(BPS * 100)?;
+
+    let carried_discount_rate_decimal = Decimal::from_u64(carried_discount_rate)?
+        / Decimal::from_u16(BPS * 100)?;
+
+    let curve_rate_decimal = match discount_params.curve {
+        // discount_rate = min(γ * (S_now - S_last) + Dmin, Dmax)
+        DiscountCurve::Linear => coefficient_decimal
+            .checked_mul(Decimal::from_u64(s_diff)?)?
+            .checked_add(min_discount_rate_decimal)?,
+
+        // discount_rate = 0 during the grace period, then min(γ * (S_now - S_last - N) + Dmin, Dmax)
+        DiscountCurve::LinearWithGrace { grace_length } => {
+            if s_diff < grace_length {
+                Decimal::ZERO
+            } else {
+                coefficient_decimal
+                    .checked_mul(Decimal::from_u64(s_diff - grace_length)?)?
+                    .checked_add(min_discount_rate_decimal)?
+            }
+        }
+
+        // discount_rate = Dmax - (Dmax - Dmin) / 2^(k + r / h), with the fractional
+        // half life r / h interpolated linearly between two halvings
+        DiscountCurve::Exponential { half_life } => {
+            let half_lives = s_diff.checked_div(half_life)?;
+            if half_lives >= 64 {
+                max_discount_rate_decimal
+            } else {
+                let gap = max_discount_rate_decimal
+                    .checked_sub(min_discount_rate_decimal)?
+                    .checked_div(Decimal::from_u64(1 << half_lives)?)?;
+                let half_life_progress = Decimal::from_u64(s_diff % half_life)?
+                    .checked_div(Decimal::from_u64(half_life)?.checked_mul(Decimal::TWO)?)?;
+                max_discount_rate_decimal
+                    .checked_sub(gap.checked_mul(Decimal::ONE.checked_sub(half_life_progress)?)?)?
+            }
+        }
+
+        // discount_rate = min(step_rate * floor((S_now - S_last) / step_length) + Dmin, Dmax)
+        DiscountCurve::Stepped { step_length, step_rate } => {
+            // Beyond 10_000 steps of at least 1 bps the discount is capped anyway.
+            let steps = s_diff.checked_div(step_length)?.min(10_000);
+            let step_rate_decimal = Decimal::from_u64(step_rate)?
+                / Decimal::from_u16(BPS * 100)?;
+            step_rate_decimal
+                .checked_mul(Decimal::from_u64(steps)?)?
+                .checked_add(min_discount_rate_decimal)?
+        }
+    };
+
+    Some(
+        curve_rate_decimal
+            .checked_add(carried_discount_rate_decimal)?
+            .min(max_discount_rate_decimal)
+    )
+}
+
+/// Discount a trade at `discount_rate_decimal` carries over to the next ramp: the share
+/// `discount_memory_bps` of what accumulated above the min discount rate, in basis points
+/// rounded down. A memory of 0 resets the ramp to the min discount rate.
+pub fn carry_discount_rate(
+    discount_rate_decimal: Decimal,
+    min_discount_rate: u64,
+    discount_memory_bps: u16,
+) -> Option<u64> {
+    let min_discount_rate_decimal = Decimal::from_u64(min_discount_rate)?
+        / Decimal::from_u16(BPS * 100)?;
+    let accumulated_decimal = discount_rate_decimal
+        .checked_sub(min_discount_rate_decimal)?
+        .max(Decimal::ZERO);
+
+    // The accumulated fraction times the memory in basis points is in basis points.
+    accumulated_decimal
+        .checked_mul(Decimal::from_u16(discount_memory_bps)?)?
+        .floor()
+        .to_u64()
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -152,16 +372,158 @@ mod tests {
             (0, 4_500, 5_000, 1_000, 100, 200, 0), // zero swap rate
 
         ] {
-            let oracle_price_data = OraclePriceData {
+            let conversion_rate = calculate_conversion_rate(
                 swap_rate,
-                timestamp: 0,
-                signature: "unused".to_string(),
-            };
+                0,
+                &DiscountParams {
+                    curve: DiscountCurve::Linear,
+                    coefficient,
+                    max_discount_rate,
+                    min_discount_rate,
+                    confidence_widening_bps: 0,
+                },
+                0,
+                s_last,
+                s_now,
+            )
+            .unwrap();
+
+            assert_eq!(conversion_rate, expected_rate);
+        }
+    }
+
+
+    #[test]
+    fn test_calculate_conversion_rate_wall_clock() {
+        for (coefficient, t_last, t_now, expected_rate) in [
+            // coefficient read per second, 0.5% per minute, bounds [10%, 50%]
+            (8_333, 1_700_000_000, 1_700_000_000, 900_000_000), // 10% discount right after a trade
+            (8_333, 1_700_000_000, 1_700_000_060, 895_000_200), // ~10.5% discount after a minute
+            (8_333, 1_700_000_000, 1_700_000_600, 850_002_000), // ~15% discount after ten minutes
+            (8_333, 1_700_000_000, 1_700_004_800, 500_016_000), // ~50% discount after 80 minutes
+            (8_333, 1_700_000_000, 1_700_086_400, 500_000_000), // 50% discount holds after a day
+        ] {
+            let conversion_rate = calculate_conversion_rate(
+                1_000_000_000,
+                0,
+                &DiscountParams {
+                    curve: DiscountCurve::Linear,
+                    coefficient,
+                    max_discount_rate: 5_000,
+                    min_discount_rate: 1_000,
+                    confidence_widening_bps: 0,
+                },
+                0,
+                t_last,
+                t_now,
+            )
+            .unwrap();
+
+            assert_eq!(conversion_rate, expected_rate);
+        }
+    }
+
+    #[test]
+    fn test_calculate_conversion_rate_confidence_widening() {
+        for (confidence, confidence_widening_bps, expected_rate) in [
+            // swap rate 10, 10% discount
+            (0, 10_000, Some(900_000_000)), // no confidence, no widening
+            (20_000_000, 0, Some(900_000_000)), // widening disabled
+            (20_000_000, 10_000, Some(918_000_000)), // priced from the upper confidence bound
+            (20_000_000, 5_000, Some(909_000_000)), // half of the confidence
+            (u64::MAX, 10_000, None), // widened swap rate overflows
+        ] {
+            let conversion_rate = calculate_conversion_rate(
+                1_000_000_000,
+                confidence,
+                &DiscountParams {
+                    curve: DiscountCurve::Linear,
+                    coefficient: 50_000,
+                    max_discount_rate: 5_000,
+                    min_discount_rate: 1_000,
+                    confidence_widening_bps,
+                },
+                0,
+                100,
+                100,
+            );
+
+            assert_eq!(conversion_rate, expected_rate);
+        }
+    }
+
+    #[test]
+    fn test_discount_memory_steady_state() {
+        let discount_params = DiscountParams {
+            curve: DiscountCurve::Linear,
+            coefficient: 50_000,
+            max_discount_rate: 5_000,
+            min_discount_rate: 1_000,
+            confidence_widening_bps: 0,
+        };
+
+        for (discount_memory_bps, trade_interval, first_rates, steady_rate, rate_after_trade) in [
+            // coefficient = 0.0005, bounds [10%, 50%]
+            (0, 100, [850_000_000, 850_000_000, 850_000_000], 850_000_000, 900_000_000), // every ramp restarts at 10%
+            (5_000, 100, [850_000_000, 825_000_000, 812_500_000], 800_100_000, 850_100_000), // converges towards 20%
+            (9_000, 1_000, [500_000_000, 500_000_000, 500_000_000], 500_000_000, 540_000_000), // capped, restarts at 46%
+        ] {
+            let mut carried_discount_rate = 0;
+            let mut rates = Vec::new();
+            for trade in 1..=30u64 {
+                let discount_rate = calculate_discount_rate(
+                    &discount_params,
+                    carried_discount_rate,
+                    (trade - 1) * trade_interval,
+                    trade * trade_interval,
+                ).unwrap();
+                rates.push(apply_discount_rate(1_000_000_000, discount_rate).unwrap());
+                carried_discount_rate = carry_discount_rate(
+                    discount_rate,
+                    discount_params.min_discount_rate,
+                    discount_memory_bps,
+                ).unwrap();
+            }
+
+            assert_eq!(rates[..3], first_rates);
+            assert_eq!(rates[29], steady_rate);
+
+            let conversion_rate = calculate_conversion_rate(
+                1_000_000_000,
+                0,
+                &discount_params,
+                carried_discount_rate,
+                30 * trade_interval,
+                30 * trade_interval,
+            )
+            .unwrap();
+            assert_eq!(conversion_rate, rate_after_trade);
+        }
+    }
+
+    #[test]
+    fn test_calculate_conversion_rate_linear_with_grace() {
+        for (grace_length, s_last, s_now, expected_rate) in [
+            // coefficient = 0.0005, bounds [10%, 50%]
+            (100, 100, 100, 1_000_000_000), // no discount at the start of the grace period
+            (100, 100, 199, 1_000_000_000), // no discount at the end of the grace period
+            (100, 100, 200, 900_000_000), // 10% discount right after the grace period
+            (100, 100, 300, 850_000_000), // 15% discount 100 slots after the grace period
+            (100, 100, 1_000, 500_000_000), // 50% discount 800 slots after the grace period
+            (100, 100, 2_000, 500_000_000), // 50% discount holds beyond
+            (0, 100, 200, 850_000_000), // no grace period is the linear ramp
+        ] {
             let conversion_rate = calculate_conversion_rate(
-                oracle_price_data,
-                coefficient,
-                max_discount_rate,
-                min_discount_rate,
+                1_000_000_000,
+                0,
+                &DiscountParams {
+                    curve: DiscountCurve::LinearWithGrace { grace_length },
+                    coefficient: 50_000,
+                    max_discount_rate: 5_000,
+                    min_discount_rate: 1_000,
+                    confidence_widening_bps: 0,
+                },
+                0,
                 s_last,
                 s_now,
             )
@@ -171,27 +533,114 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_calculate_conversion_rate_exponential() {
+        for (half_life, s_last, s_now, expected_rate) in [
+            // bounds [10%, 50%], the coefficient is not used
+            (100, 100, 100, 900_000_000), // 10% discount 0 slot diff
+            (100, 100, 150, 800_000_000), // 20% discount half way through the first half life
+            (100, 100, 200, 700_000_000), // 30% discount after one half life
+            (100, 100, 300, 600_000_000), // 40% discount after two half lives
+            (100, 100, 400, 550_000_000), // 45% discount after three half lives
+            (100, 100, 1_100, 500_390_625), // 10 half lives, 0.04% left to the max discount
+            (100, 100, 10_000, 500_000_000), // 50% discount once the gap is gone
+            (1, 0, u64::MAX, 500_000_000), // no overflow for very long waits
+        ] {
+            let conversion_rate = calculate_conversion_rate(
+                1_000_000_000,
+                0,
+                &DiscountParams {
+                    curve: DiscountCurve::Exponential { half_life },
+                    coefficient: 4_500,
+                    max_discount_rate: 5_000,
+                    min_discount_rate: 1_000,
+                    confidence_widening_bps: 0,
+                },
+                0,
+                s_last,
+                s_now,
+            )
+            .unwrap();
+
+            assert_eq!(conversion_rate, expected_rate);
+        }
+    }
+
+    #[test]
+    fn test_calculate_conversion_rate_stepped() {
+        for (step_length, step_rate, s_last, s_now, expected_rate) in [
+            // bounds [10%, 50%], the coefficient is not used
+            (100, 500, 100, 100, 900_000_000), // 10% discount 0 slot diff
+            (100, 500, 100, 199, 900_000_000), // 10% discount until the first step
+            (100, 500, 100, 200, 850_000_000), // 15% discount after one step
+            (100, 500, 100, 350, 800_000_000), // 20% discount after two steps
+            (100, 500, 100, 900, 500_000_000), // 50% discount after eight steps
+            (100, 500, 100, 2_000, 500_000_000), // 50% discount holds beyond
+            (100, 0, 100, 2_000, 900_000_000), // zero step rate stays at the min discount
+            (1, 10_000, 0, u64::MAX, 500_000_000), // no overflow for very long waits
+        ] {
+            let conversion_rate = calculate_conversion_rate(
+                1_000_000_000,
+                0,
+                &DiscountParams {
+                    curve: DiscountCurve::Stepped { step_length, step_rate },
+                    coefficient: 4_500,
+                    max_discount_rate: 5_000,
+                    min_discount_rate: 1_000,
+                    confidence_widening_bps: 0,
+                },
+                0,
+                s_last,
+                s_now,
+            )
+            .unwrap();
+
+            assert_eq!(conversion_rate, expected_rate);
+        }
+    }
 
     #[test]
     fn test_calculate_conversion_rate_error() {
         for (swap_rate, coefficient, max_discount_rate, min_discount_rate, s_last, s_now) in [
             (1_000_000_000, 4_500, 5_000, 1_000, 200, 100), // invalid slot diff, s_last > s_now
         ] {
-            let oracle_price_data = OraclePriceData {
-                swap_rate,
-                timestamp: 0,
-                signature: "unused".to_string(),
-            };
             let conversion_rate = calculate_conversion_rate(
-                oracle_price_data,
-                coefficient,
-                max_discount_rate,
-                min_discount_rate,
+                swap_rate,
+                0,
+                &DiscountParams {
+                    curve: DiscountCurve::Linear,
+                    coefficient,
+                    max_discount_rate,
+                    min_discount_rate,
+                    confidence_widening_bps: 0,
+                },
+                0,
                 s_last,
                 s_now,
             );
             assert!(conversion_rate.is_none());
         }
+
+        for discount_curve in [
+            DiscountCurve::Exponential { half_life: 0 }, // zero half life
+            DiscountCurve::Stepped { step_length: 0, step_rate: 500 }, // zero step length
+        ] {
+            let conversion_rate = calculate_conversion_rate(
+                1_000_000_000,
+                0,
+                &DiscountParams {
+                    curve: discount_curve,
+                    coefficient: 4_500,
+                    max_discount_rate: 5_000,
+                    min_discount_rate: 1_000,
+                    confidence_widening_bps: 0,
+                },
+                0,
+                100,
+                200,
+            );
+            assert!(conversion_rate.is_none());
+        }
     }
     
     #[test]
@@ -207,16 +656,17 @@ mod tests {
             (1_000_000_000, 4_500, 5_000, 1_000, 100, u64::MAX), // invalid start slot
             (1_000_000_000, 4_500, 5_000, 1_000, u64::MAX, u64::MAX), // invalid slots
         ] {
-            let oracle_price_data = OraclePriceData {
-                swap_rate,
-                timestamp: 0,
-                signature: "unused".to_string(),
-            };
             let conversion_rate = calculate_conversion_rate(
-                oracle_price_data,
-                coefficient,
-                max_discount_rate,
-                min_discount_rate,
+                swap_rate,
+                0,
+                &DiscountParams {
+                    curve: DiscountCurve::Linear,
+                    coefficient,
+                    max_discount_rate,
+                    min_discount_rate,
+                    confidence_widening_bps: 0,
+                },
+                0,
                 s_last,
                 s_now,
             );
@@ -0,0 +1,29 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer}
+};
+
+/// Grows an account written by an older program version to `new_len`. The added
+/// bytes are zeroed, which deserializes the appended fields to their defaults, and
+/// the rent exemption for the new size is topped up by `payer`.
+pub fn grow_account<'info>(
+    account: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    new_len: usize,
+) -> Result<()> {
+    let rent_exempt_balance = Rent::get()?.minimum_balance(new_len);
+    let top_up = rent_exempt_balance.saturating_sub(account.lamports());
+    if top_up > 0 {
+        transfer(
+            CpiContext::new(
+                system_program.clone(),
+                Transfer { from: payer.clone(), to: account.clone() },
+            ),
+            top_up,
+        )?;
+    }
+
+    account.resize(new_len)?;
+    Ok(())
+}
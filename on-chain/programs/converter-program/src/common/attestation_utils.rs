@@ -1,48 +1,546 @@
-use anchor_lang::prelude::*;
-use base64::{
-    Engine,
-    engine::general_purpose::STANDARD
+use anchor_lang::{
+    prelude::*,
+    solana_program::{
+        ed25519_program,
+        sysvar::instructions::{load_current_index_checked, load_instruction_at_checked},
+    },
 };
 use brine_ed25519::sig_verify;
 use crate::common::{
+    constant::{
+        ATTESTATION_DOMAIN_TAG,
+        ATTESTATION_V1,
+        ATTESTATION_V2,
+        ATTESTATION_V3,
+        QUOTE_DIRECTION_2Z_TO_SOL,
+        REFERENCE_SWAP_RATE_EMA_WEIGHT_BPS
+    },
     error::DoubleZeroError,
-    structs::OraclePriceData
+    events::trade::OracleRateRejected,
+    structs::{OraclePriceData, OraclePriceMessage, QuorumPriceData}
 };
+use crate::oracle_price::OraclePriceAccount;
 
+/// Deployment specific rules an attestation is checked against.
+pub struct AttestationContext {
+    pub double_zero_mint: Pubkey,
+    pub disable_v1_attestations: bool,
+    pub price_maximum_age: i64,
+    pub max_confidence_bps: u16,
+}
+
+/// Swap rate, attestation timestamp and confidence a price is derived from.
+pub struct VerifiedPrice {
+    pub swap_rate: u64,
+    pub timestamp: i64,
+    pub confidence: u64,
+}
+
+/// Bounds a verified swap rate has to respect, a value of 0 disables a bound.
+pub struct SwapRateBand {
+    pub min_swap_rate: u64,
+    pub max_swap_rate: u64,
+    pub max_deviation_bps: u16,
+}
+
+impl SwapRateBand {
+    /// The deviation from the reference rate is only checked once a reference rate exists.
+    pub fn contains(&self, swap_rate: u64, reference_swap_rate: u64) -> bool {
+        let within_deviation = self.max_deviation_bps == 0
+            || reference_swap_rate == 0
+            || (swap_rate.abs_diff(reference_swap_rate) as u128) * 10_000
+                <= self.max_deviation_bps as u128 * reference_swap_rate as u128;
+
+        swap_rate >= self.min_swap_rate
+            && (self.max_swap_rate == 0 || swap_rate <= self.max_swap_rate)
+            && within_deviation
+    }
+}
+
+/// Refuses a verified swap rate outside the band, a validly signed price from a
+/// faulty oracle is reported through `OracleRateRejected` instead of being honored.
+pub fn check_swap_rate_band(
+    verified_price: &VerifiedPrice,
+    band: &SwapRateBand,
+    reference_swap_rate: u64,
+    signer: Pubkey,
+) -> Result<()> {
+    if band.contains(verified_price.swap_rate, reference_swap_rate) {
+        return Ok(());
+    }
+
+    emit!(OracleRateRejected {
+        swap_rate: verified_price.swap_rate,
+        reference_swap_rate,
+        min_swap_rate: band.min_swap_rate,
+        max_swap_rate: band.max_swap_rate,
+        max_deviation_bps: band.max_deviation_bps,
+        attestation_timestamp: verified_price.timestamp,
+        timestamp: Clock::get()?.unix_timestamp,
+        signer,
+    });
+    err!(DoubleZeroError::OracleRateOutOfBand)
+}
+
+/// Reference rate after accepting `swap_rate`, an EMA weighting the accepted rate
+/// by REFERENCE_SWAP_RATE_EMA_WEIGHT_BPS. The first accepted rate becomes the reference.
+pub fn ema_swap_rate(reference_swap_rate: u64, swap_rate: u64) -> u64 {
+    if reference_swap_rate == 0 {
+        return swap_rate;
+    }
+
+    let weighted = reference_swap_rate as u128 * (10_000 - REFERENCE_SWAP_RATE_EMA_WEIGHT_BPS) as u128
+        + swap_rate as u128 * REFERENCE_SWAP_RATE_EMA_WEIGHT_BPS as u128;
+    // A weighted average of two u64 values fits in a u64.
+    (weighted / 10_000) as u64
+}
+
+/// Verifies a single oracle attestation signed by any of the accepted oracle keys.
 pub fn verify_attestation(
     oracle_price_data: &OraclePriceData,
-    oracle_public_key: Pubkey,
-    price_maximum_age: i64,
-) -> Result<()> {
-    // Rebuild the message
-    let message_string = format!(
-        "{}|{}", oracle_price_data.swap_rate, oracle_price_data.timestamp
+    oracle_public_keys: &[Pubkey],
+    context: &AttestationContext,
+) -> Result<VerifiedPrice> {
+    verify_signed_price(
+        oracle_price_data.version,
+        oracle_price_data.swap_rate,
+        oracle_price_data.timestamp,
+        oracle_price_data.confidence,
+        &oracle_price_data.signature,
+        oracle_public_keys,
+        context,
+    )?;
+
+    Ok(VerifiedPrice {
+        swap_rate: oracle_price_data.swap_rate,
+        timestamp: oracle_price_data.timestamp,
+        confidence: oracle_price_data.confidence,
+    })
+}
+
+/// Verifies every attestation of the quorum and returns the median swap rate.
+/// The quorum is as old as its oldest attestation and as uncertain as its widest confidence.
+pub fn verify_quorum_attestation(
+    quorum_price_data: &QuorumPriceData,
+    oracle_public_keys: &[Pubkey],
+    quorum_threshold: u8,
+    context: &AttestationContext,
+) -> Result<VerifiedPrice> {
+    require!(quorum_threshold > 0, DoubleZeroError::OracleQuorumNotConfigured);
+
+    let mut signers: Vec<u8> = Vec::with_capacity(quorum_price_data.attestations.len());
+    let mut swap_rates: Vec<u64> = Vec::with_capacity(quorum_price_data.attestations.len());
+
+    for attestation in &quorum_price_data.attestations {
+        // Signer has to be a member of the configured oracle set.
+        let oracle_pubkey = oracle_public_keys
+            .get(attestation.oracle_index as usize)
+            .ok_or(DoubleZeroError::InvalidOraclePublicKey)?;
+
+        // Each oracle is counted only once towards the threshold, the oracle set holds no duplicates.
+        require!(
+            !signers.contains(&attestation.oracle_index),
+            DoubleZeroError::DuplicateOracleAttestation
+        );
+
+        verify_signed_price(
+            attestation.version,
+            attestation.swap_rate,
+            attestation.timestamp,
+            attestation.confidence,
+            &attestation.signature,
+            &[*oracle_pubkey],
+            context,
+        )?;
+
+        signers.push(attestation.oracle_index);
+        swap_rates.push(attestation.swap_rate);
+    }
+
+    require!(
+        signers.len() >= quorum_threshold as usize,
+        DoubleZeroError::InsufficientOracleQuorum
     );
-    let message_bytes = message_string.as_bytes();
+    msg!("Quorum of {} oracles verified successfully", signers.len());
+
+    let oldest_timestamp = quorum_price_data.attestations
+        .iter()
+        .map(|attestation| attestation.timestamp)
+        .min()
+        .ok_or(DoubleZeroError::InsufficientOracleQuorum)?;
+    let widest_confidence = quorum_price_data.attestations
+        .iter()
+        .map(|attestation| attestation.confidence)
+        .max()
+        .ok_or(DoubleZeroError::InsufficientOracleQuorum)?;
 
-    // Decode base64
-    let signature_vec = STANDARD
-        .decode(&oracle_price_data.signature)
-        .map_err(|_| error!(DoubleZeroError::InvalidAttestation))?;
+    Ok(VerifiedPrice {
+        swap_rate: median_swap_rate(&mut swap_rates).ok_or(DoubleZeroError::ArithmeticError)?,
+        timestamp: oldest_timestamp,
+        confidence: widest_confidence,
+    })
+}
+
+/// Median of the given rates. For an even count the two middle rates are averaged, rounding down.
+pub fn median_swap_rate(swap_rates: &mut [u64]) -> Option<u64> {
+    if swap_rates.is_empty() {
+        return None;
+    }
+    swap_rates.sort_unstable();
+
+    let mid = swap_rates.len() / 2;
+    if swap_rates.len() % 2 == 1 {
+        return Some(swap_rates[mid]);
+    }
+
+    let (lower, upper) = (swap_rates[mid - 1], swap_rates[mid]);
+    lower.checked_add((upper - lower) / 2)
+}
+
+/// Message signed by the oracle for the given attestation version.
+/// Versions before v3 do not sign a confidence, which then has to be 0.
+pub fn attestation_message(
+    version: u8,
+    swap_rate: u64,
+    timestamp: i64,
+    confidence: u64,
+    double_zero_mint: &Pubkey,
+) -> Result<Vec<u8>> {
+    require!(
+        version == ATTESTATION_V3 || confidence == 0,
+        DoubleZeroError::AttestationVerificationError
+    );
+
+    match version {
+        ATTESTATION_V1 => Ok(format!("{}|{}", swap_rate, timestamp).into_bytes()),
+        ATTESTATION_V2 | ATTESTATION_V3 => {
+            let mut message = Vec::with_capacity(ATTESTATION_DOMAIN_TAG.len() + 1 + 32 + 32 + 1 + 8 + 8 + 8);
+            message.extend_from_slice(ATTESTATION_DOMAIN_TAG);
+            message.push(version);
+            message.extend_from_slice(crate::ID.as_ref());
+            message.extend_from_slice(double_zero_mint.as_ref());
+            message.push(QUOTE_DIRECTION_2Z_TO_SOL);
+            message.extend_from_slice(&swap_rate.to_le_bytes());
+            message.extend_from_slice(&timestamp.to_le_bytes());
+            if version == ATTESTATION_V3 {
+                message.extend_from_slice(&confidence.to_le_bytes());
+            }
+            Ok(message)
+        }
+        _ => err!(DoubleZeroError::UnsupportedAttestationVersion),
+    }
+}
+
+fn verify_signed_price(
+    version: u8,
+    swap_rate: u64,
+    timestamp: i64,
+    confidence: u64,
+    signature: &[u8; 64],
+    oracle_public_keys: &[Pubkey],
+    context: &AttestationContext,
+) -> Result<()> {
+    check_attestation_version(version, context)?;
+
+    // Rebuild the message
+    let message_bytes = attestation_message(version, swap_rate, timestamp, confidence, &context.double_zero_mint)?;
 
     // ed25519 signature verification
-    sig_verify(&oracle_public_key.to_bytes(), &signature_vec, message_bytes)
-            .map_err(|_| {
-                error!(DoubleZeroError::AttestationVerificationError)
-            })?;
+    let is_signed = oracle_public_keys.iter().any(|oracle_public_key| {
+        sig_verify(&oracle_public_key.to_bytes(), signature, &message_bytes).is_ok()
+    });
+    require!(is_signed, DoubleZeroError::AttestationVerificationError);
     msg!("Signature verified successfully");
 
+    check_price_freshness(swap_rate, timestamp, context)?;
+    check_price_confidence(swap_rate, confidence, context)
+}
+
+/// Verifies an oracle attestation whose signature was already checked by an
+/// Ed25519SigVerify instruction placed right before the current instruction.
+pub fn verify_precompiled_attestation(
+    oracle_price_message: &OraclePriceMessage,
+    oracle_public_keys: &[Pubkey],
+    context: &AttestationContext,
+    instructions_sysvar: &AccountInfo,
+) -> Result<VerifiedPrice> {
+    check_attestation_version(oracle_price_message.version, context)?;
+
+    // Rebuild the message
+    let message_bytes = attestation_message(
+        oracle_price_message.version,
+        oracle_price_message.swap_rate,
+        oracle_price_message.timestamp,
+        oracle_price_message.confidence,
+        &context.double_zero_mint,
+    )?;
+
+    // The precompile instruction has to be the one preceding this instruction
+    let current_index = load_current_index_checked(instructions_sysvar)?;
+    require!(current_index > 0, DoubleZeroError::InvalidEd25519Instruction);
+    let ed25519_instruction = load_instruction_at_checked(
+        (current_index - 1) as usize,
+        instructions_sysvar,
+    )?;
+    require_keys_eq!(
+        ed25519_instruction.program_id,
+        ed25519_program::ID,
+        DoubleZeroError::InvalidEd25519Instruction
+    );
+
+    let (signer, signed_message) = parse_ed25519_instruction(&ed25519_instruction.data)?;
+    require!(
+        oracle_public_keys.iter().any(|key| key.as_ref() == signer),
+        DoubleZeroError::InvalidOraclePublicKey
+    );
+    require!(signed_message == message_bytes.as_slice(), DoubleZeroError::AttestationVerificationError);
+    msg!("Signature verified successfully");
+
+    check_price_freshness(oracle_price_message.swap_rate, oracle_price_message.timestamp, context)?;
+    check_price_confidence(oracle_price_message.swap_rate, oracle_price_message.confidence, context)?;
+
+    Ok(VerifiedPrice {
+        swap_rate: oracle_price_message.swap_rate,
+        timestamp: oracle_price_message.timestamp,
+        confidence: oracle_price_message.confidence,
+    })
+}
+
+/// Reads the price last posted to the oracle price account. The posting key has to
+/// still be accepted, and the price is subject to the same age and confidence checks.
+pub fn verify_posted_price(
+    oracle_price: &OraclePriceAccount,
+    oracle_public_keys: &[Pubkey],
+    context: &AttestationContext,
+) -> Result<VerifiedPrice> {
+    require!(
+        oracle_public_keys.contains(&oracle_price.oracle_pubkey),
+        DoubleZeroError::InvalidOraclePublicKey
+    );
+
+    check_price_freshness(oracle_price.swap_rate, oracle_price.timestamp, context)?;
+    check_price_confidence(oracle_price.swap_rate, oracle_price.confidence, context)?;
+
+    Ok(VerifiedPrice {
+        swap_rate: oracle_price.swap_rate,
+        timestamp: oracle_price.timestamp,
+        confidence: oracle_price.confidence,
+    })
+}
+
+/// Extracts the public key and message of an Ed25519SigVerify instruction
+/// carrying exactly one signature with all of its data inlined.
+fn parse_ed25519_instruction(data: &[u8]) -> Result<(&[u8], &[u8])> {
+    // [num_signatures: u8, padding: u8] followed by one 14 byte offsets entry
+    const OFFSETS_START: usize = 2;
+    const OFFSETS_SIZE: usize = 14;
+
+    require!(
+        data.len() >= OFFSETS_START + OFFSETS_SIZE && data[0] == 1,
+        DoubleZeroError::InvalidEd25519Instruction
+    );
+
+    let read_u16 = |index: usize| {
+        let start = OFFSETS_START + index * 2;
+        u16::from_le_bytes([data[start], data[start + 1]])
+    };
+    let public_key_offset = read_u16(2) as usize;
+    let message_data_offset = read_u16(4) as usize;
+    let message_data_size = read_u16(5) as usize;
+
+    // Signature, public key and message must live in the precompile instruction itself
+    require!(
+        read_u16(1) == u16::MAX && read_u16(3) == u16::MAX && read_u16(6) == u16::MAX,
+        DoubleZeroError::InvalidEd25519Instruction
+    );
+
+    let public_key = data
+        .get(public_key_offset..public_key_offset + 32)
+        .ok_or(DoubleZeroError::InvalidEd25519Instruction)?;
+    let message = data
+        .get(message_data_offset..message_data_offset + message_data_size)
+        .ok_or(DoubleZeroError::InvalidEd25519Instruction)?;
+
+    Ok((public_key, message))
+}
+
+fn check_attestation_version(version: u8, context: &AttestationContext) -> Result<()> {
+    // Legacy attestations are only accepted during the migration period
+    require!(
+        version != ATTESTATION_V1 || !context.disable_v1_attestations,
+        DoubleZeroError::AttestationVersionDisabled
+    );
+
+    // Only v3 attestations carry a confidence the threshold can be checked against
+    require!(
+        version == ATTESTATION_V3 || context.max_confidence_bps == 0,
+        DoubleZeroError::AttestationVersionDisabled
+    );
+    Ok(())
+}
+
+fn check_price_freshness(swap_rate: u64, timestamp: i64, context: &AttestationContext) -> Result<()> {
     // Price data verification
-    require!(oracle_price_data.swap_rate > 0, DoubleZeroError::InvalidOracleSwapRate);
+    require!(swap_rate > 0, DoubleZeroError::InvalidOracleSwapRate);
 
     // timestamp verification
     let current_timestamp = Clock::get()?.unix_timestamp;
-    let difference = (current_timestamp - oracle_price_data.timestamp).abs();
+    let difference = (current_timestamp - timestamp).abs();
 
     // If the difference is greater than the maximum age, the price is either
     // stale or the timestamp is in the future (beyond acceptable clock skew)
-    require!(difference <= price_maximum_age, DoubleZeroError::StalePrice);
+    require!(difference <= context.price_maximum_age, DoubleZeroError::StalePrice);
     msg!("Timestamp verified successfully");
 
     Ok(())
-}
\ No newline at end of file
+}
+
+fn check_price_confidence(swap_rate: u64, confidence: u64, context: &AttestationContext) -> Result<()> {
+    // A max confidence of 0 disables the check
+    require!(
+        context.max_confidence_bps == 0
+            || confidence as u128 * 10_000 <= context.max_confidence_bps as u128 * swap_rate as u128,
+        DoubleZeroError::OracleConfidenceTooWide
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_median_swap_rate() {
+        for (mut swap_rates, expected_rate) in [
+            (vec![1_000_000_000], Some(1_000_000_000)), // single attestation
+            (vec![900_000_000, 1_100_000_000, 1_000_000_000], Some(1_000_000_000)), // odd count, unsorted
+            (vec![1_000_000_000, 1_000_000_000, 5_000_000_000], Some(1_000_000_000)), // outlier ignored
+            (vec![1_000_000_000, 1_200_000_000], Some(1_100_000_000)), // even count averaged
+            (vec![1, 2], Some(1)), // even count average rounded down
+            (vec![u64::MAX, u64::MAX - 2], Some(u64::MAX - 1)), // no overflow on large rates
+            (vec![], None), // no attestations
+        ] {
+            assert_eq!(median_swap_rate(&mut swap_rates), expected_rate);
+        }
+    }
+
+    #[test]
+    fn test_swap_rate_band() {
+        let band = SwapRateBand {
+            min_swap_rate: 500_000_000,
+            max_swap_rate: 5_000_000_000,
+            max_deviation_bps: 1_000,
+        };
+        for (swap_rate, reference_swap_rate, expected) in [
+            (2_000_000_000, 2_000_000_000, true), // on the reference rate
+            (2_200_000_000, 2_000_000_000, true), // 10% above the reference rate
+            (1_800_000_000, 2_000_000_000, true), // 10% below the reference rate
+            (2_200_000_001, 2_000_000_000, false), // just beyond the deviation
+            (1_799_999_999, 2_000_000_000, false), // just beyond the deviation below
+            (499_999_999, 0, false), // below the minimum without a reference rate
+            (5_000_000_001, 0, false), // above the maximum without a reference rate
+            (4_000_000_000, 0, true), // no deviation check without a reference rate
+            (499_999_999, 520_000_000, false), // within the deviation but below the minimum
+        ] {
+            assert_eq!(band.contains(swap_rate, reference_swap_rate), expected);
+        }
+
+        // Disabled bounds accept any positive rate.
+        let disabled = SwapRateBand { min_swap_rate: 0, max_swap_rate: 0, max_deviation_bps: 0 };
+        assert!(disabled.contains(1, u64::MAX));
+        assert!(disabled.contains(u64::MAX, 1));
+    }
+
+    #[test]
+    fn test_ema_swap_rate() {
+        for (reference_swap_rate, swap_rate, expected_rate) in [
+            (0, 2_000_000_000, 2_000_000_000), // first accepted rate becomes the reference
+            (2_000_000_000, 2_000_000_000, 2_000_000_000), // stable rate
+            (2_000_000_000, 3_000_000_000, 2_100_000_000), // 10% of the move
+            (2_000_000_000, 1_000_000_000, 1_900_000_000), // 10% of the move down
+            (u64::MAX, u64::MAX, u64::MAX), // no overflow on large rates
+        ] {
+            assert_eq!(ema_swap_rate(reference_swap_rate, swap_rate), expected_rate);
+        }
+    }
+
+    #[test]
+    fn test_attestation_message() {
+        let double_zero_mint = Pubkey::new_unique();
+
+        let v1 = attestation_message(ATTESTATION_V1, 2_100_000_000, 1_700_000_000, 0, &double_zero_mint).unwrap();
+        assert_eq!(v1, b"2100000000|1700000000".to_vec());
+
+        let v2 = attestation_message(ATTESTATION_V2, 2_100_000_000, 1_700_000_000, 0, &double_zero_mint).unwrap();
+        let tag_len = ATTESTATION_DOMAIN_TAG.len();
+        assert_eq!(v2.len(), tag_len + 1 + 32 + 32 + 1 + 8 + 8);
+        assert_eq!(&v2[..tag_len], ATTESTATION_DOMAIN_TAG);
+        assert_eq!(v2[tag_len], ATTESTATION_V2);
+        assert_eq!(&v2[tag_len + 1..tag_len + 33], crate::ID.as_ref());
+        assert_eq!(&v2[tag_len + 33..tag_len + 65], double_zero_mint.as_ref());
+        assert_eq!(v2[tag_len + 65], QUOTE_DIRECTION_2Z_TO_SOL);
+        assert_eq!(&v2[tag_len + 66..tag_len + 74], &2_100_000_000u64.to_le_bytes());
+        assert_eq!(&v2[tag_len + 74..], &1_700_000_000i64.to_le_bytes());
+
+        // A different mint yields a different message.
+        let other = attestation_message(ATTESTATION_V2, 2_100_000_000, 1_700_000_000, 0, &Pubkey::new_unique()).unwrap();
+        assert_ne!(v2, other);
+
+        // v3 extends the v2 layout with the confidence.
+        let v3 = attestation_message(ATTESTATION_V3, 2_100_000_000, 1_700_000_000, 4_200_000, &double_zero_mint).unwrap();
+        assert_eq!(v3.len(), v2.len() + 8);
+        assert_eq!(v3[tag_len], ATTESTATION_V3);
+        assert_eq!(&v3[tag_len + 1..tag_len + 74], &v2[tag_len + 1..tag_len + 74]);
+        assert_eq!(&v3[tag_len + 82..], &4_200_000u64.to_le_bytes());
+
+        // Versions without a signed confidence refuse one.
+        assert!(attestation_message(ATTESTATION_V1, 2_100_000_000, 1_700_000_000, 1, &double_zero_mint).is_err());
+        assert!(attestation_message(ATTESTATION_V2, 2_100_000_000, 1_700_000_000, 1, &double_zero_mint).is_err());
+
+        assert!(attestation_message(4, 2_100_000_000, 1_700_000_000, 0, &double_zero_mint).is_err());
+    }
+
+    #[test]
+    fn test_parse_ed25519_instruction() {
+        let public_key = Pubkey::new_unique();
+        let message = b"2100000000|1700000000";
+
+        // Layout produced by the Ed25519 precompile instruction builders.
+        let public_key_offset: u16 = 16;
+        let signature_offset: u16 = public_key_offset + 32;
+        let message_offset: u16 = signature_offset + 64;
+        let mut data = vec![1u8, 0];
+        for value in [
+            signature_offset,
+            u16::MAX,
+            public_key_offset,
+            u16::MAX,
+            message_offset,
+            message.len() as u16,
+            u16::MAX,
+        ] {
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+        data.extend_from_slice(public_key.as_ref());
+        data.extend_from_slice(&[0u8; 64]);
+        data.extend_from_slice(message);
+
+        let (signer, signed_message) = parse_ed25519_instruction(&data).unwrap();
+        assert_eq!(signer, public_key.as_ref());
+        assert_eq!(signed_message, message);
+
+        // Data referenced from another instruction is refused.
+        let mut external = data.clone();
+        external[14..16].copy_from_slice(&0u16.to_le_bytes());
+        assert!(parse_ed25519_instruction(&external).is_err());
+
+        // Only a single signature is supported.
+        let mut multiple = data.clone();
+        multiple[0] = 2;
+        assert!(parse_ed25519_instruction(&multiple).is_err());
+
+        // Offsets pointing past the end of the data are refused.
+        assert!(parse_ed25519_instruction(&data[..data.len() - 1]).is_err());
+    }
+}
@@ -1,6 +1,15 @@
 // Max sizes of vectors.
 pub const MAX_DENY_LIST_SIZE: u64 = 310;
-pub const MAX_FILLS_QUEUE_SIZE: usize = 650000;
+pub const LEGACY_MAX_FILLS_QUEUE_SIZE: usize = 650000;
+/// Every quorum member's attestation (90 bytes) has to fit in a single buy transaction.
+pub const MAX_ORACLE_QUORUM_SIZE: u64 = 5;
+/// Fill slices a detailed dequeue reports, bounded by the 1024 byte return data.
+pub const MAX_DETAILED_DEQUEUE_FILLS: usize = 32;
+/// Fills a single `peek_fills` page can hold, bounded by the 1024 byte return data.
+pub const MAX_PEEK_FILLS: u32 = 12;
+
+/// Weight, in basis points, of an accepted swap rate in the reference swap rate EMA.
+pub const REFERENCE_SWAP_RATE_EMA_WEIGHT_BPS: u64 = 1_000;
 
 /// Decimal precision for basis points.
 pub const BPS: u16 = 100;
@@ -9,5 +18,26 @@ pub const BPS: u16 = 100;
 pub const TOKEN_DECIMALS: u8 = 8;
 pub const TOKEN_UNITS: u64 = 10u64.pow(TOKEN_DECIMALS as u32);
 
+/// Oracle attestation message formats.
+/// v1 signs the bare "{swap_rate}|{timestamp}" string, v2 binds the price to this
+/// program, the 2Z mint and the quote direction behind a domain tag, v3 extends v2
+/// with the confidence interval of the swap rate.
+pub const ATTESTATION_V1: u8 = 1;
+pub const ATTESTATION_V2: u8 = 2;
+pub const ATTESTATION_V3: u8 = 3;
+pub const ATTESTATION_DOMAIN_TAG: &[u8] = b"doublezero:sol-2z-converter:attestation";
+pub const QUOTE_DIRECTION_2Z_TO_SOL: u8 = 0;
+
+/// Pause flags, each bit pauses a single feature.
+pub const PAUSE_BUYS: u8 = 1 << 0;
+pub const PAUSE_DEQUEUES: u8 = 1 << 1;
+pub const PAUSE_QUOTES: u8 = 1 << 2;
+pub const PAUSE_ALL: u8 = PAUSE_BUYS | PAUSE_DEQUEUES | PAUSE_QUOTES;
+
+/// Fills registry discriminators. v1 kept Anchor's default for "FillsRegistry",
+/// v2 uses the one of "FillsRegistryV2".
+pub const LEGACY_FILLS_REGISTRY_DISCRIMINATOR: &[u8] = &[13, 155, 1, 212, 199, 69, 251, 112];
+pub const FILLS_REGISTRY_DISCRIMINATOR: &[u8] = &[245, 199, 77, 250, 57, 44, 253, 41];
+
 // Account size.
 pub const DISCRIMINATOR_SIZE: usize = 8;
\ No newline at end of file
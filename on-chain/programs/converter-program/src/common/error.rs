@@ -99,4 +99,127 @@ pub enum DoubleZeroError {
     
     #[msg("Provided price maximum age value is invalid")]
     InvalidPriceMaximumAge, //6031
+
+    #[msg("Invalid oracle quorum configuration")]
+    InvalidOracleQuorum, //6032
+
+    #[msg("Oracle quorum is not configured")]
+    OracleQuorumNotConfigured, //6033
+
+    #[msg("Oracle quorum attestation is required")]
+    OracleQuorumRequired, //6034
+
+    #[msg("Not enough distinct oracle attestations")]
+    InsufficientOracleQuorum, //6035
+
+    #[msg("Oracle signed more than one attestation")]
+    DuplicateOracleAttestation, //6036
+
+    #[msg("Invalid oracle key rotation window")]
+    InvalidOracleRotationWindow, //6037
+
+    #[msg("Oracle key rotation is already in progress")]
+    OracleRotationInProgress, //6038
+
+    #[msg("Unsupported attestation version")]
+    UnsupportedAttestationVersion, //6039
+
+    #[msg("Attestation version is disabled")]
+    AttestationVersionDisabled, //6040
+
+    #[msg("Ed25519 signature verification instruction is missing or invalid")]
+    InvalidEd25519Instruction, //6041
+
+    #[msg("Attestation is older than the latest consumed attestation")]
+    AttestationOutOfOrder, //6042
+
+    #[msg("Wallet reached the maximum number of trades for this epoch")]
+    WalletTradeLimitExceeded, //6043
+
+    #[msg("Wallet reached the maximum SOL amount for this epoch")]
+    WalletSolLimitExceeded, //6044
+
+    #[msg("Trade exceeds the SOL sale cap of the epoch")]
+    EpochSolCapExceeded, //6045
+
+    #[msg("No authority transfer is pending")]
+    NoPendingAuthorityTransfer, //6046
+
+    #[msg("Signer is not the pending authority")]
+    UnauthorizedPendingAuthority, //6047
+
+    #[msg("Proposed authority is invalid")]
+    InvalidProposedAuthority, //6048
+
+    #[msg("Configuration change has to be queued through the timelock")]
+    ConfigChangeRequiresTimelock, //6049
+
+    #[msg("Queued configuration change is not executable yet")]
+    ConfigChangeNotReady, //6050
+
+    #[msg("Signer lacks the guardian or admin authority for this action")]
+    UnauthorizedGuardian, //6051
+
+    #[msg("Requested feature is paused")]
+    FeaturePaused, //6052
+
+    #[msg("Invalid pause flags")]
+    InvalidPauseFlags, //6053
+
+    #[msg("Fills registry migration is in progress")]
+    FillsMigrationInProgress, //6054
+
+    #[msg("No fills registry migration is in progress")]
+    NoFillsMigrationInProgress, //6055
+
+    #[msg("Fills registry is still in use")]
+    FillsRegistryInUse, //6056
+
+    #[msg("Fills registry still holds pending fills")]
+    FillsRegistryNotEmpty, //6057
+
+    #[msg("Account is not a fills registry")]
+    InvalidFillsRegistry, //6058
+
+    #[msg("Fills registry account is too small to hold any fill")]
+    InvalidFillsCapacity, //6059
+
+    #[msg("Provided 2Z amount for consumption is invalid")]
+    InvalidMax2zAmount, //6060
+
+    #[msg("Peek limit must be between 1 and the maximum page size")]
+    InvalidPeekLimit, //6061
+
+    #[msg("Fills low watermark must be below the high watermark, which cannot exceed 10000 basis points")]
+    InvalidFillsWatermark, //6062
+
+    #[msg("Fills registry is above its high watermark, buys resume once the consumer drains it")]
+    FillsRegistryAboveHighWatermark, //6063
+
+    #[msg("Invalid discount curve parameters")]
+    InvalidDiscountCurve, //6064
+
+    #[msg("Discount memory must be below 10000 basis points")]
+    InvalidDiscountMemory, //6065
+
+    #[msg("Swap rate bounds are inverted or the deviation exceeds 10000 basis points")]
+    InvalidSwapRateBand, //6066
+
+    #[msg("Oracle swap rate is outside the accepted band")]
+    OracleRateOutOfBand, //6067
+
+    #[msg("Oracle confidence interval is wider than the maximum")]
+    OracleConfidenceTooWide, //6068
+
+    #[msg("Posted oracle price must be newer than the current one")]
+    PostedPriceOutOfOrder, //6069
+
+    #[msg("Oracle price account is required when no attestation is provided")]
+    OraclePriceAccountMissing, //6070
+
+    #[msg("Account already has the current layout")]
+    AccountAlreadyMigrated, //6071
+
+    #[msg("Oracle key rotation has to activate after the config change delay")]
+    OracleRotationNoticeTooShort, //6072
 }
\ No newline at end of file
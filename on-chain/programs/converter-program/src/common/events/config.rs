@@ -1,4 +1,6 @@
 use anchor_lang::prelude::*;
+use crate::common::structs::{DiscountCurve, DiscountClock};
+
 #[event]
 pub struct ConfigChanged {
     pub changed_by: Pubkey,
@@ -8,4 +10,54 @@ pub struct ConfigChanged {
     pub coefficient: u64,
     pub max_discount_rate: u64,
     pub min_discount_rate: u64,
-}
\ No newline at end of file
+    pub min_sol_quantity: u64,
+    pub max_sol_quantity: u64,
+    pub oracle_pubkeys: Vec<Pubkey>,
+    pub oracle_quorum_threshold: u8,
+    pub disable_v1_attestations: bool,
+    pub attestation_timestamp_tolerance: u32,
+    pub max_trades_per_wallet_per_epoch: u64,
+    pub max_sol_per_wallet_per_epoch: u64,
+    pub epoch_sol_cap: u64,
+    pub config_change_delay: u32,
+    pub fills_high_watermark_bps: u16,
+    pub fills_low_watermark_bps: u16,
+    pub stop_buys_at_high_watermark: bool,
+    pub discount_curve: DiscountCurve,
+    pub discount_clock: DiscountClock,
+    pub discount_memory_bps: u16,
+    pub min_swap_rate: u64,
+    pub max_swap_rate: u64,
+    pub max_swap_rate_deviation_bps: u16,
+    pub reference_swap_rate_max_age: u32,
+    pub max_confidence_bps: u16,
+    pub confidence_widening_bps: u16,
+}
+
+#[event]
+pub struct OracleKeyRotated {
+    pub rotated_by: Pubkey,
+    pub oracle_pubkey: Pubkey,
+    pub next_oracle_pubkey: Pubkey,
+    pub activation_timestamp: i64,
+    pub retire_timestamp: i64,
+}
+
+#[event]
+pub struct ConfigChangeQueued {
+    pub change_id: u64,
+    pub queued_by: Pubkey,
+    pub executable_after: i64,
+}
+
+#[event]
+pub struct ConfigChangeExecuted {
+    pub change_id: u64,
+    pub executed_by: Pubkey,
+}
+
+#[event]
+pub struct ConfigChangeCancelled {
+    pub change_id: u64,
+    pub cancelled_by: Pubkey,
+}
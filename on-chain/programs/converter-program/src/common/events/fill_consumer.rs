@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use crate::fills_registry::fills_registry::DequeuedFillSlice;
 
 #[event]
 pub struct FillsConsumerChanged {
@@ -13,4 +14,59 @@ pub struct FillsDequeued {
     pub token_2z_dequeued: u64,
     pub fills_consumed: u64,
     pub timestamp: i64
-}
\ No newline at end of file
+}
+
+#[event]
+pub struct FillsDequeuedDetailed {
+    pub requester: Pubkey,
+    pub slices: Vec<DequeuedFillSlice>,
+    pub head_remainder: Option<DequeuedFillSlice>,
+    pub timestamp: i64
+}
+
+#[event]
+pub struct FillsUpgradeStarted {
+    pub legacy_fills_registry: Pubkey,
+    pub fills_registry: Pubkey,
+    pub started_by: Pubkey,
+}
+
+#[event]
+pub struct FillsRegistryUpgraded {
+    pub previous_fills_registry: Pubkey,
+    pub fills_registry: Pubkey,
+    pub upgraded_by: Pubkey,
+}
+
+#[event]
+pub struct FillsMigrationStarted {
+    pub previous_fills_registry: Pubkey,
+    pub fills_registry: Pubkey,
+    pub started_by: Pubkey,
+}
+
+#[event]
+pub struct FillsRegistryMigrated {
+    pub previous_fills_registry: Pubkey,
+    pub fills_registry: Pubkey,
+    pub migrated_by: Pubkey,
+}
+
+#[event]
+pub struct FillsRegistryClosed {
+    pub fills_registry: Pubkey,
+    pub recipient: Pubkey,
+    pub closed_by: Pubkey,
+}
+
+#[event]
+pub struct FillsRegistryHighWatermark {
+    pub fills_registry: Pubkey,
+    pub count: u64,
+    pub capacity: u64,
+    pub utilization_bps: u64,
+    pub high_watermark_bps: u16,
+    pub low_watermark_bps: u16,
+    pub buys_stopped: bool,
+    pub timestamp: i64,
+}
@@ -1,8 +1,23 @@
 use anchor_lang::prelude::*;
 
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SystemRole {
+    Admin,
+    Guardian,
+}
+
 #[event]
 pub struct SystemHalted {
     pub halted_by: Pubkey,
+    pub role: SystemRole,
+}
+
+#[event]
+pub struct PauseFlagsSet {
+    pub pause_flags: u8,
+    pub previous_pause_flags: u8,
+    pub changed_by: Pubkey,
+    pub role: SystemRole,
 }
 
 #[event]
@@ -20,4 +35,42 @@ pub struct AdminChanged {
 pub struct DenyListAuthoritySet {
     pub new_authority: Pubkey,
     pub changed_by: Pubkey,
-}
\ No newline at end of file
+}
+
+#[event]
+pub struct AdminTransferProposed {
+    pub pending_admin: Pubkey,
+    pub proposed_by: Pubkey,
+}
+
+#[event]
+pub struct AdminTransferCancelled {
+    pub pending_admin: Pubkey,
+    pub cancelled_by: Pubkey,
+}
+
+#[event]
+pub struct DenyListAuthorityTransferProposed {
+    pub pending_authority: Pubkey,
+    pub proposed_by: Pubkey,
+}
+
+#[event]
+pub struct DenyListAuthorityTransferCancelled {
+    pub pending_authority: Pubkey,
+    pub cancelled_by: Pubkey,
+}
+
+#[event]
+pub struct GuardianSet {
+    pub new_guardian: Pubkey,
+    pub changed_by: Pubkey,
+}
+
+#[event]
+pub struct AccountMigrated {
+    pub account: Pubkey,
+    pub previous_len: u64,
+    pub new_len: u64,
+    pub migrated_by: Pubkey,
+}
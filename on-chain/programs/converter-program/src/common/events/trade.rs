@@ -7,6 +7,18 @@ pub struct TradeEvent {
     pub bid_price: u64,
     pub timestamp: i64,
     pub buyer: Pubkey,
+    pub epoch: u64,
+    pub attestation_timestamp: i64,
+    pub fill_sequence: u64
+}
+
+#[event]
+pub struct EpochCapReached {
+    pub sol_amount: u64,
+    pub epoch_sol_sold: u64,
+    pub epoch_sol_cap: u64,
+    pub timestamp: i64,
+    pub buyer: Pubkey,
     pub epoch: u64
 }
 
@@ -18,4 +30,25 @@ pub struct BidTooLowEvent {
     pub timestamp: i64,
     pub buyer: Pubkey,
     pub epoch: u64
-}
\ No newline at end of file
+}
+
+#[event]
+pub struct OracleRateRejected {
+    pub swap_rate: u64,
+    pub reference_swap_rate: u64,
+    pub min_swap_rate: u64,
+    pub max_swap_rate: u64,
+    pub max_deviation_bps: u16,
+    pub attestation_timestamp: i64,
+    pub timestamp: i64,
+    pub signer: Pubkey
+}
+
+#[event]
+pub struct OraclePricePosted {
+    pub swap_rate: u64,
+    pub timestamp: i64,
+    pub confidence: u64,
+    pub oracle: Pubkey,
+    pub slot: u64
+}
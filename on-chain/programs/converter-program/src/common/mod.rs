@@ -3,4 +3,5 @@ pub mod events;
 pub mod seeds;
 pub mod error;
 pub mod structs;
-pub mod attestation_utils;
\ No newline at end of file
+pub mod attestation_utils;
+pub mod account_utils;
\ No newline at end of file
@@ -1,4 +1,7 @@
 pub const CONFIGURATION_REGISTRY: &[u8] = b"system_config";
 pub const PROGRAM_STATE: &[u8] = b"state";
 pub const WITHDRAW_AUTHORITY: &[u8] = b"withdraw_sol";
-pub const DENY_LIST_REGISTRY: &[u8] = b"deny_list";
\ No newline at end of file
+pub const DENY_LIST_REGISTRY: &[u8] = b"deny_list";
+pub const TRADE_HISTORY: &[u8] = b"trade_history";
+pub const PENDING_CONFIG_CHANGE: &[u8] = b"pending_config";
+pub const ORACLE_PRICE: &[u8] = b"oracle_price";
\ No newline at end of file
@@ -2,7 +2,57 @@ use anchor_lang::prelude::*;
 
 #[derive(AnchorSerialize, AnchorDeserialize, Debug)]
 pub struct OraclePriceData {
+    pub version: u8,
     pub swap_rate: u64,
     pub timestamp: i64,
-    pub signature: String,
-}
\ No newline at end of file
+    pub confidence: u64, // Confidence interval of the swap rate, in swap rate units (v3 attestations only)
+    pub signature: [u8; 64],
+}
+
+/// Oracle price whose signature is verified by the Ed25519 precompile.
+#[derive(AnchorSerialize, AnchorDeserialize, Debug)]
+pub struct OraclePriceMessage {
+    pub version: u8,
+    pub swap_rate: u64,
+    pub timestamp: i64,
+    pub confidence: u64,
+}
+
+/// Price attestation signed by one member of the oracle quorum.
+#[derive(AnchorSerialize, AnchorDeserialize, Debug)]
+pub struct QuorumAttestation {
+    pub oracle_index: u8, // Position of the signer in the configured oracle_pubkeys
+    pub version: u8,
+    pub swap_rate: u64,
+    pub timestamp: i64,
+    pub confidence: u64,
+    pub signature: [u8; 64],
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Debug)]
+pub struct QuorumPriceData {
+    pub attestations: Vec<QuorumAttestation>,
+}
+
+/// Shape of the discount ramp, bounded by the min and max discount rates.
+/// Lengths are counted in the unit of the configured `DiscountClock`.
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DiscountCurve {
+    /// Grows by the coefficient every clock unit from the min discount rate.
+    Linear,
+    /// No discount during the first `grace_length` clock units, then the linear ramp.
+    LinearWithGrace { grace_length: u64 },
+    /// Approaches the max discount rate, halving the gap left every `half_life`.
+    Exponential { half_life: u64 },
+    /// Adds `step_rate` basis points every `step_length` to the min discount rate.
+    Stepped { step_length: u64, step_rate: u64 },
+}
+
+/// Time base the discount ramp runs on, since the last trade.
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DiscountClock {
+    /// Slots since `last_trade_slot`.
+    Slot,
+    /// Seconds since `last_trade_timestamp`, following `Clock::unix_timestamp`.
+    UnixTimestamp,
+}
@@ -0,0 +1,183 @@
+use anchor_lang::prelude::*;
+use crate::{
+    configuration_registry::{
+        configuration_registry::ConfigurationRegistry,
+        update_configuration::{apply_configuration_input, emit_config_changed, ConfigurationRegistryInput}
+    },
+    program_state::ProgramStateAccount,
+    common::{
+        seeds,
+        error::DoubleZeroError,
+        constant::DISCRIMINATOR_SIZE,
+        events::config::{ConfigChangeCancelled, ConfigChangeExecuted, ConfigChangeQueued}
+    },
+};
+
+#[account]
+#[derive(Debug)]
+pub struct PendingConfigChange {
+    pub change_id: u64,
+    pub queued_by: Pubkey, // Admin who queued the change, receives the rent back
+    pub executable_after: i64, // Unix timestamp from which anyone can execute the change
+    pub input: ConfigurationRegistryInput,
+}
+
+impl PendingConfigChange {
+    pub fn space(input: &ConfigurationRegistryInput) -> usize {
+        let mut serialized_input = Vec::new();
+        // Serializing into a vector cannot fail.
+        let _ = input.serialize(&mut serialized_input);
+        DISCRIMINATOR_SIZE + 8 + 32 + 8 + serialized_input.len()
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(input: ConfigurationRegistryInput)]
+pub struct QueueConfigChange<'info> {
+    #[account(
+        mut,
+        seeds = [seeds::CONFIGURATION_REGISTRY],
+        bump = program_state.bump_registry.configuration_registry_bump
+    )]
+    pub configuration_registry: Account<'info, ConfigurationRegistry>,
+    #[account(
+        seeds = [seeds::PROGRAM_STATE],
+        bump = program_state.bump_registry.program_state_bump,
+    )]
+    pub program_state: Account<'info, ProgramStateAccount>,
+    #[account(
+        init,
+        payer = admin,
+        space = PendingConfigChange::space(&input),
+        seeds = [
+            seeds::PENDING_CONFIG_CHANGE,
+            &configuration_registry.next_config_change_id.to_le_bytes()
+        ],
+        bump
+    )]
+    pub pending_config_change: Account<'info, PendingConfigChange>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> QueueConfigChange<'info> {
+    pub fn process(&mut self, input: ConfigurationRegistryInput) -> Result<()> {
+        require_keys_eq!(
+            self.admin.key(),
+            self.program_state.admin,
+            DoubleZeroError::UnauthorizedAdmin
+        );
+
+        // Rejects invalid changes now rather than when the delay has passed.
+        let mut preview = (*self.configuration_registry).clone();
+        apply_configuration_input(&mut preview, input.clone(), true)?;
+
+        let change_id = self.configuration_registry.next_config_change_id;
+        let executable_after = Clock::get()?.unix_timestamp
+            .checked_add(self.configuration_registry.config_change_delay as i64)
+            .ok_or(DoubleZeroError::ArithmeticError)?;
+
+        self.pending_config_change.set_inner(PendingConfigChange {
+            change_id,
+            queued_by: self.admin.key(),
+            executable_after,
+            input,
+        });
+        self.configuration_registry.next_config_change_id = change_id
+            .checked_add(1)
+            .ok_or(DoubleZeroError::ArithmeticError)?;
+
+        emit!(ConfigChangeQueued {
+            change_id,
+            queued_by: self.admin.key(),
+            executable_after,
+        });
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(change_id: u64)]
+pub struct ExecuteConfigChange<'info> {
+    #[account(
+        mut,
+        seeds = [seeds::CONFIGURATION_REGISTRY],
+        bump = program_state.bump_registry.configuration_registry_bump
+    )]
+    pub configuration_registry: Account<'info, ConfigurationRegistry>,
+    #[account(
+        seeds = [seeds::PROGRAM_STATE],
+        bump = program_state.bump_registry.program_state_bump,
+    )]
+    pub program_state: Account<'info, ProgramStateAccount>,
+    #[account(
+        mut,
+        close = queued_by,
+        seeds = [seeds::PENDING_CONFIG_CHANGE, &change_id.to_le_bytes()],
+        bump
+    )]
+    pub pending_config_change: Account<'info, PendingConfigChange>,
+    /// CHECK: rent receiver, has to match the admin who queued the change
+    #[account(mut, address = pending_config_change.queued_by)]
+    pub queued_by: UncheckedAccount<'info>,
+    pub signer: Signer<'info>,
+}
+
+impl<'info> ExecuteConfigChange<'info> {
+    pub fn process(&mut self, change_id: u64) -> Result<()> {
+        require!(
+            Clock::get()?.unix_timestamp >= self.pending_config_change.executable_after,
+            DoubleZeroError::ConfigChangeNotReady
+        );
+
+        apply_configuration_input(
+            &mut self.configuration_registry,
+            self.pending_config_change.input.clone(),
+            true
+        )?;
+        emit_config_changed(&self.configuration_registry, self.pending_config_change.queued_by);
+        emit!(ConfigChangeExecuted {
+            change_id,
+            executed_by: self.signer.key(),
+        });
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(change_id: u64)]
+pub struct CancelConfigChange<'info> {
+    #[account(
+        seeds = [seeds::PROGRAM_STATE],
+        bump = program_state.bump_registry.program_state_bump,
+    )]
+    pub program_state: Account<'info, ProgramStateAccount>,
+    #[account(
+        mut,
+        close = queued_by,
+        seeds = [seeds::PENDING_CONFIG_CHANGE, &change_id.to_le_bytes()],
+        bump
+    )]
+    pub pending_config_change: Account<'info, PendingConfigChange>,
+    /// CHECK: rent receiver, has to match the admin who queued the change
+    #[account(mut, address = pending_config_change.queued_by)]
+    pub queued_by: UncheckedAccount<'info>,
+    pub admin: Signer<'info>,
+}
+
+impl<'info> CancelConfigChange<'info> {
+    pub fn process(&mut self, change_id: u64) -> Result<()> {
+        require_keys_eq!(
+            self.admin.key(),
+            self.program_state.admin,
+            DoubleZeroError::UnauthorizedAdmin
+        );
+
+        emit!(ConfigChangeCancelled {
+            change_id,
+            cancelled_by: self.admin.key(),
+        });
+        Ok(())
+    }
+}
@@ -1,14 +1,173 @@
 use anchor_lang::prelude::*;
+use crate::{
+    calculate_ask_price::DiscountParams,
+    common::{
+        attestation_utils::{AttestationContext, SwapRateBand},
+        constant::MAX_ORACLE_QUORUM_SIZE,
+        structs::{DiscountCurve, DiscountClock}
+    }
+};
 
 #[account]
 #[derive(InitSpace, Debug)]
 pub struct ConfigurationRegistry {
     pub oracle_pubkey: Pubkey, // Public key of the swap oracle service
-    pub sol_quantity: u64,
+    pub sol_quantity: u64, // Default SOL amount (in lamports) quoted to clients
     pub price_maximum_age: i64, // Maximum acceptable age for oracle price data
     pub fills_consumer: Pubkey,
     // Price calculation
     pub coefficient: u64, // Coefficient of the discount function in basis points (0 <= coefficient <= 100_000_000)
     pub max_discount_rate: u64, // Maximum discount rate in basis points (0 <= max_discount_rate <= 10_000)
     pub min_discount_rate: u64, // Minimum discount rate in basis points (0 <= min_discount_rate <= 10_000)
-}
\ No newline at end of file
+    // Trade size bounds
+    pub min_sol_quantity: u64, // Minimum SOL amount (in lamports) accepted per buy
+    pub max_sol_quantity: u64, // Maximum SOL amount (in lamports) accepted per buy
+    // Oracle quorum
+    #[max_len(MAX_ORACLE_QUORUM_SIZE)]
+    pub oracle_pubkeys: Vec<Pubkey>, // Public keys of the oracles forming the quorum
+    pub oracle_quorum_threshold: u8, // Distinct signers required per quorum attestation (0 disables the quorum)
+    // Oracle key rotation
+    pub next_oracle_pubkey: Pubkey, // Oracle key taking over from oracle_pubkey (default when no rotation is scheduled)
+    pub next_oracle_activation_timestamp: i64, // Unix timestamp from which next_oracle_pubkey is accepted
+    pub oracle_retire_timestamp: i64, // Unix timestamp from which oracle_pubkey is no longer accepted
+    // Attestation format
+    pub disable_v1_attestations: bool, // Rejects the legacy v1 attestation message once clients migrated
+    // Replay protection
+    pub attestation_timestamp_tolerance: u32, // Seconds an attestation may predate the latest consumed attestation
+    // Per wallet limits (0 disables a limit)
+    pub max_trades_per_wallet_per_epoch: u64, // Maximum number of buys a single wallet can do per epoch
+    pub max_sol_per_wallet_per_epoch: u64, // Maximum SOL amount (in lamports) a single wallet can buy per epoch
+    // Global sale limit (0 disables the cap)
+    pub epoch_sol_cap: u64, // Maximum SOL amount (in lamports) sold per epoch across all buyers
+    // Timelocked configuration changes (0 applies economic changes immediately)
+    pub config_change_delay: u32, // Seconds a queued configuration change waits before it can be executed
+    pub next_config_change_id: u64, // Id assigned to the next queued configuration change
+    // Fills registry backpressure, utilization in basis points of the capacity (0 disables the watermarks)
+    pub fills_high_watermark_bps: u16, // Utilization at which FillsRegistryHighWatermark is emitted
+    pub fills_low_watermark_bps: u16, // Utilization the consumer has to drain down to after crossing the high watermark
+    pub stop_buys_at_high_watermark: bool, // Refuses buys between crossing the high watermark and reaching the low watermark
+    pub discount_curve: DiscountCurve, // Shape of the discount ramp between min_discount_rate and max_discount_rate
+    pub discount_clock: DiscountClock, // Unit the discount ramp counts in, the coefficient applies per slot or per second
+    pub discount_memory_bps: u16, // Share of the discount above min_discount_rate a trade carries over (0 resets the ramp)
+    // Oracle rate sanity checks (0 disables a check)
+    pub min_swap_rate: u64, // Lowest swap rate accepted from the oracles
+    pub max_swap_rate: u64, // Highest swap rate accepted from the oracles
+    pub max_swap_rate_deviation_bps: u16, // Maximum deviation from the reference swap rate, in basis points of it
+    pub reference_swap_rate_max_age: u32, // Seconds without a trade after which the next trade re-seeds the reference swap rate
+    // Oracle confidence (0 disables the threshold and the widening)
+    pub max_confidence_bps: u16, // Widest confidence accepted, in basis points of the swap rate, requires v3 attestations
+    pub confidence_widening_bps: u16, // Share of the confidence added to the swap rate the ask is priced from
+}
+
+impl ConfigurationRegistry {
+    pub fn discount_params(&self) -> DiscountParams {
+        DiscountParams {
+            curve: self.discount_curve,
+            coefficient: self.coefficient,
+            max_discount_rate: self.max_discount_rate,
+            min_discount_rate: self.min_discount_rate,
+            confidence_widening_bps: self.confidence_widening_bps,
+        }
+    }
+
+    pub fn has_pending_oracle_rotation(&self) -> bool {
+        self.next_oracle_pubkey != Pubkey::default()
+    }
+
+    /// Oracle keys whose single attestations are accepted at the given time.
+    /// Both keys are accepted between activation and retirement of a scheduled rotation.
+    pub fn accepted_oracle_pubkeys(&self, current_timestamp: i64) -> Vec<Pubkey> {
+        if !self.has_pending_oracle_rotation() {
+            return vec![self.oracle_pubkey];
+        }
+
+        let mut accepted = Vec::with_capacity(2);
+        if current_timestamp < self.oracle_retire_timestamp {
+            accepted.push(self.oracle_pubkey);
+        }
+        if current_timestamp >= self.next_oracle_activation_timestamp {
+            accepted.push(self.next_oracle_pubkey);
+        }
+        accepted
+    }
+
+    /// Promotes the next oracle key once the old key has been retired.
+    pub fn complete_oracle_rotation(&mut self, current_timestamp: i64) {
+        if self.has_pending_oracle_rotation() && current_timestamp >= self.oracle_retire_timestamp {
+            self.oracle_pubkey = self.next_oracle_pubkey;
+            self.clear_oracle_rotation();
+        }
+    }
+
+    pub fn swap_rate_band(&self) -> SwapRateBand {
+        SwapRateBand {
+            min_swap_rate: self.min_swap_rate,
+            max_swap_rate: self.max_swap_rate,
+            max_deviation_bps: self.max_swap_rate_deviation_bps,
+        }
+    }
+
+    pub fn attestation_context(&self, double_zero_mint: Pubkey) -> AttestationContext {
+        AttestationContext {
+            double_zero_mint,
+            disable_v1_attestations: self.disable_v1_attestations,
+            price_maximum_age: self.price_maximum_age,
+            max_confidence_bps: self.max_confidence_bps,
+        }
+    }
+
+    /// Zeroed fields appended by an upgrade read as disabled, except the trade size
+    /// bounds. Those start at the fixed SOL quantity buys were limited to before.
+    pub fn seed_migrated_defaults(&mut self) {
+        if self.max_sol_quantity == 0 {
+            self.min_sol_quantity = self.sol_quantity;
+            self.max_sol_quantity = self.sol_quantity;
+        }
+    }
+
+    pub fn clear_oracle_rotation(&mut self) {
+        self.next_oracle_pubkey = Pubkey::default();
+        self.next_oracle_activation_timestamp = 0;
+        self.oracle_retire_timestamp = 0;
+    }
+}
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::common::constant::DISCRIMINATOR_SIZE;
+
+    #[test]
+    fn test_migrated_registry_layout() {
+        // Registry as initialized before any field was appended, grown with zeroes.
+        let oracle_pubkey = Pubkey::new_unique();
+        let fills_consumer = Pubkey::new_unique();
+        let mut data = ConfigurationRegistry::DISCRIMINATOR.to_vec();
+        data.extend_from_slice(oracle_pubkey.as_ref());
+        data.extend_from_slice(&1_000_000_000u64.to_le_bytes()); // sol_quantity
+        data.extend_from_slice(&324i64.to_le_bytes()); // price_maximum_age
+        data.extend_from_slice(fills_consumer.as_ref());
+        data.extend_from_slice(&4_500u64.to_le_bytes()); // coefficient
+        data.extend_from_slice(&5_000u64.to_le_bytes()); // max_discount_rate
+        data.extend_from_slice(&1_000u64.to_le_bytes()); // min_discount_rate
+        data.resize(DISCRIMINATOR_SIZE + ConfigurationRegistry::INIT_SPACE, 0);
+
+        let mut registry = ConfigurationRegistry::try_deserialize(&mut &data[..]).unwrap();
+        assert_eq!(registry.oracle_pubkey, oracle_pubkey);
+        assert_eq!(registry.fills_consumer, fills_consumer);
+        assert_eq!(registry.min_discount_rate, 1_000);
+        assert!(registry.oracle_pubkeys.is_empty());
+        assert_eq!(registry.discount_curve, DiscountCurve::Linear);
+        assert_eq!(registry.discount_clock, DiscountClock::Slot);
+        assert_eq!(registry.next_oracle_pubkey, Pubkey::default());
+
+        registry.seed_migrated_defaults();
+        assert_eq!(registry.min_sol_quantity, 1_000_000_000);
+        assert_eq!(registry.max_sol_quantity, 1_000_000_000);
+
+        // The grown registry writes back to the same size.
+        let mut written = vec![0u8; data.len()];
+        registry.try_serialize(&mut &mut written[..]).unwrap();
+        assert_eq!(ConfigurationRegistry::try_deserialize(&mut &written[..]).unwrap().max_sol_quantity, 1_000_000_000);
+    }
+}
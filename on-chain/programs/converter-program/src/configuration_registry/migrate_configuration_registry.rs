@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+use crate::{
+    program_state::ProgramStateAccount,
+    configuration_registry::configuration_registry::ConfigurationRegistry,
+    common::{
+        account_utils::grow_account,
+        constant::DISCRIMINATOR_SIZE,
+        seeds,
+        events::system::AccountMigrated,
+        error::DoubleZeroError
+    },
+};
+
+/// Only the admin can call this
+#[derive(Accounts)]
+pub struct MigrateConfigurationRegistry<'info> {
+    /// CHECK: a registry written by an older program version does not deserialize until it is grown
+    #[account(
+        mut,
+        seeds = [seeds::CONFIGURATION_REGISTRY],
+        bump = program_state.bump_registry.configuration_registry_bump,
+        owner = crate::ID,
+    )]
+    pub configuration_registry: UncheckedAccount<'info>,
+    #[account(
+        seeds = [seeds::PROGRAM_STATE],
+        bump = program_state.bump_registry.program_state_bump,
+    )]
+    pub program_state: Account<'info, ProgramStateAccount>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MigrateConfigurationRegistry<'info> {
+    /// Grows the registry to the current layout after a program upgrade appended fields to it.
+    pub fn process(&mut self) -> Result<()> {
+        require_keys_eq!(
+            self.admin.key(),
+            self.program_state.admin,
+            DoubleZeroError::UnauthorizedAdmin
+        );
+
+        let account = self.configuration_registry.to_account_info();
+        let previous_len = account.data_len();
+        let new_len = DISCRIMINATOR_SIZE + ConfigurationRegistry::INIT_SPACE;
+        require!(previous_len < new_len, DoubleZeroError::AccountAlreadyMigrated);
+
+        grow_account(
+            &account,
+            &self.admin.to_account_info(),
+            &self.system_program.to_account_info(),
+            new_len,
+        )?;
+
+        let mut registry = ConfigurationRegistry::try_deserialize(&mut &account.try_borrow_data()?[..])?;
+        registry.seed_migrated_defaults();
+        registry.try_serialize(&mut &mut account.try_borrow_mut_data()?[..])?;
+
+        emit!(AccountMigrated {
+            account: account.key(),
+            previous_len: previous_len as u64,
+            new_len: new_len as u64,
+            migrated_by: self.admin.key(),
+        });
+        Ok(())
+    }
+}
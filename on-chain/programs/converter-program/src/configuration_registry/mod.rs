@@ -1,3 +1,6 @@
 pub mod configuration_registry;
+pub mod rotate_oracle_key;
 pub mod set_fills_consumer;
-pub mod update_configuration;
\ No newline at end of file
+pub mod update_configuration;
+pub mod config_timelock;
+pub mod migrate_configuration_registry;
\ No newline at end of file
@@ -0,0 +1,88 @@
+use anchor_lang::prelude::*;
+use crate::{
+    program_state::ProgramStateAccount,
+    configuration_registry::configuration_registry::ConfigurationRegistry,
+    common::{
+        seeds,
+        events::config::OracleKeyRotated,
+        error::DoubleZeroError
+    },
+};
+
+/// Only the admin can call this
+#[derive(Accounts)]
+pub struct RotateOracleKey<'info> {
+    #[account(
+        mut,
+        seeds = [seeds::CONFIGURATION_REGISTRY],
+        bump = program_state.bump_registry.configuration_registry_bump
+    )]
+    pub configuration_registry: Account<'info, ConfigurationRegistry>,
+    #[account(
+        seeds = [seeds::PROGRAM_STATE],
+        bump = program_state.bump_registry.program_state_bump,
+    )]
+    pub program_state: Account<'info, ProgramStateAccount>,
+    pub admin: Signer<'info>,
+}
+
+impl<'info> RotateOracleKey<'info> {
+    pub fn process(
+        &mut self,
+        next_oracle_pubkey: Pubkey,
+        activation_timestamp: i64,
+        retire_timestamp: i64
+    ) -> Result<()> {
+        require_keys_eq!(
+            self.admin.key(),
+            self.program_state.admin,
+            DoubleZeroError::UnauthorizedAdmin
+        );
+
+        let current_timestamp = Clock::get()?.unix_timestamp;
+        let registry = &mut self.configuration_registry;
+
+        // A finished rotation is promoted before scheduling the next one.
+        registry.complete_oracle_rotation(current_timestamp);
+
+        // A rotation that has not reached its overlap window yet can be rescheduled.
+        require!(
+            !registry.has_pending_oracle_rotation()
+                || current_timestamp < registry.next_oracle_activation_timestamp,
+            DoubleZeroError::OracleRotationInProgress
+        );
+
+        require!(
+            next_oracle_pubkey != Pubkey::default() && next_oracle_pubkey != registry.oracle_pubkey,
+            DoubleZeroError::InvalidOraclePublicKey
+        );
+
+        // Both keys have to be accepted for a while before the old key retires.
+        require!(
+            activation_timestamp < retire_timestamp && retire_timestamp > current_timestamp,
+            DoubleZeroError::InvalidOracleRotationWindow
+        );
+
+        // Swapping the oracle key is an economic change, it gets the same notice as a queued config change.
+        let earliest_activation = current_timestamp
+            .checked_add(registry.config_change_delay as i64)
+            .ok_or(DoubleZeroError::ArithmeticError)?;
+        require!(
+            registry.config_change_delay == 0 || activation_timestamp >= earliest_activation,
+            DoubleZeroError::OracleRotationNoticeTooShort
+        );
+
+        registry.next_oracle_pubkey = next_oracle_pubkey;
+        registry.next_oracle_activation_timestamp = activation_timestamp;
+        registry.oracle_retire_timestamp = retire_timestamp;
+
+        emit!(OracleKeyRotated {
+            rotated_by: self.admin.key(),
+            oracle_pubkey: registry.oracle_pubkey,
+            next_oracle_pubkey,
+            activation_timestamp,
+            retire_timestamp,
+        });
+        Ok(())
+    }
+}
@@ -5,11 +5,13 @@ use crate::{
     common::{
         seeds,
         error::DoubleZeroError,
-        events::config::ConfigChanged
+        events::config::ConfigChanged,
+        constant::MAX_ORACLE_QUORUM_SIZE,
+        structs::{DiscountCurve, DiscountClock}
     },
 };
 
-#[derive(AnchorSerialize, AnchorDeserialize)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
 pub struct ConfigurationRegistryInput {
     pub oracle_pubkey: Option<Pubkey>,
     pub sol_quantity: Option<u64>,
@@ -17,6 +19,53 @@ pub struct ConfigurationRegistryInput {
     pub coefficient: Option<u64>,
     pub max_discount_rate: Option<u64>,
     pub min_discount_rate: Option<u64>,
+    pub min_sol_quantity: Option<u64>,
+    pub max_sol_quantity: Option<u64>,
+    pub oracle_pubkeys: Option<Vec<Pubkey>>,
+    pub oracle_quorum_threshold: Option<u8>,
+    pub disable_v1_attestations: Option<bool>,
+    pub attestation_timestamp_tolerance: Option<u32>,
+    pub max_trades_per_wallet_per_epoch: Option<u64>,
+    pub max_sol_per_wallet_per_epoch: Option<u64>,
+    pub epoch_sol_cap: Option<u64>,
+    pub config_change_delay: Option<u32>, //in seconds
+    pub fills_high_watermark_bps: Option<u16>,
+    pub fills_low_watermark_bps: Option<u16>,
+    pub stop_buys_at_high_watermark: Option<bool>,
+    pub discount_curve: Option<DiscountCurve>,
+    pub discount_clock: Option<DiscountClock>,
+    pub discount_memory_bps: Option<u16>,
+    pub min_swap_rate: Option<u64>,
+    pub max_swap_rate: Option<u64>,
+    pub max_swap_rate_deviation_bps: Option<u16>,
+    pub reference_swap_rate_max_age: Option<u32>, //in seconds
+    pub max_confidence_bps: Option<u16>,
+    pub confidence_widening_bps: Option<u16>,
+}
+
+impl ConfigurationRegistryInput {
+    /// Whether the input changes the pricing economics, which integrators
+    /// have to be warned about through the timelock.
+    pub fn changes_economics(&self, registry: &ConfigurationRegistry) -> bool {
+        fn differs<T: PartialEq>(input: &Option<T>, current: &T) -> bool {
+            input.as_ref().is_some_and(|value| value != current)
+        }
+
+        differs(&self.oracle_pubkey, &registry.oracle_pubkey)
+            || differs(&self.sol_quantity, &registry.sol_quantity)
+            || differs(&self.price_maximum_age, &registry.price_maximum_age)
+            || differs(&self.coefficient, &registry.coefficient)
+            || differs(&self.max_discount_rate, &registry.max_discount_rate)
+            || differs(&self.min_discount_rate, &registry.min_discount_rate)
+            || differs(&self.min_sol_quantity, &registry.min_sol_quantity)
+            || differs(&self.max_sol_quantity, &registry.max_sol_quantity)
+            || differs(&self.oracle_pubkeys, &registry.oracle_pubkeys)
+            || differs(&self.oracle_quorum_threshold, &registry.oracle_quorum_threshold)
+            || differs(&self.discount_curve, &registry.discount_curve)
+            || differs(&self.discount_clock, &registry.discount_clock)
+            || differs(&self.discount_memory_bps, &registry.discount_memory_bps)
+            || differs(&self.confidence_widening_bps, &registry.confidence_widening_bps)
+    }
 }
 
 #[derive(Accounts)]
@@ -43,51 +92,249 @@ impl<'info> ConfigurationRegistryUpdate<'info> {
             DoubleZeroError::UnauthorizedAdmin
         );
 
-        if let Some(oracle_pubkey) = input.oracle_pubkey {
-            self.configuration_registry.oracle_pubkey = oracle_pubkey;
-        }
-        if let Some(sol_quantity) = input.sol_quantity {
-            self.configuration_registry.sol_quantity = sol_quantity;
-        }
-        if let Some(price_maximum_age) = input.price_maximum_age {
-            require!(price_maximum_age > 0, DoubleZeroError::InvalidPriceMaximumAge);
-            self.configuration_registry.price_maximum_age = price_maximum_age;
+        apply_configuration_input(&mut self.configuration_registry, input, false)?;
+        emit_config_changed(&self.configuration_registry, self.admin.key());
+        Ok(())
+    }
+}
+
+/// Validates the input and applies it to the registry.
+/// Once a config change delay is set, economic changes and delay reductions
+/// are only accepted from an executed timelocked change. Resubmitting the
+/// current economic values stays allowed.
+pub fn apply_configuration_input(
+    registry: &mut ConfigurationRegistry,
+    input: ConfigurationRegistryInput,
+    timelocked: bool,
+) -> Result<()> {
+    if !timelocked {
+        require!(
+            registry.config_change_delay == 0 || !input.changes_economics(registry),
+            DoubleZeroError::ConfigChangeRequiresTimelock
+        );
+        if let Some(config_change_delay) = input.config_change_delay {
+            require!(
+                config_change_delay >= registry.config_change_delay,
+                DoubleZeroError::ConfigChangeRequiresTimelock
+            );
         }
+    }
 
-        if let Some(coefficient) = input.coefficient {
-            require!(coefficient <= 100_000_000, DoubleZeroError::InvalidCoefficient);
-            self.configuration_registry.coefficient = coefficient;
+    if let Some(oracle_pubkey) = input.oracle_pubkey {
+        // Keys already part of a scheduled rotation leave it running,
+        // any other key replaces the oracle immediately.
+        if oracle_pubkey != registry.oracle_pubkey && oracle_pubkey != registry.next_oracle_pubkey {
+            registry.oracle_pubkey = oracle_pubkey;
+            registry.clear_oracle_rotation();
         }
+    }
+    if let Some(sol_quantity) = input.sol_quantity {
+        registry.sol_quantity = sol_quantity;
+    }
+    if let Some(price_maximum_age) = input.price_maximum_age {
+        require!(price_maximum_age > 0, DoubleZeroError::InvalidPriceMaximumAge);
+        registry.price_maximum_age = price_maximum_age;
+    }
+
+    if let Some(coefficient) = input.coefficient {
+        require!(coefficient <= 100_000_000, DoubleZeroError::InvalidCoefficient);
+        registry.coefficient = coefficient;
+    }
 
-        if let Some(max_discount_rate) = input.max_discount_rate {
-            require!(max_discount_rate <= 10_000, DoubleZeroError::InvalidMaxDiscountRate);
+    if let Some(max_discount_rate) = input.max_discount_rate {
+        require!(max_discount_rate <= 10_000, DoubleZeroError::InvalidMaxDiscountRate);
 
-            let min_rate = input.min_discount_rate
-                .unwrap_or(self.configuration_registry.min_discount_rate);
+        let min_rate = input.min_discount_rate
+            .unwrap_or(registry.min_discount_rate);
 
-            require!(max_discount_rate > min_rate, DoubleZeroError::InvalidMaxDiscountRate);
-            self.configuration_registry.max_discount_rate = max_discount_rate;
-        }
+        require!(max_discount_rate > min_rate, DoubleZeroError::InvalidMaxDiscountRate);
+        registry.max_discount_rate = max_discount_rate;
+    }
 
-        if let Some(min_discount_rate) = input.min_discount_rate {
+    if let Some(min_discount_rate) = input.min_discount_rate {
 
-            let max_rate = input.max_discount_rate
-                .unwrap_or(self.configuration_registry.max_discount_rate);
+        let max_rate = input.max_discount_rate
+            .unwrap_or(registry.max_discount_rate);
 
-            require!(min_discount_rate < max_rate, DoubleZeroError::InvalidMinDiscountRate);
-            self.configuration_registry.min_discount_rate = min_discount_rate;
-        }
+        require!(min_discount_rate < max_rate, DoubleZeroError::InvalidMinDiscountRate);
+        registry.min_discount_rate = min_discount_rate;
+    }
 
-        emit!(ConfigChanged {
-            changed_by: self.admin.key(),
-            oracle_pubkey: self.configuration_registry.oracle_pubkey,
-            sol_quantity: self.configuration_registry.sol_quantity,
-            price_maximum_age: self.configuration_registry.price_maximum_age,
-            coefficient: self.configuration_registry.coefficient,
-            max_discount_rate: self.configuration_registry.max_discount_rate,
-            min_discount_rate: self.configuration_registry.min_discount_rate,
-        });
+    if let Some(min_sol_quantity) = input.min_sol_quantity {
+        let max_quantity = input.max_sol_quantity
+            .unwrap_or(registry.max_sol_quantity);
 
-        Ok(())
+        require!(
+            min_sol_quantity > 0 && min_sol_quantity <= max_quantity,
+            DoubleZeroError::InvalidSolQuantity
+        );
+        registry.min_sol_quantity = min_sol_quantity;
+    }
+
+    if let Some(max_sol_quantity) = input.max_sol_quantity {
+        let min_quantity = input.min_sol_quantity
+            .unwrap_or(registry.min_sol_quantity);
+
+        require!(max_sol_quantity >= min_quantity, DoubleZeroError::InvalidSolQuantity);
+        registry.max_sol_quantity = max_sol_quantity;
+    }
+
+    if let Some(oracle_pubkeys) = input.oracle_pubkeys {
+        require!(
+            oracle_pubkeys.len() <= MAX_ORACLE_QUORUM_SIZE as usize,
+            DoubleZeroError::InvalidOracleQuorum
+        );
+
+        let has_duplicates = oracle_pubkeys
+            .iter()
+            .enumerate()
+            .any(|(i, key)| oracle_pubkeys[..i].contains(key));
+        require!(!has_duplicates, DoubleZeroError::InvalidOracleQuorum);
+
+        registry.oracle_pubkeys = oracle_pubkeys;
+    }
+
+    if let Some(oracle_quorum_threshold) = input.oracle_quorum_threshold {
+        registry.oracle_quorum_threshold = oracle_quorum_threshold;
+    }
+
+    // Threshold can never exceed the number of configured oracles.
+    require!(
+        registry.oracle_quorum_threshold as usize
+            <= registry.oracle_pubkeys.len(),
+        DoubleZeroError::InvalidOracleQuorum
+    );
+
+    if let Some(disable_v1_attestations) = input.disable_v1_attestations {
+        registry.disable_v1_attestations = disable_v1_attestations;
+    }
+
+    if let Some(attestation_timestamp_tolerance) = input.attestation_timestamp_tolerance {
+        registry.attestation_timestamp_tolerance = attestation_timestamp_tolerance;
+    }
+
+    if let Some(max_trades_per_wallet_per_epoch) = input.max_trades_per_wallet_per_epoch {
+        registry.max_trades_per_wallet_per_epoch = max_trades_per_wallet_per_epoch;
+    }
+
+    if let Some(max_sol_per_wallet_per_epoch) = input.max_sol_per_wallet_per_epoch {
+        registry.max_sol_per_wallet_per_epoch = max_sol_per_wallet_per_epoch;
+    }
+
+    if let Some(epoch_sol_cap) = input.epoch_sol_cap {
+        registry.epoch_sol_cap = epoch_sol_cap;
+    }
+
+    if let Some(config_change_delay) = input.config_change_delay {
+        registry.config_change_delay = config_change_delay;
+    }
+
+    if let Some(fills_high_watermark_bps) = input.fills_high_watermark_bps {
+        registry.fills_high_watermark_bps = fills_high_watermark_bps;
+    }
+
+    if let Some(fills_low_watermark_bps) = input.fills_low_watermark_bps {
+        registry.fills_low_watermark_bps = fills_low_watermark_bps;
+    }
+
+    if let Some(stop_buys_at_high_watermark) = input.stop_buys_at_high_watermark {
+        registry.stop_buys_at_high_watermark = stop_buys_at_high_watermark;
+    }
+
+    if let Some(discount_curve) = input.discount_curve {
+        let is_valid = match discount_curve {
+            DiscountCurve::Linear | DiscountCurve::LinearWithGrace { .. } => true,
+            DiscountCurve::Exponential { half_life } => half_life > 0,
+            DiscountCurve::Stepped { step_length, step_rate } => step_length > 0 && step_rate <= 10_000,
+        };
+        require!(is_valid, DoubleZeroError::InvalidDiscountCurve);
+        registry.discount_curve = discount_curve;
+    }
+
+    if let Some(discount_clock) = input.discount_clock {
+        registry.discount_clock = discount_clock;
     }
+
+    if let Some(discount_memory_bps) = input.discount_memory_bps {
+        // A full memory would never let the discount come back down.
+        require!(discount_memory_bps < 10_000, DoubleZeroError::InvalidDiscountMemory);
+        registry.discount_memory_bps = discount_memory_bps;
+    }
+
+    if let Some(min_swap_rate) = input.min_swap_rate {
+        registry.min_swap_rate = min_swap_rate;
+    }
+
+    if let Some(max_swap_rate) = input.max_swap_rate {
+        registry.max_swap_rate = max_swap_rate;
+    }
+
+    if let Some(max_swap_rate_deviation_bps) = input.max_swap_rate_deviation_bps {
+        registry.max_swap_rate_deviation_bps = max_swap_rate_deviation_bps;
+    }
+
+    if let Some(reference_swap_rate_max_age) = input.reference_swap_rate_max_age {
+        registry.reference_swap_rate_max_age = reference_swap_rate_max_age;
+    }
+
+    if let Some(max_confidence_bps) = input.max_confidence_bps {
+        registry.max_confidence_bps = max_confidence_bps;
+    }
+
+    if let Some(confidence_widening_bps) = input.confidence_widening_bps {
+        registry.confidence_widening_bps = confidence_widening_bps;
+    }
+
+    // Low watermark has to sit below an enabled high watermark.
+    require!(
+        registry.fills_high_watermark_bps <= 10_000
+            && (registry.fills_high_watermark_bps == 0
+                || registry.fills_low_watermark_bps < registry.fills_high_watermark_bps),
+        DoubleZeroError::InvalidFillsWatermark
+    );
+
+    // Swap rate bounds cannot exclude every rate, and a reference rate the deviation
+    // guard compares against has to expire, or a lasting market move would stop all trades.
+    require!(
+        (registry.max_swap_rate == 0 || registry.min_swap_rate <= registry.max_swap_rate)
+            && registry.max_swap_rate_deviation_bps <= 10_000
+            && (registry.max_swap_rate_deviation_bps == 0 || registry.reference_swap_rate_max_age > 0),
+        DoubleZeroError::InvalidSwapRateBand
+    );
+
+    Ok(())
+}
+
+pub fn emit_config_changed(registry: &ConfigurationRegistry, changed_by: Pubkey) {
+    emit!(ConfigChanged {
+        changed_by,
+        oracle_pubkey: registry.oracle_pubkey,
+        sol_quantity: registry.sol_quantity,
+        price_maximum_age: registry.price_maximum_age,
+        coefficient: registry.coefficient,
+        max_discount_rate: registry.max_discount_rate,
+        min_discount_rate: registry.min_discount_rate,
+        min_sol_quantity: registry.min_sol_quantity,
+        max_sol_quantity: registry.max_sol_quantity,
+        oracle_pubkeys: registry.oracle_pubkeys.clone(),
+        oracle_quorum_threshold: registry.oracle_quorum_threshold,
+        disable_v1_attestations: registry.disable_v1_attestations,
+        attestation_timestamp_tolerance: registry.attestation_timestamp_tolerance,
+        max_trades_per_wallet_per_epoch: registry.max_trades_per_wallet_per_epoch,
+        max_sol_per_wallet_per_epoch: registry.max_sol_per_wallet_per_epoch,
+        epoch_sol_cap: registry.epoch_sol_cap,
+        config_change_delay: registry.config_change_delay,
+        fills_high_watermark_bps: registry.fills_high_watermark_bps,
+        fills_low_watermark_bps: registry.fills_low_watermark_bps,
+        stop_buys_at_high_watermark: registry.stop_buys_at_high_watermark,
+        discount_curve: registry.discount_curve,
+        discount_clock: registry.discount_clock,
+        discount_memory_bps: registry.discount_memory_bps,
+        min_swap_rate: registry.min_swap_rate,
+        max_swap_rate: registry.max_swap_rate,
+        max_swap_rate_deviation_bps: registry.max_swap_rate_deviation_bps,
+        reference_swap_rate_max_age: registry.reference_swap_rate_max_age,
+        max_confidence_bps: registry.max_confidence_bps,
+        confidence_widening_bps: registry.confidence_widening_bps,
+    });
 }
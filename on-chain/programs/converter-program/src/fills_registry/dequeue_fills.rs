@@ -5,14 +5,17 @@ use crate::{
     common::{
         seeds,
         error::DoubleZeroError,
-        events::fill_consumer::FillsDequeued,
-        constant::MAX_FILLS_QUEUE_SIZE
+        events::fill_consumer::{FillsDequeued, FillsDequeuedDetailed},
+        constant::{PAUSE_DEQUEUES, MAX_DETAILED_DEQUEUE_FILLS}
     },
     program_state::ProgramStateAccount,
     configuration_registry::configuration_registry::ConfigurationRegistry,
     fills_registry::fills_registry::{
         FillsRegistry,
+        FillsQueue,
         DequeueFillsResult,
+        DequeueFillsDetailedResult,
+        DequeuedFillSlice,
         Fill
     },
 };
@@ -37,11 +40,106 @@ pub struct DequeueFills<'info> {
     pub signer: Signer<'info>
 }
 
+/// What the amount passed to a dequeue is measured in.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub enum DequeueLimit {
+    Sol(u64),
+    Token2z(u64),
+}
+
+impl DequeueLimit {
+    fn max_amount(&self) -> u64 {
+        match *self {
+            DequeueLimit::Sol(max_sol_amount) => max_sol_amount,
+            DequeueLimit::Token2z(max_2z_amount) => max_2z_amount,
+        }
+    }
+
+    fn amount_of(&self, fill: &Fill) -> u64 {
+        match self {
+            DequeueLimit::Sol(_) => fill.sol_in,
+            DequeueLimit::Token2z(_) => fill.token_2z_out,
+        }
+    }
+
+    /// Takes `amount` out of the fill, the other side is split pro rata and rounded down.
+    fn split(&self, fill: &Fill, amount: u64) -> Result<Fill> {
+        let (total, other_total) = match self {
+            DequeueLimit::Sol(_) => (fill.sol_in, fill.token_2z_out),
+            DequeueLimit::Token2z(_) => (fill.token_2z_out, fill.sol_in),
+        };
+        let other_amount = (other_total as u128)
+            .checked_mul(amount as u128)
+            .ok_or(DoubleZeroError::ArithmeticError)?
+            .checked_div(total as u128)
+            .ok_or(DoubleZeroError::ArithmeticError)?
+            .try_into()
+            .map_err(|_| DoubleZeroError::ArithmeticError)?;
+
+        // It keeps the buyer, slot, price and sequence of the original fill.
+        let (sol_in, token_2z_out) = match self {
+            DequeueLimit::Sol(_) => (amount, other_amount),
+            DequeueLimit::Token2z(_) => (other_amount, amount),
+        };
+        Ok(Fill { sol_in, token_2z_out, ..*fill })
+    }
+}
+
 impl<'info> DequeueFills<'info> {
     pub fn process(
         &mut self,
-        max_sol_amount: u64,
+        limit: DequeueLimit,
     ) -> Result<DequeueFillsResult> {
+        let (dequeue_fills_result, _) = self.dequeue(limit, None)?;
+        self.emit_dequeued(&dequeue_fills_result)?;
+        Ok(dequeue_fills_result)
+    }
+
+    /// Same as `process`, also listing the consumed fill slices and what is left of the
+    /// head fill. At most `MAX_DETAILED_DEQUEUE_FILLS` slices fit in the return data,
+    /// so a detailed dequeue stops there even below the limit.
+    pub fn process_detailed(
+        &mut self,
+        limit: DequeueLimit,
+    ) -> Result<DequeueFillsDetailedResult> {
+        let mut slices = Vec::with_capacity(MAX_DETAILED_DEQUEUE_FILLS);
+        let (dequeue_fills_result, head_remainder) = self.dequeue(limit, Some(&mut slices))?;
+        self.emit_dequeued(&dequeue_fills_result)?;
+
+        emit!(FillsDequeuedDetailed {
+            requester: self.signer.key(),
+            slices: slices.clone(),
+            head_remainder: head_remainder.clone(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(DequeueFillsDetailedResult {
+            sol_dequeued: dequeue_fills_result.sol_dequeued,
+            token_2z_dequeued: dequeue_fills_result.token_2z_dequeued,
+            fills_consumed: dequeue_fills_result.fills_consumed,
+            slices,
+            head_remainder,
+        })
+    }
+
+    fn emit_dequeued(&self, dequeue_fills_result: &DequeueFillsResult) -> Result<()> {
+        emit!(FillsDequeued {
+            requester: self.signer.key(),
+            sol_dequeued: dequeue_fills_result.sol_dequeued,
+            token_2z_dequeued: dequeue_fills_result.token_2z_dequeued,
+            fills_consumed: dequeue_fills_result.fills_consumed,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Consumes fills up to the limit, recording each consumed slice when `slices` is given.
+    /// Returns what is left of the head fill if it was only partly consumed.
+    fn dequeue(
+        &mut self,
+        limit: DequeueLimit,
+        mut slices: Option<&mut Vec<DequeuedFillSlice>>,
+    ) -> Result<(DequeueFillsResult, Option<DequeuedFillSlice>)> {
 
         require_keys_eq!(
             self.signer.key(),
@@ -49,54 +147,74 @@ impl<'info> DequeueFills<'info> {
             DoubleZeroError::UnauthorizedFillConsumer
         );
 
-        require!(max_sol_amount > 0, DoubleZeroError::InvalidMaxSolAmount);
-        
-        let fills_registry = &mut self.fills_registry.load_mut()?;
+        self.program_state.require_not_paused(PAUSE_DEQUEUES)?;
+
+        // The oldest fills may already sit in the new registry while they are being moved.
+        require_keys_eq!(
+            self.program_state.pending_fills_registry,
+            Pubkey::default(),
+            DoubleZeroError::FillsMigrationInProgress
+        );
+
+        let max_amount = limit.max_amount();
+        match limit {
+            DequeueLimit::Sol(_) => require!(max_amount > 0, DoubleZeroError::InvalidMaxSolAmount),
+            DequeueLimit::Token2z(_) => require!(max_amount > 0, DoubleZeroError::InvalidMax2zAmount),
+        }
+
+        let mut data = self.fills_registry.as_ref().try_borrow_mut_data()?;
+        let FillsQueue { registry: fills_registry, fills } = FillsQueue::from_account_data(&mut data)?;
         
         // Dequeue fills.
+        let mut amount_dequeued = 0u64; // measured like the limit
         let mut sol_dequeued = 0u64;
         let mut token_2z_dequeued = 0u64;
         let mut fills_consumed = 0u64;
+        let mut head_remainder = None;
 
         require!(fills_registry.count > 0, DoubleZeroError::EmptyFillsRegistry);
 
-        // Consume fills until max_sol_amount reached.
-        while fills_registry.count > 0 && sol_dequeued < max_sol_amount {
+        // Consume fills until the limit is reached.
+        while fills_registry.count > 0 && amount_dequeued < max_amount {
+            if let Some(slices) = &slices {
+                if slices.len() >= MAX_DETAILED_DEQUEUE_FILLS {
+                    break;
+                }
+            }
+
             let head_index = fills_registry.head as usize;
-            let next_entry = &fills_registry.fills[head_index];
-            let remaining_sol = max_sol_amount - sol_dequeued; // safe, can't underflow
+            let next_entry = &fills[head_index];
+            let remaining_amount = max_amount - amount_dequeued; // safe, can't underflow
 
-            let dequeued_fill = if next_entry.sol_in <= remaining_sol {
+            let dequeued_fill = if limit.amount_of(next_entry) <= remaining_amount {
                 // Full dequeue.
                 let fill = *next_entry; // copy the entire fill
-                fills_registry.head = (fills_registry.head + 1) % MAX_FILLS_QUEUE_SIZE as u64;
+                fills_registry.head = (fills_registry.head + 1) % fills_registry.capacity;
                 fills_registry.count -= 1;
                 fills_consumed += 1;
                 fill
             } else {
                 // Partial dequeue.
-                let token_2z_dequeued = (next_entry.token_2z_out as u128)
-                    .checked_mul(remaining_sol as u128)
-                    .ok_or(DoubleZeroError::ArithmeticError)?
-                    .checked_div(next_entry.sol_in as u128)
-                    .ok_or(DoubleZeroError::ArithmeticError)?
-                    .try_into()
-                    .map_err(|_| DoubleZeroError::ArithmeticError)?;
+                let dequeued_part = limit.split(next_entry, remaining_amount)?;
 
                 // Updated remainder fill.
-                fills_registry.fills[head_index] = Fill {
-                    sol_in: next_entry.sol_in - remaining_sol,
-                    token_2z_out: next_entry.token_2z_out - token_2z_dequeued
+                let remainder = Fill {
+                    sol_in: next_entry.sol_in - dequeued_part.sol_in,
+                    token_2z_out: next_entry.token_2z_out - dequeued_part.token_2z_out,
+                    ..*next_entry
                 };
+                fills[head_index] = remainder;
+                head_remainder = Some(DequeuedFillSlice::from(&remainder));
 
-                Fill {
-                    sol_in: remaining_sol,
-                    token_2z_out: token_2z_dequeued,
-                }
+                dequeued_part
             };
 
+            amount_dequeued += limit.amount_of(&dequeued_fill);
             sol_dequeued += dequeued_fill.sol_in;
             token_2z_dequeued += dequeued_fill.token_2z_out;
+            if let Some(slices) = slices.as_deref_mut() {
+                slices.push(DequeuedFillSlice::from(&dequeued_fill));
+            }
         }
 
         fills_registry.total_sol_pending = fills_registry.total_sol_pending
@@ -113,14 +231,6 @@ impl<'info> DequeueFills<'info> {
             fills_consumed,
         };
 
-        emit!(FillsDequeued {
-            requester: self.signer.key(),
-            sol_dequeued: dequeue_fills_result.sol_dequeued,
-            token_2z_dequeued: dequeue_fills_result.token_2z_dequeued,
-            fills_consumed: dequeue_fills_result.fills_consumed,
-            timestamp: Clock::get()?.unix_timestamp,
-        });
-
-        Ok(dequeue_fills_result)
+        Ok((dequeue_fills_result, head_remainder))
     }
-}
\ No newline at end of file
+}
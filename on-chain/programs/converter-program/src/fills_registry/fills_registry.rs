@@ -1,20 +1,137 @@
-use crate::common::constant::MAX_FILLS_QUEUE_SIZE;
+use crate::common::{
+    constant::{DISCRIMINATOR_SIZE, FILLS_REGISTRY_DISCRIMINATOR},
+    error::DoubleZeroError
+};
 use anchor_lang::prelude::*;
+use std::mem::size_of;
 
-#[account(zero_copy)]
+// Fills got richer in v2, so the registry carries its own discriminator
+// and a legacy (v1) registry can never be read with this layout.
+// The header is followed by `capacity` fills in the account data.
+#[account(zero_copy, discriminator = FILLS_REGISTRY_DISCRIMINATOR)]
 pub struct FillsRegistry {
     pub total_sol_pending: u64,      // Total SOL in not dequeued fills
     pub total_2z_pending: u64,       // Total 2Z in not dequeued fills
-    pub fills: [Fill; MAX_FILLS_QUEUE_SIZE],
     pub head: u64,   // index of oldest element
     pub tail: u64,   // index to insert next element
     pub count: u64,  // number of valid elements
+    pub next_fill_sequence: u64, // sequence number given to the next enqueued fill
+    pub capacity: u64, // number of fills the account has room for
+}
+
+impl FillsRegistry {
+    /// Number of fills an account of `data_len` bytes has room for.
+    pub fn capacity_for(data_len: usize) -> u64 {
+        (data_len.saturating_sub(DISCRIMINATOR_SIZE + size_of::<FillsRegistry>()) / size_of::<Fill>()) as u64
+    }
+
+    /// Initializes a new registry, its capacity follows from the account size.
+    pub fn init(fills_registry: &AccountLoader<FillsRegistry>, next_fill_sequence: u64) -> Result<()> {
+        let capacity = Self::capacity_for(fills_registry.as_ref().data_len());
+        require!(capacity > 0, DoubleZeroError::InvalidFillsCapacity);
+
+        let registry = &mut fills_registry.load_init()?;
+        registry.capacity = capacity;
+        registry.next_fill_sequence = next_fill_sequence;
+        Ok(())
+    }
+
+    /// Share of the capacity taken by pending fills, in basis points.
+    pub fn utilization_bps(&self) -> u64 {
+        self.count * 10_000 / self.capacity.max(1)
+    }
+
+    /// Read only counterpart of `FillsQueue::from_account_data`.
+    pub fn from_account_data(data: &[u8]) -> Result<(&FillsRegistry, &[Fill])> {
+        require!(
+            data.len() >= DISCRIMINATOR_SIZE + size_of::<FillsRegistry>(),
+            DoubleZeroError::InvalidFillsRegistry
+        );
+        let (header, fills) = data[DISCRIMINATOR_SIZE..].split_at(size_of::<FillsRegistry>());
+        let registry: &FillsRegistry = bytemuck::from_bytes(header);
+
+        let fills_size = registry.capacity as usize * size_of::<Fill>();
+        let fills = fills.get(..fills_size).ok_or(DoubleZeroError::InvalidFillsRegistry)?;
+        Ok((registry, bytemuck::cast_slice(fills)))
+    }
+}
+
+/// Registry header together with its fills, borrowed from the account data.
+pub struct FillsQueue<'a> {
+    pub registry: &'a mut FillsRegistry,
+    pub fills: &'a mut [Fill],
+}
+
+impl<'a> FillsQueue<'a> {
+    /// `data` is the whole account data, discriminator included.
+    pub fn from_account_data(data: &'a mut [u8]) -> Result<Self> {
+        require!(
+            data.len() >= DISCRIMINATOR_SIZE + size_of::<FillsRegistry>(),
+            DoubleZeroError::InvalidFillsRegistry
+        );
+        let (header, fills) = data[DISCRIMINATOR_SIZE..].split_at_mut(size_of::<FillsRegistry>());
+        let registry: &mut FillsRegistry = bytemuck::from_bytes_mut(header);
+
+        let fills_size = registry.capacity as usize * size_of::<Fill>();
+        let fills = fills.get_mut(..fills_size).ok_or(DoubleZeroError::InvalidFillsRegistry)?;
+        Ok(Self {
+            registry,
+            fills: bytemuck::cast_slice_mut(fills),
+        })
+    }
+
+    /// Appends a fill at the tail and stamps it with the next sequence number.
+    pub fn enqueue(&mut self, mut fill: Fill) -> Result<u64> {
+        fill.sequence = self.registry.next_fill_sequence;
+        self.push(fill)?;
+        self.registry.next_fill_sequence += 1;
+        Ok(fill.sequence)
+    }
+
+    /// Appends a fill at the tail as is, used when fills move between registries.
+    pub fn push(&mut self, fill: Fill) -> Result<()> {
+        let registry = &mut *self.registry;
+        require!(registry.count < registry.capacity, DoubleZeroError::RegistryFull);
+
+        // Insert the new fill.
+        let tail_index = registry.tail as usize;
+        self.fills[tail_index] = fill;
+
+        // Update tail and count.
+        registry.tail = (registry.tail + 1) % registry.capacity;
+        registry.count += 1;
+
+        registry.total_sol_pending += fill.sol_in;
+        registry.total_2z_pending += fill.token_2z_out;
+        Ok(())
+    }
+
+    /// Removes the oldest fill, if any.
+    pub fn pop(&mut self) -> Option<Fill> {
+        let registry = &mut *self.registry;
+        if registry.count == 0 {
+            return None;
+        }
+
+        let fill = self.fills[registry.head as usize];
+        registry.head = (registry.head + 1) % registry.capacity;
+        registry.count -= 1;
+
+        registry.total_sol_pending -= fill.sol_in;
+        registry.total_2z_pending -= fill.token_2z_out;
+        Some(fill)
+    }
 }
 
 #[zero_copy]
 pub struct Fill {
     pub sol_in: u64,
-    pub token_2z_out: u64
+    pub token_2z_out: u64,
+    pub ask_price: u64,  // Ask price the fill was executed at
+    pub sequence: u64,   // Monotonically increasing across the registry
+    pub slot: u64,       // Slot of the trade
+    pub timestamp: i64,  // Unix timestamp of the trade
+    pub buyer: Pubkey,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
@@ -22,4 +139,63 @@ pub struct DequeueFillsResult {
     pub sol_dequeued: u64,
     pub token_2z_dequeued: u64,
     pub fills_consumed: u64
-}
\ No newline at end of file
+}
+
+/// Part of a fill taken by a detailed dequeue, or left at the head after it.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct DequeuedFillSlice {
+    pub sequence: u64,
+    pub sol_in: u64,
+    pub token_2z_out: u64,
+}
+
+impl From<&Fill> for DequeuedFillSlice {
+    fn from(fill: &Fill) -> Self {
+        Self {
+            sequence: fill.sequence,
+            sol_in: fill.sol_in,
+            token_2z_out: fill.token_2z_out,
+        }
+    }
+}
+
+/// Fill as returned by `peek_fills`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct PeekedFill {
+    pub sol_in: u64,
+    pub token_2z_out: u64,
+    pub ask_price: u64,
+    pub sequence: u64,
+    pub slot: u64,
+    pub timestamp: i64,
+    pub buyer: Pubkey,
+}
+
+impl From<&Fill> for PeekedFill {
+    fn from(fill: &Fill) -> Self {
+        Self {
+            sol_in: fill.sol_in,
+            token_2z_out: fill.token_2z_out,
+            ask_price: fill.ask_price,
+            sequence: fill.sequence,
+            slot: fill.slot,
+            timestamp: fill.timestamp,
+            buyer: fill.buyer,
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct PeekFillsResult {
+    pub count: u64,   // pending fills in the registry
+    pub fills: Vec<PeekedFill>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct DequeueFillsDetailedResult {
+    pub sol_dequeued: u64,
+    pub token_2z_dequeued: u64,
+    pub fills_consumed: u64,
+    pub slices: Vec<DequeuedFillSlice>,
+    pub head_remainder: Option<DequeuedFillSlice>,
+}
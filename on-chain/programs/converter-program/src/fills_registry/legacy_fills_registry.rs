@@ -0,0 +1,19 @@
+use crate::common::constant::{LEGACY_FILLS_REGISTRY_DISCRIMINATOR, LEGACY_MAX_FILLS_QUEUE_SIZE};
+use anchor_lang::prelude::*;
+
+/// v1 fills registry layout, only read to upgrade existing deployments.
+#[account(zero_copy, discriminator = LEGACY_FILLS_REGISTRY_DISCRIMINATOR)]
+pub struct LegacyFillsRegistry {
+    pub total_sol_pending: u64,
+    pub total_2z_pending: u64,
+    pub fills: [LegacyFill; LEGACY_MAX_FILLS_QUEUE_SIZE],
+    pub head: u64,
+    pub tail: u64,
+    pub count: u64,
+}
+
+#[zero_copy]
+pub struct LegacyFill {
+    pub sol_in: u64,
+    pub token_2z_out: u64
+}
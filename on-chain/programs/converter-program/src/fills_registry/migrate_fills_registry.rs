@@ -0,0 +1,216 @@
+use anchor_lang::prelude::*;
+use crate::{
+    common::{
+        seeds,
+        error::DoubleZeroError,
+        constant::{DISCRIMINATOR_SIZE, FILLS_REGISTRY_DISCRIMINATOR, LEGACY_FILLS_REGISTRY_DISCRIMINATOR},
+        events::fill_consumer::{FillsMigrationStarted, FillsRegistryMigrated, FillsRegistryClosed}
+    },
+    program_state::ProgramStateAccount,
+    fills_registry::{
+        fills_registry::{FillsRegistry, FillsQueue},
+        legacy_fills_registry::LegacyFillsRegistry
+    },
+};
+
+#[derive(Accounts)]
+pub struct BeginFillsMigration<'info> {
+    pub admin: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [seeds::PROGRAM_STATE],
+        bump = program_state.bump_registry.program_state_bump,
+    )]
+    pub program_state: Account<'info, ProgramStateAccount>,
+    #[account(address = program_state.fills_registry_address)]
+    pub previous_fills_registry: AccountLoader<'info, FillsRegistry>,
+    #[account(zero)]
+    pub fills_registry: AccountLoader<'info, FillsRegistry>,
+}
+
+impl<'info> BeginFillsMigration<'info> {
+    pub fn process(&mut self) -> Result<()> {
+        require_keys_eq!(
+            self.admin.key(),
+            self.program_state.admin,
+            DoubleZeroError::UnauthorizedAdmin
+        );
+        require_keys_eq!(
+            self.program_state.pending_fills_registry,
+            Pubkey::default(),
+            DoubleZeroError::FillsMigrationInProgress
+        );
+
+        // Sequence numbers carry on from the previous registry.
+        let next_fill_sequence = self.previous_fills_registry.load()?.next_fill_sequence;
+        FillsRegistry::init(&self.fills_registry, next_fill_sequence)?;
+        self.program_state.pending_fills_registry = self.fills_registry.key();
+
+        emit!(FillsMigrationStarted {
+            previous_fills_registry: self.previous_fills_registry.key(),
+            fills_registry: self.fills_registry.key(),
+            started_by: self.admin.key(),
+        });
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct MigrateFills<'info> {
+    pub admin: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [seeds::PROGRAM_STATE],
+        bump = program_state.bump_registry.program_state_bump,
+    )]
+    pub program_state: Account<'info, ProgramStateAccount>,
+    #[account(
+        mut,
+        address = program_state.fills_registry_address
+    )]
+    pub previous_fills_registry: AccountLoader<'info, FillsRegistry>,
+    #[account(
+        mut,
+        address = program_state.pending_fills_registry @ DoubleZeroError::NoFillsMigrationInProgress
+    )]
+    pub fills_registry: AccountLoader<'info, FillsRegistry>,
+    /// CHECK: only receives the lamports of the previous registry once it is closed.
+    #[account(mut)]
+    pub recipient: UncheckedAccount<'info>,
+}
+
+impl<'info> MigrateFills<'info> {
+    /// Moves up to `max_fills` fills, oldest first, into the new registry.
+    /// Once the previous registry is empty the program switches over to the new one
+    /// and the previous registry is closed.
+    pub fn process(&mut self, max_fills: u32) -> Result<()> {
+        require_keys_eq!(
+            self.admin.key(),
+            self.program_state.admin,
+            DoubleZeroError::UnauthorizedAdmin
+        );
+
+        let fills_left = {
+            let mut previous_data = self.previous_fills_registry.as_ref().try_borrow_mut_data()?;
+            let previous_fills_queue = &mut FillsQueue::from_account_data(&mut previous_data)?;
+            let mut data = self.fills_registry.as_ref().try_borrow_mut_data()?;
+            let fills_queue = &mut FillsQueue::from_account_data(&mut data)?;
+
+            let mut fills_moved = 0u32;
+            while fills_moved < max_fills {
+                let Some(fill) = previous_fills_queue.pop() else {
+                    break;
+                };
+                fills_queue.push(fill)?;
+                fills_moved += 1;
+            }
+
+            msg!("Moved {} fills, {} left", fills_moved, previous_fills_queue.registry.count);
+            previous_fills_queue.registry.count
+        };
+
+        if fills_left == 0 {
+            self.program_state.fills_registry_address = self.fills_registry.key();
+            self.program_state.pending_fills_registry = Pubkey::default();
+            self.previous_fills_registry.close(self.recipient.to_account_info())?;
+
+            emit!(FillsRegistryMigrated {
+                previous_fills_registry: self.previous_fills_registry.key(),
+                fills_registry: self.fills_registry.key(),
+                migrated_by: self.admin.key(),
+            });
+            emit!(FillsRegistryClosed {
+                fills_registry: self.previous_fills_registry.key(),
+                recipient: self.recipient.key(),
+                closed_by: self.admin.key(),
+            });
+        }
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct CloseFillsRegistry<'info> {
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [seeds::PROGRAM_STATE],
+        bump = program_state.bump_registry.program_state_bump,
+    )]
+    pub program_state: Account<'info, ProgramStateAccount>,
+    /// CHECK: either fills registry layout, told apart by its discriminator.
+    #[account(mut, owner = crate::ID)]
+    pub fills_registry: UncheckedAccount<'info>,
+    /// CHECK: only receives the lamports of the closed registry.
+    #[account(mut)]
+    pub recipient: UncheckedAccount<'info>,
+}
+
+impl<'info> CloseFillsRegistry<'info> {
+    /// Closes an empty registry the program no longer uses, such as the legacy
+    /// registry left behind by an upgrade.
+    pub fn process(&mut self) -> Result<()> {
+        require_keys_eq!(
+            self.admin.key(),
+            self.program_state.admin,
+            DoubleZeroError::UnauthorizedAdmin
+        );
+
+        let fills_registry_key = self.fills_registry.key();
+        require!(
+            fills_registry_key != self.program_state.fills_registry_address
+                && fills_registry_key != self.program_state.pending_fills_registry,
+            DoubleZeroError::FillsRegistryInUse
+        );
+
+        let info = self.fills_registry.to_account_info();
+        let fills_left = {
+            let data = info.try_borrow_data()?;
+            match data.get(..DISCRIMINATOR_SIZE) {
+                Some(FILLS_REGISTRY_DISCRIMINATOR) => pending_fills::<FillsRegistry>(&data)?,
+                Some(LEGACY_FILLS_REGISTRY_DISCRIMINATOR) => pending_fills::<LegacyFillsRegistry>(&data)?,
+                _ => return err!(DoubleZeroError::InvalidFillsRegistry),
+            }
+        };
+        require!(fills_left == 0, DoubleZeroError::FillsRegistryNotEmpty);
+
+        // Same as closing through Anchor, the account goes back to the system program.
+        let recipient = self.recipient.to_account_info();
+        **recipient.lamports.borrow_mut() = recipient.lamports()
+            .checked_add(info.lamports())
+            .ok_or(DoubleZeroError::ArithmeticError)?;
+        **info.lamports.borrow_mut() = 0;
+        info.assign(&System::id());
+        info.resize(0)?;
+
+        emit!(FillsRegistryClosed {
+            fills_registry: fills_registry_key,
+            recipient: self.recipient.key(),
+            closed_by: self.admin.key(),
+        });
+        Ok(())
+    }
+}
+
+/// Number of pending fills in a registry of either layout.
+fn pending_fills<T: PendingFills>(data: &[u8]) -> Result<u64> {
+    let registry_data = data
+        .get(DISCRIMINATOR_SIZE..DISCRIMINATOR_SIZE + std::mem::size_of::<T>())
+        .ok_or(DoubleZeroError::InvalidFillsRegistry)?;
+    Ok(bytemuck::from_bytes::<T>(registry_data).pending_fills())
+}
+
+trait PendingFills: bytemuck::Pod {
+    fn pending_fills(&self) -> u64;
+}
+
+impl PendingFills for FillsRegistry {
+    fn pending_fills(&self) -> u64 {
+        self.count
+    }
+}
+
+impl PendingFills for LegacyFillsRegistry {
+    fn pending_fills(&self) -> u64 {
+        self.count
+    }
+}
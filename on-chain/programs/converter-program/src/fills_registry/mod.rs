@@ -1,2 +1,6 @@
 pub mod dequeue_fills;
-pub mod fills_registry;
\ No newline at end of file
+pub mod peek_fills;
+pub mod fills_registry;
+pub mod legacy_fills_registry;
+pub mod upgrade_fills_registry;
+pub mod migrate_fills_registry;
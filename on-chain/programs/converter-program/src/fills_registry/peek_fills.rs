@@ -0,0 +1,66 @@
+use anchor_lang::prelude::*;
+use crate::{
+    common::{
+        seeds,
+        error::DoubleZeroError,
+        constant::MAX_PEEK_FILLS
+    },
+    program_state::ProgramStateAccount,
+    fills_registry::fills_registry::{
+        FillsRegistry,
+        PeekFillsResult,
+        PeekedFill
+    },
+};
+
+#[derive(Accounts)]
+pub struct PeekFills<'info> {
+    #[account(
+        seeds = [seeds::PROGRAM_STATE],
+        bump = program_state.bump_registry.program_state_bump,
+    )]
+    pub program_state: Account<'info, ProgramStateAccount>,
+    #[account(
+        address = program_state.fills_registry_address
+    )]
+    pub fills_registry: AccountLoader<'info, FillsRegistry>,
+}
+
+impl<'info> PeekFills<'info> {
+    /// Returns up to `limit` pending fills starting `offset` fills after the head,
+    /// without consuming them. An offset past the last fill gives an empty page.
+    pub fn process(
+        &self,
+        offset: u64,
+        limit: u32,
+    ) -> Result<PeekFillsResult> {
+        require!(
+            limit > 0 && limit <= MAX_PEEK_FILLS,
+            DoubleZeroError::InvalidPeekLimit
+        );
+
+        // Pending fills are split across both registries while they are being moved.
+        require_keys_eq!(
+            self.program_state.pending_fills_registry,
+            Pubkey::default(),
+            DoubleZeroError::FillsMigrationInProgress
+        );
+
+        let data = self.fills_registry.as_ref().try_borrow_data()?;
+        let (fills_registry, fills) = FillsRegistry::from_account_data(&data)?;
+
+        let end = fills_registry.count.min(offset.saturating_add(limit as u64));
+        let page = (offset..end)
+            .map(|i| {
+                // Circular queue indexing
+                let index = (fills_registry.head + i) % fills_registry.capacity;
+                PeekedFill::from(&fills[index as usize])
+            })
+            .collect();
+
+        Ok(PeekFillsResult {
+            count: fills_registry.count,
+            fills: page,
+        })
+    }
+}
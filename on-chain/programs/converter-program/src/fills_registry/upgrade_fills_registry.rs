@@ -0,0 +1,139 @@
+use anchor_lang::{
+    prelude::*,
+    solana_program::native_token::LAMPORTS_PER_SOL
+};
+use crate::{
+    common::{
+        seeds,
+        error::DoubleZeroError,
+        constant::LEGACY_MAX_FILLS_QUEUE_SIZE,
+        events::fill_consumer::{FillsUpgradeStarted, FillsRegistryUpgraded}
+    },
+    program_state::ProgramStateAccount,
+    fills_registry::{
+        fills_registry::{FillsRegistry, FillsQueue, Fill},
+        legacy_fills_registry::LegacyFillsRegistry
+    },
+};
+
+#[derive(Accounts)]
+pub struct BeginFillsUpgrade<'info> {
+    pub admin: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [seeds::PROGRAM_STATE],
+        bump = program_state.bump_registry.program_state_bump,
+    )]
+    pub program_state: Account<'info, ProgramStateAccount>,
+    #[account(address = program_state.fills_registry_address)]
+    pub legacy_fills_registry: AccountLoader<'info, LegacyFillsRegistry>,
+    #[account(zero)]
+    pub fills_registry: AccountLoader<'info, FillsRegistry>,
+}
+
+impl<'info> BeginFillsUpgrade<'info> {
+    pub fn process(&mut self) -> Result<()> {
+        require_keys_eq!(
+            self.admin.key(),
+            self.program_state.admin,
+            DoubleZeroError::UnauthorizedAdmin
+        );
+        require_keys_eq!(
+            self.program_state.pending_fills_registry,
+            Pubkey::default(),
+            DoubleZeroError::FillsMigrationInProgress
+        );
+
+        FillsRegistry::init(&self.fills_registry, 0)?;
+        self.program_state.pending_fills_registry = self.fills_registry.key();
+
+        emit!(FillsUpgradeStarted {
+            legacy_fills_registry: self.legacy_fills_registry.key(),
+            fills_registry: self.fills_registry.key(),
+            started_by: self.admin.key(),
+        });
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct UpgradeFills<'info> {
+    pub admin: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [seeds::PROGRAM_STATE],
+        bump = program_state.bump_registry.program_state_bump,
+    )]
+    pub program_state: Account<'info, ProgramStateAccount>,
+    #[account(
+        mut,
+        address = program_state.fills_registry_address
+    )]
+    pub legacy_fills_registry: AccountLoader<'info, LegacyFillsRegistry>,
+    #[account(
+        mut,
+        address = program_state.pending_fills_registry @ DoubleZeroError::NoFillsMigrationInProgress
+    )]
+    pub fills_registry: AccountLoader<'info, FillsRegistry>,
+}
+
+impl<'info> UpgradeFills<'info> {
+    /// Moves up to `max_fills` legacy fills, oldest first, into the v2 registry.
+    /// Once the legacy registry is empty the program switches over to the v2 one.
+    pub fn process(&mut self, max_fills: u32) -> Result<()> {
+        require_keys_eq!(
+            self.admin.key(),
+            self.program_state.admin,
+            DoubleZeroError::UnauthorizedAdmin
+        );
+
+        let legacy_fills_registry = &mut self.legacy_fills_registry.load_mut()?;
+        let mut fills_registry_data = self.fills_registry.as_ref().try_borrow_mut_data()?;
+        let fills_queue = &mut FillsQueue::from_account_data(&mut fills_registry_data)?;
+
+        let mut fills_moved = 0u32;
+        while legacy_fills_registry.count > 0 && fills_moved < max_fills {
+            let legacy_fill = legacy_fills_registry.fills[legacy_fills_registry.head as usize];
+
+            // Legacy fills never recorded the buyer, slot or time of the trade,
+            // their ask price is derived from the amounts.
+            let ask_price = (legacy_fill.token_2z_out as u128)
+                .checked_mul(LAMPORTS_PER_SOL as u128)
+                .ok_or(DoubleZeroError::ArithmeticError)?
+                .checked_div(legacy_fill.sol_in as u128)
+                .ok_or(DoubleZeroError::ArithmeticError)?
+                .try_into()
+                .map_err(|_| DoubleZeroError::ArithmeticError)?;
+
+            fills_queue.enqueue(Fill {
+                sol_in: legacy_fill.sol_in,
+                token_2z_out: legacy_fill.token_2z_out,
+                ask_price,
+                sequence: 0, // assigned by the registry
+                slot: 0,
+                timestamp: 0,
+                buyer: Pubkey::default(),
+            })?;
+
+            legacy_fills_registry.head = (legacy_fills_registry.head + 1) % LEGACY_MAX_FILLS_QUEUE_SIZE as u64;
+            legacy_fills_registry.count -= 1;
+            legacy_fills_registry.total_sol_pending -= legacy_fill.sol_in;
+            legacy_fills_registry.total_2z_pending -= legacy_fill.token_2z_out;
+            fills_moved += 1;
+        }
+
+        msg!("Moved {} fills, {} left", fills_moved, legacy_fills_registry.count);
+
+        if legacy_fills_registry.count == 0 {
+            self.program_state.fills_registry_address = self.fills_registry.key();
+            self.program_state.pending_fills_registry = Pubkey::default();
+
+            emit!(FillsRegistryUpgraded {
+                previous_fills_registry: self.legacy_fills_registry.key(),
+                fills_registry: self.fills_registry.key(),
+                upgraded_by: self.admin.key(),
+            });
+        }
+        Ok(())
+    }
+}
@@ -13,6 +13,18 @@ use crate::{
     program::ConverterProgram
 };
 
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct InitializeSystemInput {
+    pub oracle_pubkey: Pubkey,
+    pub sol_quantity: u64,
+    pub price_maximum_age: i64, //in seconds
+    pub coefficient: u64,
+    pub max_discount_rate: u64,
+    pub min_discount_rate: u64,
+    pub min_sol_quantity: u64,
+    pub max_sol_quantity: u64,
+}
+
 /// Only the current upgrade authority can call this.
 #[derive(Accounts)]
 pub struct InitializeSystem<'info> {
@@ -62,22 +74,20 @@ pub struct InitializeSystem<'info> {
 }
 
 impl<'info> InitializeSystem<'info> {
-    pub fn process(
-        &mut self,
-        oracle_pubkey: Pubkey,
-        sol_quantity: u64,
-        price_maximum_age: i64,
-        coefficient: u64,
-        max_discount_rate: u64,
-        min_discount_rate: u64,
-        configuration_registry_bump: u8,
-        program_state_bump: u8,
-        deny_list_registry_bump: u8,
-        withdraw_authority_bump: u8,
-    ) -> Result<()> {
+    pub fn process(&mut self, input: InitializeSystemInput, bumps: &InitializeSystemBumps) -> Result<()> {
+        let InitializeSystemInput {
+            oracle_pubkey,
+            sol_quantity,
+            price_maximum_age,
+            coefficient,
+            max_discount_rate,
+            min_discount_rate,
+            min_sol_quantity,
+            max_sol_quantity,
+        } = input;
 
         // Initializing Fills Registry
-        self.fills_registry.load_init()?;
+        FillsRegistry::init(&self.fills_registry, 0)?;
         // Store it in program state
         self.program_state.fills_registry_address = self.fills_registry.key();
 
@@ -85,13 +95,19 @@ impl<'info> InitializeSystem<'info> {
         self.program_state.admin = self.authority.key();
         self.program_state.deny_list_authority = self.authority.key();
 
-        // Set last trade slot to current slot
-        self.program_state.last_trade_slot = Clock::get()?.slot;
+        // Set last trade slot and timestamp to the current ones
+        let clock = Clock::get()?;
+        self.program_state.last_trade_slot = clock.slot;
+        self.program_state.last_trade_timestamp = clock.unix_timestamp;
 
         require!(max_discount_rate <= 10_000, DoubleZeroError::InvalidMaxDiscountRate);
         require!(coefficient <= 100_000_000, DoubleZeroError::InvalidCoefficient);
         require!(min_discount_rate < max_discount_rate, DoubleZeroError::InvalidMinDiscountRate);
         require!(price_maximum_age > 0, DoubleZeroError::InvalidPriceMaximumAge);
+        require!(
+            min_sol_quantity > 0 && min_sol_quantity <= max_sol_quantity,
+            DoubleZeroError::InvalidSolQuantity
+        );
 
         self.configuration_registry.oracle_pubkey = oracle_pubkey;
         self.configuration_registry.sol_quantity = sol_quantity;
@@ -99,12 +115,14 @@ impl<'info> InitializeSystem<'info> {
         self.configuration_registry.coefficient = coefficient;
         self.configuration_registry.max_discount_rate = max_discount_rate;
         self.configuration_registry.min_discount_rate = min_discount_rate;
+        self.configuration_registry.min_sol_quantity = min_sol_quantity;
+        self.configuration_registry.max_sol_quantity = max_sol_quantity;
 
         let bump_registry = &mut self.program_state.bump_registry;
-        bump_registry.configuration_registry_bump = configuration_registry_bump;
-        bump_registry.program_state_bump = program_state_bump;
-        bump_registry.deny_list_registry_bump = deny_list_registry_bump;
-        bump_registry.withdraw_authority_bump = withdraw_authority_bump;
+        bump_registry.configuration_registry_bump = bumps.configuration_registry;
+        bump_registry.program_state_bump = bumps.program_state;
+        bump_registry.deny_list_registry_bump = bumps.deny_list_registry;
+        bump_registry.withdraw_authority_bump = bumps.withdraw_authority;
 
         emit!(SystemInitialized {});
         Ok(())
@@ -9,20 +9,32 @@ mod init_system;
 mod program_state;
 mod buy_sol;
 mod deny_list_registry;
+mod trade_history;
+mod oracle_price;
 
 use system_management::set_admin::*;
 use system_management::set_deny_list_authority::*;
+use system_management::authority_transfer::*;
+use system_management::set_guardian::*;
 use system_management::system_state::*;
+use system_management::migrate_program_state::*;
 use anchor_lang::prelude::*;
 use common::structs::*;
 use configuration_registry::update_configuration::*;
 use configuration_registry::set_fills_consumer::*;
+use configuration_registry::rotate_oracle_key::*;
+use configuration_registry::config_timelock::*;
+use configuration_registry::migrate_configuration_registry::*;
 use calculate_ask_price::*;
 use init_system::*;
 use buy_sol::*;
 use deny_list_registry::*;
 use fills_registry::dequeue_fills::*;
+use fills_registry::peek_fills::*;
 use fills_registry::fills_registry::*;
+use fills_registry::upgrade_fills_registry::*;
+use fills_registry::migrate_fills_registry::*;
+use oracle_price::*;
 
 declare_id!("YrQk4TE5Bi6Hsi4u2LbBNwjZUWEaSUaCDJdapJbCE4z");
 #[program]
@@ -33,27 +45,11 @@ pub mod converter_program {
 
     pub fn initialize_system(
         ctx: Context<InitializeSystem>,
-        oracle_pubkey: Pubkey,
-        sol_quantity: u64,
-        price_maximum_age: i64,
-        coefficient: u64,
-        max_discount_rate: u64,
-        min_discount_rate: u64
+        input: InitializeSystemInput
     ) -> Result<()> {
 
         // Calling Init instruction
-        ctx.accounts.process(
-            oracle_pubkey,
-            sol_quantity,
-            price_maximum_age,
-            coefficient,
-            max_discount_rate,
-            min_discount_rate,
-            ctx.bumps.configuration_registry,
-            ctx.bumps.program_state,
-            ctx.bumps.deny_list_registry,
-            ctx.bumps.withdraw_authority
-        )
+        ctx.accounts.process(input, &ctx.bumps)
     }
 
     pub fn update_configuration_registry(
@@ -63,6 +59,29 @@ pub mod converter_program {
         ctx.accounts.process_update(input)
     }
 
+    pub fn queue_config_change(
+        ctx: Context<QueueConfigChange>,
+        input: ConfigurationRegistryInput
+    ) -> Result<()> {
+        ctx.accounts.process(input)
+    }
+
+    pub fn execute_config_change(ctx: Context<ExecuteConfigChange>, change_id: u64) -> Result<()> {
+        ctx.accounts.process(change_id)
+    }
+
+    pub fn cancel_config_change(ctx: Context<CancelConfigChange>, change_id: u64) -> Result<()> {
+        ctx.accounts.process(change_id)
+    }
+
+    pub fn migrate_program_state(ctx: Context<MigrateProgramState>) -> Result<()> {
+        ctx.accounts.process()
+    }
+
+    pub fn migrate_configuration_registry(ctx: Context<MigrateConfigurationRegistry>) -> Result<()> {
+        ctx.accounts.process()
+    }
+
     pub fn set_fills_consumer(
         ctx: Context<SetFillsConsumer>,
         new_consumer: Pubkey,
@@ -70,6 +89,15 @@ pub mod converter_program {
         ctx.accounts.set_fills_consumer(new_consumer)
     }
 
+    pub fn rotate_oracle_key(
+        ctx: Context<RotateOracleKey>,
+        next_oracle_pubkey: Pubkey,
+        activation_timestamp: i64,
+        retire_timestamp: i64,
+    ) -> Result<()> {
+        ctx.accounts.process(next_oracle_pubkey, activation_timestamp, retire_timestamp)
+    }
+
     pub fn add_to_deny_list(ctx: Context<UpdateDenyList>, address: Pubkey) -> Result<()> {
         ctx.accounts.add_to_deny_list(address)
     }
@@ -86,23 +114,129 @@ pub mod converter_program {
         ctx.accounts.process(new_authority)
     }
 
+    pub fn propose_admin(ctx: Context<AuthorityTransfer>, new_admin: Pubkey) -> Result<()> {
+        ctx.accounts.propose_admin(new_admin)
+    }
+
+    pub fn accept_admin(ctx: Context<AuthorityTransfer>) -> Result<()> {
+        ctx.accounts.accept_admin()
+    }
+
+    pub fn cancel_admin_transfer(ctx: Context<AuthorityTransfer>) -> Result<()> {
+        ctx.accounts.cancel_admin_transfer()
+    }
+
+    pub fn propose_deny_list_authority(ctx: Context<AuthorityTransfer>, new_authority: Pubkey) -> Result<()> {
+        ctx.accounts.propose_deny_list_authority(new_authority)
+    }
+
+    pub fn accept_deny_list_authority(ctx: Context<AuthorityTransfer>) -> Result<()> {
+        ctx.accounts.accept_deny_list_authority()
+    }
+
+    pub fn cancel_deny_list_authority_transfer(ctx: Context<AuthorityTransfer>) -> Result<()> {
+        ctx.accounts.cancel_deny_list_authority_transfer()
+    }
+
     pub fn toggle_system_state(ctx: Context<SystemState>, set_to: bool) -> Result<()> {
         ctx.accounts.process(set_to)
     }
 
+    pub fn set_guardian(ctx: Context<SetGuardian>, new_guardian: Pubkey) -> Result<()> {
+        ctx.accounts.process(new_guardian)
+    }
+
+    pub fn halt_system(ctx: Context<HaltSystem>) -> Result<()> {
+        ctx.accounts.process()
+    }
+
+    pub fn set_pause_flags(ctx: Context<SetPauseFlags>, pause_flags: u8) -> Result<()> {
+        ctx.accounts.process(pause_flags)
+    }
+
+    pub fn begin_fills_upgrade(ctx: Context<BeginFillsUpgrade>) -> Result<()> {
+        ctx.accounts.process()
+    }
+
+    pub fn upgrade_fills(ctx: Context<UpgradeFills>, max_fills: u32) -> Result<()> {
+        ctx.accounts.process(max_fills)
+    }
+
+    pub fn begin_fills_migration(ctx: Context<BeginFillsMigration>) -> Result<()> {
+        ctx.accounts.process()
+    }
+
+    pub fn migrate_fills(ctx: Context<MigrateFills>, max_fills: u32) -> Result<()> {
+        ctx.accounts.process(max_fills)
+    }
+
+    pub fn close_fills_registry(ctx: Context<CloseFillsRegistry>) -> Result<()> {
+        ctx.accounts.process()
+    }
+
+    //////////////////////// ORACLE FLOW ////////////////////////
+
+    pub fn post_price(
+        ctx: Context<PostPrice>,
+        swap_rate: u64,
+        timestamp: i64,
+        confidence: u64
+    ) -> Result<()> {
+        ctx.accounts.process(swap_rate, timestamp, confidence, ctx.bumps.oracle_price)
+    }
+
     //////////////////////// USER FLOW ////////////////////////
 
     pub fn buy_sol(
         ctx: Context<BuySol>,
         bid_price: u64,
+        sol_amount: u64,
         oracle_price_data: OraclePriceData
     ) -> Result<()> {
         ctx.accounts.process(
             bid_price,
+            sol_amount,
             oracle_price_data
         )
     }
 
+    pub fn buy_sol_with_precompile(
+        ctx: Context<BuySol>,
+        bid_price: u64,
+        sol_amount: u64,
+        oracle_price_message: OraclePriceMessage
+    ) -> Result<()> {
+        ctx.accounts.process_with_precompile(
+            bid_price,
+            sol_amount,
+            oracle_price_message
+        )
+    }
+
+    pub fn buy_sol_with_quorum(
+        ctx: Context<BuySol>,
+        bid_price: u64,
+        sol_amount: u64,
+        quorum_price_data: QuorumPriceData
+    ) -> Result<()> {
+        ctx.accounts.process_with_quorum(
+            bid_price,
+            sol_amount,
+            quorum_price_data
+        )
+    }
+
+    pub fn buy_sol_with_posted_price(
+        ctx: Context<BuySol>,
+        bid_price: u64,
+        sol_amount: u64
+    ) -> Result<()> {
+        ctx.accounts.process_with_posted_price(
+            bid_price,
+            sol_amount
+        )
+    }
+
     pub fn get_conversion_rate(
         ctx: Context<CalculateAskPrice>,
         oracle_price_data: OraclePriceData,
@@ -110,11 +244,53 @@ pub mod converter_program {
         ctx.accounts.get_conversion_rate(oracle_price_data)
     }
 
+    pub fn get_conversion_rate_with_precompile(
+        ctx: Context<CalculateAskPrice>,
+        oracle_price_message: OraclePriceMessage,
+    ) -> Result<u64> {
+        ctx.accounts.get_conversion_rate_with_precompile(oracle_price_message)
+    }
+
+    pub fn get_conversion_rate_with_quorum(
+        ctx: Context<CalculateAskPrice>,
+        quorum_price_data: QuorumPriceData,
+    ) -> Result<u64> {
+        ctx.accounts.get_conversion_rate_with_quorum(quorum_price_data)
+    }
+
+    pub fn get_conversion_rate_with_posted_price(
+        ctx: Context<CalculateAskPrice>,
+    ) -> Result<u64> {
+        ctx.accounts.get_conversion_rate_with_posted_price()
+    }
+
     //////////////////////// Integration Contract ////////////////////////
     pub fn dequeue_fills(
         ctx: Context<DequeueFills>,
         max_sol_amount: u64,
     ) -> Result<DequeueFillsResult> {
-        ctx.accounts.process(max_sol_amount)
+        ctx.accounts.process(DequeueLimit::Sol(max_sol_amount))
+    }
+
+    pub fn dequeue_fills_by_2z(
+        ctx: Context<DequeueFills>,
+        max_2z_amount: u64,
+    ) -> Result<DequeueFillsResult> {
+        ctx.accounts.process(DequeueLimit::Token2z(max_2z_amount))
+    }
+
+    pub fn dequeue_fills_detailed(
+        ctx: Context<DequeueFills>,
+        limit: DequeueLimit,
+    ) -> Result<DequeueFillsDetailedResult> {
+        ctx.accounts.process_detailed(limit)
+    }
+
+    pub fn peek_fills(
+        ctx: Context<PeekFills>,
+        offset: u64,
+        limit: u32,
+    ) -> Result<PeekFillsResult> {
+        ctx.accounts.process(offset, limit)
     }
 }
\ No newline at end of file
@@ -0,0 +1,96 @@
+use anchor_lang::prelude::*;
+use crate::{
+    program_state::ProgramStateAccount,
+    configuration_registry::configuration_registry::ConfigurationRegistry,
+    common::{
+        constant::DISCRIMINATOR_SIZE,
+        seeds,
+        error::DoubleZeroError,
+        events::trade::OraclePricePosted,
+    },
+};
+
+/// Latest swap rate pushed by the oracle, read by buys and quotes
+/// that do not carry an attestation of their own.
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct OraclePriceAccount {
+    pub swap_rate: u64,
+    pub timestamp: i64, // Oracle timestamp the swap rate was observed at
+    pub confidence: u64,
+    pub oracle_pubkey: Pubkey, // Oracle key that posted the price
+    pub posted_slot: u64,
+    pub bump: u8,
+}
+
+/// Only an accepted oracle key can call this
+#[derive(Accounts)]
+pub struct PostPrice<'info> {
+    #[account(
+        seeds = [seeds::CONFIGURATION_REGISTRY],
+        bump = program_state.bump_registry.configuration_registry_bump,
+    )]
+    pub configuration_registry: Account<'info, ConfigurationRegistry>,
+    #[account(
+        seeds = [seeds::PROGRAM_STATE],
+        bump = program_state.bump_registry.program_state_bump,
+    )]
+    pub program_state: Account<'info, ProgramStateAccount>,
+    #[account(
+        init_if_needed,
+        payer = oracle,
+        space = DISCRIMINATOR_SIZE + OraclePriceAccount::INIT_SPACE,
+        seeds = [seeds::ORACLE_PRICE],
+        bump,
+    )]
+    pub oracle_price: Account<'info, OraclePriceAccount>,
+    #[account(mut)]
+    pub oracle: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> PostPrice<'info> {
+    pub fn process(
+        &mut self,
+        swap_rate: u64,
+        timestamp: i64,
+        confidence: u64,
+        bump: u8
+    ) -> Result<()> {
+        let clock = Clock::get()?;
+
+        // The transaction signature of the oracle key authenticates the price.
+        require!(
+            self.configuration_registry
+                .accepted_oracle_pubkeys(clock.unix_timestamp)
+                .contains(self.oracle.key),
+            DoubleZeroError::InvalidOraclePublicKey
+        );
+
+        require!(swap_rate > 0, DoubleZeroError::InvalidOracleSwapRate);
+
+        // Prices only move forward, within the clock skew accepted for attestations.
+        require!(timestamp > self.oracle_price.timestamp, DoubleZeroError::PostedPriceOutOfOrder);
+        require!(
+            (clock.unix_timestamp - timestamp).abs() <= self.configuration_registry.price_maximum_age,
+            DoubleZeroError::StalePrice
+        );
+
+        let oracle_price = &mut self.oracle_price;
+        oracle_price.swap_rate = swap_rate;
+        oracle_price.timestamp = timestamp;
+        oracle_price.confidence = confidence;
+        oracle_price.oracle_pubkey = self.oracle.key();
+        oracle_price.posted_slot = clock.slot;
+        oracle_price.bump = bump;
+
+        emit!(OraclePricePosted {
+            swap_rate,
+            timestamp,
+            confidence,
+            oracle: self.oracle.key(),
+            slot: clock.slot,
+        });
+        Ok(())
+    }
+}
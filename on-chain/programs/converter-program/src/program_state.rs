@@ -1,14 +1,79 @@
 use anchor_lang::prelude::*;
+use crate::common::{error::DoubleZeroError, structs::DiscountClock};
 
 #[account]
 #[derive(InitSpace, Debug)]
 pub struct ProgramStateAccount {
     pub admin: Pubkey,
     pub fills_registry_address: Pubkey,
-    pub is_halted: bool,  // Indicates whether the system accepts conversion requests
+    pub is_halted: bool,  // Pauses every feature, regardless of pause_flags
     pub bump_registry: BumpRegistry,
     pub last_trade_slot: u64,
     pub deny_list_authority: Pubkey,
+    pub last_attestation_timestamp: i64, // Timestamp of the latest attestation consumed by a trade
+    pub current_epoch: u64, // Epoch the sale counter belongs to
+    pub epoch_sol_sold: u64, // SOL amount (in lamports) sold during current_epoch
+    pub pending_admin: Pubkey, // Proposed admin, has to accept before taking over (default when none)
+    pub pending_deny_list_authority: Pubkey, // Proposed deny list authority (default when none)
+    pub guardian: Pubkey, // Can halt but not unhalt the system (default when none)
+    pub pause_flags: u8, // Features paused individually (see PAUSE_* constants)
+    pub pending_fills_registry: Pubkey, // Registry pending fills are being moved to (default when none)
+    pub fills_above_high_watermark: bool, // Set when a buy crosses the high watermark, cleared at the low watermark
+    pub last_trade_timestamp: i64, // Unix timestamp of the latest trade, used by the wall clock discount ramp
+    pub carried_discount_rate: u64, // Discount (basis points) the latest trade carried over, added on top of the ramp
+    pub reference_swap_rate: u64, // EMA of the swap rates trades executed at, 0 until the first trade
+}
+
+impl ProgramStateAccount {
+    pub fn require_not_paused(&self, feature: u8) -> Result<()> {
+        require!(!self.is_halted, DoubleZeroError::SystemIsHalted);
+        require!(self.pause_flags & feature == 0, DoubleZeroError::FeaturePaused);
+        Ok(())
+    }
+
+    /// Last trade and current point on the clock the discount ramp runs on.
+    pub fn discount_clock_points(&self, discount_clock: DiscountClock, clock: &Clock) -> (u64, u64) {
+        match discount_clock {
+            DiscountClock::Slot => (self.last_trade_slot, clock.slot),
+            // Timestamps are not guaranteed to increase, a clock going back counts as no time passed.
+            // Without a recorded trade timestamp, e.g. right after switching clocks, no time has passed either.
+            DiscountClock::UnixTimestamp => {
+                let now = clock.unix_timestamp.max(0) as u64;
+                if self.last_trade_timestamp <= 0 {
+                    return (now, now);
+                }
+                let last = self.last_trade_timestamp as u64;
+                (last, now.max(last))
+            }
+        }
+    }
+
+    /// Reference rate the deviation guard compares against. Once no trade happened for
+    /// `max_age` seconds it reads as 0, the deviation check is skipped and the next
+    /// trade re-seeds it (a `max_age` of 0 never expires it).
+    pub fn live_reference_swap_rate(&self, max_age: u32, current_timestamp: i64) -> u64 {
+        if max_age > 0 && current_timestamp.saturating_sub(self.last_trade_timestamp) >= max_age as i64 {
+            return 0;
+        }
+        self.reference_swap_rate
+    }
+
+    pub fn sol_sold_in(&self, epoch: u64) -> u64 {
+        if self.current_epoch == epoch { self.epoch_sol_sold } else { 0 }
+    }
+
+    /// The sale counter restarts on the first trade of a new epoch.
+    pub fn record_sale(&mut self, epoch: u64, sol_amount: u64) -> Result<()> {
+        if self.current_epoch != epoch {
+            self.current_epoch = epoch;
+            self.epoch_sol_sold = 0;
+        }
+
+        self.epoch_sol_sold = self.epoch_sol_sold
+            .checked_add(sol_amount)
+            .ok_or(DoubleZeroError::ArithmeticError)?;
+        Ok(())
+    }
 }
 
 #[derive(Debug, AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
@@ -17,4 +82,70 @@ pub struct BumpRegistry {
     pub program_state_bump: u8,
     pub deny_list_registry_bump: u8,
     pub withdraw_authority_bump: u8,
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::common::constant::DISCRIMINATOR_SIZE;
+
+    #[test]
+    fn test_migrated_program_state_layout() {
+        // Program state as initialized before any field was appended, grown with zeroes.
+        let admin = Pubkey::new_unique();
+        let fills_registry_address = Pubkey::new_unique();
+        let deny_list_authority = Pubkey::new_unique();
+        let mut data = ProgramStateAccount::DISCRIMINATOR.to_vec();
+        data.extend_from_slice(admin.as_ref());
+        data.extend_from_slice(fills_registry_address.as_ref());
+        data.push(0); // is_halted
+        data.extend_from_slice(&[254, 253, 252, 251]); // bump_registry
+        data.extend_from_slice(&42u64.to_le_bytes()); // last_trade_slot
+        data.extend_from_slice(deny_list_authority.as_ref());
+        data.resize(DISCRIMINATOR_SIZE + ProgramStateAccount::INIT_SPACE, 0);
+
+        let program_state = ProgramStateAccount::try_deserialize(&mut &data[..]).unwrap();
+        assert_eq!(program_state.admin, admin);
+        assert_eq!(program_state.fills_registry_address, fills_registry_address);
+        assert_eq!(program_state.bump_registry.program_state_bump, 253);
+        assert_eq!(program_state.last_trade_slot, 42);
+        assert_eq!(program_state.deny_list_authority, deny_list_authority);
+        assert_eq!(program_state.pending_admin, Pubkey::default());
+        assert_eq!(program_state.guardian, Pubkey::default());
+        assert_eq!(program_state.pause_flags, 0);
+        assert_eq!(program_state.pending_fills_registry, Pubkey::default());
+        assert_eq!(program_state.reference_swap_rate, 0);
+
+        // No trade timestamp recorded yet, the wall clock ramp starts from now.
+        let clock = Clock { slot: 100, unix_timestamp: 1_700_000_000, ..Clock::default() };
+        assert_eq!(
+            program_state.discount_clock_points(DiscountClock::UnixTimestamp, &clock),
+            (1_700_000_000, 1_700_000_000)
+        );
+        assert_eq!(program_state.discount_clock_points(DiscountClock::Slot, &clock), (42, 100));
+    }
+
+    #[test]
+    fn test_live_reference_swap_rate() {
+        let program_state = ProgramStateAccount {
+            last_trade_timestamp: 1_700_000_000,
+            reference_swap_rate: 2_000_000_000,
+            ..test_program_state()
+        };
+        for (max_age, current_timestamp, expected_rate) in [
+            (0, 1_800_000_000, 2_000_000_000), // never expires
+            (600, 1_700_000_599, 2_000_000_000), // within the maximum age
+            (600, 1_700_000_600, 0), // expired, the next trade re-seeds it
+            (600, 1_699_999_000, 2_000_000_000), // clock behind the last trade
+        ] {
+            assert_eq!(program_state.live_reference_swap_rate(max_age, current_timestamp), expected_rate);
+        }
+    }
+
+    fn test_program_state() -> ProgramStateAccount {
+        let mut data = ProgramStateAccount::DISCRIMINATOR.to_vec();
+        data.resize(DISCRIMINATOR_SIZE + ProgramStateAccount::INIT_SPACE, 0);
+        ProgramStateAccount::try_deserialize(&mut &data[..]).unwrap()
+    }
+}
@@ -0,0 +1,149 @@
+use anchor_lang::prelude::*;
+use crate::{
+    common::{
+        seeds,
+        error::DoubleZeroError,
+        events::system::{
+            AdminChanged,
+            AdminTransferCancelled,
+            AdminTransferProposed,
+            DenyListAuthoritySet,
+            DenyListAuthorityTransferCancelled,
+            DenyListAuthorityTransferProposed
+        }
+    },
+    program_state::ProgramStateAccount,
+};
+
+#[derive(Accounts)]
+pub struct AuthorityTransfer<'info> {
+    pub signer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [seeds::PROGRAM_STATE],
+        bump = program_state.bump_registry.program_state_bump,
+    )]
+    pub program_state: Account<'info, ProgramStateAccount>,
+}
+
+impl<'info> AuthorityTransfer<'info> {
+    pub fn propose_admin(&mut self, new_admin: Pubkey) -> Result<()> {
+        // Only the current admin can hand over administration
+        require_keys_eq!(
+            self.signer.key(),
+            self.program_state.admin,
+            DoubleZeroError::UnauthorizedAdmin
+        );
+        require!(
+            new_admin != Pubkey::default() && new_admin != self.program_state.admin,
+            DoubleZeroError::InvalidProposedAuthority
+        );
+
+        self.program_state.pending_admin = new_admin;
+        emit!(AdminTransferProposed {
+            pending_admin: new_admin,
+            proposed_by: self.signer.key()
+        });
+        Ok(())
+    }
+
+    pub fn accept_admin(&mut self) -> Result<()> {
+        require!(
+            self.program_state.pending_admin != Pubkey::default(),
+            DoubleZeroError::NoPendingAuthorityTransfer
+        );
+        // Proposed key has to sign, proving it is usable
+        require_keys_eq!(
+            self.signer.key(),
+            self.program_state.pending_admin,
+            DoubleZeroError::UnauthorizedPendingAuthority
+        );
+
+        self.program_state.admin = self.program_state.pending_admin;
+        self.program_state.pending_admin = Pubkey::default();
+        emit!(AdminChanged {
+            new_admin: self.program_state.admin,
+            changed_by: self.signer.key()
+        });
+        Ok(())
+    }
+
+    pub fn cancel_admin_transfer(&mut self) -> Result<()> {
+        require_keys_eq!(
+            self.signer.key(),
+            self.program_state.admin,
+            DoubleZeroError::UnauthorizedAdmin
+        );
+        require!(
+            self.program_state.pending_admin != Pubkey::default(),
+            DoubleZeroError::NoPendingAuthorityTransfer
+        );
+
+        emit!(AdminTransferCancelled {
+            pending_admin: self.program_state.pending_admin,
+            cancelled_by: self.signer.key()
+        });
+        self.program_state.pending_admin = Pubkey::default();
+        Ok(())
+    }
+
+    pub fn propose_deny_list_authority(&mut self, new_authority: Pubkey) -> Result<()> {
+        // Only the current deny list authority can hand over the role
+        require_keys_eq!(
+            self.signer.key(),
+            self.program_state.deny_list_authority,
+            DoubleZeroError::UnauthorizedDenyListAuthority
+        );
+        require!(
+            new_authority != Pubkey::default() && new_authority != self.program_state.deny_list_authority,
+            DoubleZeroError::InvalidProposedAuthority
+        );
+
+        self.program_state.pending_deny_list_authority = new_authority;
+        emit!(DenyListAuthorityTransferProposed {
+            pending_authority: new_authority,
+            proposed_by: self.signer.key()
+        });
+        Ok(())
+    }
+
+    pub fn accept_deny_list_authority(&mut self) -> Result<()> {
+        require!(
+            self.program_state.pending_deny_list_authority != Pubkey::default(),
+            DoubleZeroError::NoPendingAuthorityTransfer
+        );
+        // Proposed key has to sign, proving it is usable
+        require_keys_eq!(
+            self.signer.key(),
+            self.program_state.pending_deny_list_authority,
+            DoubleZeroError::UnauthorizedPendingAuthority
+        );
+
+        self.program_state.deny_list_authority = self.program_state.pending_deny_list_authority;
+        self.program_state.pending_deny_list_authority = Pubkey::default();
+        emit!(DenyListAuthoritySet {
+            new_authority: self.program_state.deny_list_authority,
+            changed_by: self.signer.key()
+        });
+        Ok(())
+    }
+
+    pub fn cancel_deny_list_authority_transfer(&mut self) -> Result<()> {
+        require_keys_eq!(
+            self.signer.key(),
+            self.program_state.deny_list_authority,
+            DoubleZeroError::UnauthorizedDenyListAuthority
+        );
+        require!(
+            self.program_state.pending_deny_list_authority != Pubkey::default(),
+            DoubleZeroError::NoPendingAuthorityTransfer
+        );
+
+        emit!(DenyListAuthorityTransferCancelled {
+            pending_authority: self.program_state.pending_deny_list_authority,
+            cancelled_by: self.signer.key()
+        });
+        self.program_state.pending_deny_list_authority = Pubkey::default();
+        Ok(())
+    }
+}
@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+use crate::{
+    program_state::ProgramStateAccount,
+    common::{
+        account_utils::grow_account,
+        constant::DISCRIMINATOR_SIZE,
+        seeds,
+        events::system::AccountMigrated,
+        error::DoubleZeroError
+    },
+};
+
+/// Only the admin can call this
+#[derive(Accounts)]
+pub struct MigrateProgramState<'info> {
+    /// CHECK: program state written by an older program version does not deserialize until it is grown
+    #[account(
+        mut,
+        seeds = [seeds::PROGRAM_STATE],
+        bump,
+        owner = crate::ID,
+    )]
+    pub program_state: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MigrateProgramState<'info> {
+    /// Grows the program state to the current layout after a program upgrade appended fields to it.
+    pub fn process(&mut self) -> Result<()> {
+        let account = self.program_state.to_account_info();
+        let previous_len = account.data_len();
+        let new_len = DISCRIMINATOR_SIZE + ProgramStateAccount::INIT_SPACE;
+        require!(previous_len < new_len, DoubleZeroError::AccountAlreadyMigrated);
+
+        grow_account(
+            &account,
+            &self.admin.to_account_info(),
+            &self.system_program.to_account_info(),
+            new_len,
+        )?;
+
+        // The admin is only readable once the account is grown, a failed check reverts the growth.
+        let program_state = ProgramStateAccount::try_deserialize(&mut &account.try_borrow_data()?[..])?;
+        require_keys_eq!(
+            self.admin.key(),
+            program_state.admin,
+            DoubleZeroError::UnauthorizedAdmin
+        );
+
+        emit!(AccountMigrated {
+            account: account.key(),
+            previous_len: previous_len as u64,
+            new_len: new_len as u64,
+            migrated_by: self.admin.key(),
+        });
+        Ok(())
+    }
+}
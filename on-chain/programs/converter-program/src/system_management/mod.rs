@@ -1,3 +1,6 @@
 pub mod set_admin;
 pub mod system_state;
-pub mod set_deny_list_authority;
\ No newline at end of file
+pub mod set_deny_list_authority;
+pub mod authority_transfer;
+pub mod set_guardian;
+pub mod migrate_program_state;
\ No newline at end of file
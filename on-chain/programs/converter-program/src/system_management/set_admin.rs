@@ -30,6 +30,8 @@ pub struct SetAdmin<'info> {
 impl<'info> SetAdmin<'info> {
     pub fn process(&mut self, new_admin: Pubkey) -> Result<()> {
         self.program_state.admin = new_admin;
+        // Upgrade authority override supersedes any pending transfer
+        self.program_state.pending_admin = Pubkey::default();
         emit!(AdminChanged {
             new_admin: self.program_state.admin,
             changed_by: self.admin.key()
@@ -28,6 +28,8 @@ pub struct SetDenyListAuthority<'info> {
 impl<'info> SetDenyListAuthority<'info> {
     pub fn process(&mut self, new_authority: Pubkey) -> Result<()> {
         self.program_state.deny_list_authority = new_authority;
+        // Upgrade authority override supersedes any pending transfer
+        self.program_state.pending_deny_list_authority = Pubkey::default();
         emit!(DenyListAuthoritySet {
             new_authority,
             changed_by: self.admin.key()
@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+use crate::{
+    common::{
+        seeds,
+        error::DoubleZeroError,
+        events::system::GuardianSet
+    },
+    program_state::ProgramStateAccount,
+};
+
+#[derive(Accounts)]
+pub struct SetGuardian<'info> {
+    pub admin: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [seeds::PROGRAM_STATE],
+        bump = program_state.bump_registry.program_state_bump,
+    )]
+    pub program_state: Account<'info, ProgramStateAccount>,
+}
+
+impl<'info> SetGuardian<'info> {
+    /// Setting the default key removes the guardian.
+    pub fn process(&mut self, new_guardian: Pubkey) -> Result<()> {
+        require_keys_eq!(
+            self.admin.key(),
+            self.program_state.admin,
+            DoubleZeroError::UnauthorizedAdmin
+        );
+
+        self.program_state.guardian = new_guardian;
+        emit!(GuardianSet {
+            new_guardian,
+            changed_by: self.admin.key()
+        });
+        Ok(())
+    }
+}
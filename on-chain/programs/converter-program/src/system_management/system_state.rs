@@ -3,7 +3,8 @@ use anchor_lang::prelude::*;
 use crate::{
     common::{
         error::DoubleZeroError,
-        events::system::{SystemHalted, SystemUnhalted},
+        events::system::{PauseFlagsSet, SystemHalted, SystemRole, SystemUnhalted},
+        constant::PAUSE_ALL,
         seeds,
     },
     program_state::ProgramStateAccount,
@@ -33,7 +34,8 @@ impl<'info> SystemState<'info> {
         if set_to && !self.program_state.is_halted {
             self.program_state.is_halted = true;
             emit!(SystemHalted {
-                halted_by: self.admin.key()
+                halted_by: self.admin.key(),
+                role: SystemRole::Admin
             });
         } else if !set_to && self.program_state.is_halted {
             self.program_state.is_halted = false;
@@ -45,4 +47,79 @@ impl<'info> SystemState<'info> {
         }
         Ok(())
     }
-}
\ No newline at end of file
+}
+
+#[derive(Accounts)]
+pub struct HaltSystem<'info> {
+    pub signer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [seeds::PROGRAM_STATE],
+        bump = program_state.bump_registry.program_state_bump,
+    )]
+    pub program_state: Account<'info, ProgramStateAccount>,
+}
+
+impl<'info> HaltSystem<'info> {
+    /// Halting only, unhalting stays with the admin.
+    pub fn process(&mut self) -> Result<()> {
+        let signer = self.signer.key();
+        let role = signer_role(&self.program_state, signer)?;
+
+        require!(!self.program_state.is_halted, DoubleZeroError::InvalidSystemState);
+        self.program_state.is_halted = true;
+        emit!(SystemHalted {
+            halted_by: signer,
+            role
+        });
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct SetPauseFlags<'info> {
+    pub signer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [seeds::PROGRAM_STATE],
+        bump = program_state.bump_registry.program_state_bump,
+    )]
+    pub program_state: Account<'info, ProgramStateAccount>,
+}
+
+impl<'info> SetPauseFlags<'info> {
+    /// The guardian can only pause more features, resuming one stays with the admin.
+    pub fn process(&mut self, pause_flags: u8) -> Result<()> {
+        require!(pause_flags & !PAUSE_ALL == 0, DoubleZeroError::InvalidPauseFlags);
+
+        let signer = self.signer.key();
+        let role = signer_role(&self.program_state, signer)?;
+        let previous_pause_flags = self.program_state.pause_flags;
+        if role == SystemRole::Guardian {
+            require!(
+                pause_flags & previous_pause_flags == previous_pause_flags,
+                DoubleZeroError::UnauthorizedGuardian
+            );
+        }
+
+        self.program_state.pause_flags = pause_flags;
+        emit!(PauseFlagsSet {
+            pause_flags,
+            previous_pause_flags,
+            changed_by: signer,
+            role
+        });
+        Ok(())
+    }
+}
+
+/// The admin keeps its powers when it is also set as the guardian.
+fn signer_role(program_state: &ProgramStateAccount, signer: Pubkey) -> Result<SystemRole> {
+    if signer == program_state.admin {
+        Ok(SystemRole::Admin)
+    } else if program_state.guardian != Pubkey::default() && signer == program_state.guardian {
+        Ok(SystemRole::Guardian)
+    } else {
+        err!(DoubleZeroError::UnauthorizedGuardian)
+    }
+}
@@ -0,0 +1,66 @@
+use anchor_lang::prelude::*;
+use crate::common::error::DoubleZeroError;
+
+/// Trades of a single buyer, tracked for the current epoch only.
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct TradeHistory {
+    pub epoch: u64,
+    pub num_of_trades: u64,
+    pub sol_bought: u64, // SOL amount (in lamports) bought during the epoch
+}
+
+impl TradeHistory {
+    pub fn num_of_trades_in(&self, epoch: u64) -> u64 {
+        if self.epoch == epoch { self.num_of_trades } else { 0 }
+    }
+
+    pub fn sol_bought_in(&self, epoch: u64) -> u64 {
+        if self.epoch == epoch { self.sol_bought } else { 0 }
+    }
+
+    /// Counters restart on the first trade of a new epoch.
+    pub fn record_trade(&mut self, epoch: u64, sol_amount: u64) -> Result<()> {
+        if self.epoch != epoch {
+            self.epoch = epoch;
+            self.num_of_trades = 0;
+            self.sol_bought = 0;
+        }
+
+        self.num_of_trades = self.num_of_trades
+            .checked_add(1)
+            .ok_or(DoubleZeroError::ArithmeticError)?;
+        self.sol_bought = self.sol_bought
+            .checked_add(sol_amount)
+            .ok_or(DoubleZeroError::ArithmeticError)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_record_trade() {
+        let mut trade_history = TradeHistory { epoch: 0, num_of_trades: 0, sol_bought: 0 };
+
+        trade_history.record_trade(5, 10).unwrap();
+        trade_history.record_trade(5, 20).unwrap();
+        assert_eq!(trade_history.num_of_trades_in(5), 2);
+        assert_eq!(trade_history.sol_bought_in(5), 30);
+
+        // Counters of a past epoch do not count towards the current one.
+        assert_eq!(trade_history.num_of_trades_in(6), 0);
+        assert_eq!(trade_history.sol_bought_in(6), 0);
+
+        trade_history.record_trade(6, 7).unwrap();
+        assert_eq!(trade_history.epoch, 6);
+        assert_eq!(trade_history.num_of_trades_in(6), 1);
+        assert_eq!(trade_history.sol_bought_in(6), 7);
+
+        trade_history.sol_bought = u64::MAX;
+        assert!(trade_history.record_trade(6, 1).is_err());
+    }
+}
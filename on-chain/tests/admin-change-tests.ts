@@ -1,10 +1,19 @@
-import { Keypair } from "@solana/web3.js";
+import { Keypair, PublicKey } from "@solana/web3.js";
 import { getDefaultKeyPair } from "./core/utils/accounts";
 import { setAdminAndVerify, setAdminAndVerifyFail } from "./core/test-flow/set-admin";
 import { setup } from "./core/setup";
 import { initializeSystemIfNeeded } from "./core/test-flow/system-initialize";
 import { setDenyListAuthorityAndVerify, setDenyListAuthorityShouldFail } from "./core/test-flow/deny-list";
 import {ErrorMsg} from "./core/constants";
+import {
+  acceptAuthorityAndVerify,
+  acceptAuthorityShouldFail,
+  AuthorityRole,
+  cancelAuthorityTransferAndVerify,
+  cancelAuthorityTransferShouldFail,
+  proposeAuthorityAndVerify,
+  proposeAuthorityShouldFail
+} from "./core/test-flow/authority-transfer";
 
 describe("Admin Change Tests", async () => {
   const program = await setup();
@@ -36,4 +45,83 @@ describe("Admin Change Tests", async () => {
     const newDenyListAuthority = Keypair.generate();
     await setDenyListAuthorityShouldFail(program, newDenyListAuthority.publicKey, ErrorMsg.RAW_CONSTRAINT_VIOLATION, newDenyListAuthority);
   });
-});
\ No newline at end of file
+
+  describe("Two step admin transfer", () => {
+    it("Proposed admin can accept the transfer", async () => {
+      const newAdmin = Keypair.generate();
+      await proposeAuthorityAndVerify(program, AuthorityRole.Admin, newAdmin.publicKey, adminKeyPair);
+      await acceptAuthorityAndVerify(program, AuthorityRole.Admin, newAdmin);
+
+      // Revert: hand administration back to adminKeyPair
+      await proposeAuthorityAndVerify(program, AuthorityRole.Admin, adminKeyPair.publicKey, newAdmin);
+      await acceptAuthorityAndVerify(program, AuthorityRole.Admin, adminKeyPair);
+    });
+
+    it("Should fail to propose admin if not admin", async () => {
+      const intruder = Keypair.generate();
+      await proposeAuthorityShouldFail(program, AuthorityRole.Admin, intruder.publicKey, intruder, ErrorMsg.UNAUTHORIZED_ADMIN);
+    });
+
+    it("Should fail to propose the default key or the current admin", async () => {
+      await proposeAuthorityShouldFail(program, AuthorityRole.Admin, PublicKey.default, adminKeyPair, ErrorMsg.INVALID_PROPOSED_AUTHORITY);
+      await proposeAuthorityShouldFail(program, AuthorityRole.Admin, adminKeyPair.publicKey, adminKeyPair, ErrorMsg.INVALID_PROPOSED_AUTHORITY);
+    });
+
+    it("Only the proposed admin can accept", async () => {
+      const newAdmin = Keypair.generate();
+      const intruder = Keypair.generate();
+      await proposeAuthorityAndVerify(program, AuthorityRole.Admin, newAdmin.publicKey, adminKeyPair);
+      await acceptAuthorityShouldFail(program, AuthorityRole.Admin, intruder, ErrorMsg.UNAUTHORIZED_PENDING_AUTHORITY);
+      await cancelAuthorityTransferAndVerify(program, AuthorityRole.Admin, adminKeyPair);
+    });
+
+    it("Cancelled transfer cannot be accepted", async () => {
+      const newAdmin = Keypair.generate();
+      await proposeAuthorityAndVerify(program, AuthorityRole.Admin, newAdmin.publicKey, adminKeyPair);
+      await cancelAuthorityTransferAndVerify(program, AuthorityRole.Admin, adminKeyPair);
+      await acceptAuthorityShouldFail(program, AuthorityRole.Admin, newAdmin, ErrorMsg.NO_PENDING_AUTHORITY_TRANSFER);
+    });
+
+    it("Should fail to cancel without a pending transfer", async () => {
+      await cancelAuthorityTransferShouldFail(program, AuthorityRole.Admin, adminKeyPair, ErrorMsg.NO_PENDING_AUTHORITY_TRANSFER);
+    });
+
+    it("Program deployer override clears the pending admin", async () => {
+      const newAdmin = Keypair.generate();
+      await proposeAuthorityAndVerify(program, AuthorityRole.Admin, newAdmin.publicKey, adminKeyPair);
+      await setAdminAndVerify(program, newAdmin.publicKey, adminKeyPair);
+      await setAdminAndVerify(program, adminKeyPair.publicKey, adminKeyPair);
+      await acceptAuthorityShouldFail(program, AuthorityRole.Admin, newAdmin, ErrorMsg.NO_PENDING_AUTHORITY_TRANSFER);
+    });
+  });
+
+  describe("Two step deny list authority transfer", () => {
+    before("Set deny list authority to a known key", async () => {
+      await setDenyListAuthorityAndVerify(program, adminKeyPair.publicKey);
+    });
+
+    it("Proposed deny list authority can accept the transfer", async () => {
+      const newAuthority = Keypair.generate();
+      await proposeAuthorityAndVerify(program, AuthorityRole.DenyListAuthority, newAuthority.publicKey, adminKeyPair);
+      await acceptAuthorityAndVerify(program, AuthorityRole.DenyListAuthority, newAuthority);
+
+      // Revert: hand the role back to adminKeyPair
+      await proposeAuthorityAndVerify(program, AuthorityRole.DenyListAuthority, adminKeyPair.publicKey, newAuthority);
+      await acceptAuthorityAndVerify(program, AuthorityRole.DenyListAuthority, adminKeyPair);
+    });
+
+    it("Should fail to propose if not deny list authority", async () => {
+      const intruder = Keypair.generate();
+      await proposeAuthorityShouldFail(program, AuthorityRole.DenyListAuthority, intruder.publicKey, intruder, ErrorMsg.UNAUTHORIZED_DENY_LIST_AUTHORITY);
+    });
+
+    it("Only the proposed deny list authority can accept", async () => {
+      const newAuthority = Keypair.generate();
+      const intruder = Keypair.generate();
+      await proposeAuthorityAndVerify(program, AuthorityRole.DenyListAuthority, newAuthority.publicKey, adminKeyPair);
+      await acceptAuthorityShouldFail(program, AuthorityRole.DenyListAuthority, intruder, ErrorMsg.UNAUTHORIZED_PENDING_AUTHORITY);
+      await cancelAuthorityTransferAndVerify(program, AuthorityRole.DenyListAuthority, adminKeyPair);
+      await acceptAuthorityShouldFail(program, AuthorityRole.DenyListAuthority, newAuthority, ErrorMsg.NO_PENDING_AUTHORITY_TRANSFER);
+    });
+  });
+});
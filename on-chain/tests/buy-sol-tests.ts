@@ -1,22 +1,45 @@
 import * as anchor from "@coral-xyz/anchor";
 import {Program} from "@coral-xyz/anchor";
-import {airdrop, getDefaultKeyPair} from "./core/utils/accounts";
+import {airdrop, fetchProgramState, getDefaultKeyPair} from "./core/utils/accounts";
 import {initializeMockTransferSystemIfNeeded, mint2z} from "./core/test-flow/mock-transfer-program";
-import {createTokenAccount} from "./core/utils/token-utils";
-import {getMockProgramPDAs} from "./core/utils/pda-helper";
+import {createTokenAccount, getTokenBalance} from "./core/utils/token-utils";
+import {getMockProgramPDAs, getTradeHistoryPDA} from "./core/utils/pda-helper";
 import {Keypair, LAMPORTS_PER_SOL, PublicKey, Transaction, TransactionInstruction} from "@solana/web3.js";
-import {buySolAndVerify, buySolFail, buySolSuccess, prepareBuySolInstruction} from "./core/test-flow/buy-sol-flow";
+import {
+    buySolAndVerify,
+    buySolFail,
+    buySolSuccess,
+    prepareBuySolInstruction,
+    prepareBuySolWithPostedPriceInstruction,
+    prepareBuySolWithPrecompileInstructions,
+    prepareBuySolWithQuorumInstruction
+} from "./core/test-flow/buy-sol-flow";
+import {
+    getPostedConversionPriceAndVerify,
+    nextPostTimestamp,
+    postPriceAndVerify
+} from "./core/test-flow/oracle-price";
 import {ConverterProgram} from "../target/types/converter_program";
 import {initializeSystemIfNeeded} from "./core/test-flow/system-initialize";
 import {DEFAULT_CONFIGS, SystemConfig} from "./core/utils/configuration-registry";
 import {updateConfigsAndVerify} from "./core/test-flow/change-configs";
-import {getConversionPriceAndVerify} from "./core/test-flow/conversion-price";
-import {getOraclePriceData, getOraclePriceDataFor} from "./core/utils/price-oracle";
-import {BPS, ErrorMsg, Events, MOCK_TRANSFER_PROGRAM, TOKEN_UNITS} from "./core/constants";
+import {getConversionPriceAndVerify, getQuorumConversionPriceAndVerify} from "./core/test-flow/conversion-price";
+import {getOraclePriceData, getOraclePriceDataFor, getQuorumPriceDataFor} from "./core/utils/price-oracle";
+import {
+    BPS,
+    ErrorMsg,
+    Events,
+    MAX_ORACLE_QUORUM_SIZE,
+    MOCK_TRANSFER_PROGRAM,
+    PauseFlags,
+    TOKEN_UNITS
+} from "./core/constants";
 import {airdropJournal} from "./core/utils/mock-transfer-program-utils";
 import {addToDenyListAndVerify, removeFromDenyListAndVerify, setDenyListAuthorityAndVerify} from "./core/test-flow/deny-list";
-import {toggleSystemStateAndVerify} from "./core/test-flow/system-state";
+import {setPauseFlagsAndVerify, toggleSystemStateAndVerify} from "./core/test-flow/system-state";
 import {assert, expect} from "chai";
+import {getFillsRegistryAccount} from "./core/utils/fills-registry";
+import {findAnchorEventInLogs, getTransactionLogs} from "./core/utils/return-data";
 
 describe("Buy Sol Tests", () => {
     // Configure the client to use the local cluster.
@@ -192,7 +215,7 @@ describe("Buy Sol Tests", () => {
                 askPrice,
                 userKeyPair,
                 oraclePriceData,
-                ErrorMsg.ATTESTATION_INVALID
+                ErrorMsg.ATTESTATION_NOT_AUTHENTIC
             );
         });
 
@@ -510,6 +533,26 @@ describe("Buy Sol Tests", () => {
             );
         });
 
+        it("should fail to do buy sol while buys are paused", async () => {
+            await toggleSystemStateAndVerify(program, false);
+            await setPauseFlagsAndVerify(program, PauseFlags.BUYS);
+
+            const oraclePriceData = await getOraclePriceData();
+            // Quotes keep working while only buys are paused.
+            const askPrice = await getConversionPriceAndVerify(program, oraclePriceData);
+            await buySolFail(
+                program,
+                tokenAccountForUser,
+                askPrice,
+                userKeyPair,
+                oraclePriceData,
+                ErrorMsg.FEATURE_PAUSED
+            );
+
+            await setPauseFlagsAndVerify(program, 0);
+            await toggleSystemStateAndVerify(program, true);
+        });
+
         it("User should be able to do buy SOL after market gets opened", async () => {
             await toggleSystemStateAndVerify(program, false);
 
@@ -626,6 +669,560 @@ describe("Buy Sol Tests", () => {
 
     });
 
+    describe("Variable SOL amount", async () => {
+        it("User does buy a SOL amount different from the default quantity", async () => {
+            const solAmount = 3 * LAMPORTS_PER_SOL;
+            const oraclePriceData = await getOraclePriceData();
+            const askPrice = await getConversionPriceAndVerify(program, oraclePriceData, userKeyPair);
+            // Ensure that user has sufficient 2Z.
+            await mint2z(
+                program,
+                tokenAccountForUser,
+                askPrice * solAmount / LAMPORTS_PER_SOL
+            );
+            // Ensure journal has funds.
+            await airdropJournal(program, new anchor.BN(solAmount))
+
+            await buySolAndVerify(
+                program,
+                tokenAccountForUser,
+                askPrice,
+                userKeyPair,
+                oraclePriceData,
+                currentConfigs,
+                solAmount
+            );
+        });
+
+        it("Fails to buy SOL below the minimum SOL quantity", async () => {
+            const solAmount = Number(currentConfigs.minSolQuantity) - 1;
+            const oraclePriceData = await getOraclePriceData();
+            const askPrice = await getConversionPriceAndVerify(program, oraclePriceData, userKeyPair);
+
+            await buySolFail(
+                program,
+                tokenAccountForUser,
+                askPrice,
+                userKeyPair,
+                oraclePriceData,
+                ErrorMsg.INVALID_SOL_QUANTITY,
+                "",
+                solAmount
+            );
+        });
+
+        it("Fails to buy SOL above the maximum SOL quantity", async () => {
+            const solAmount = Number(currentConfigs.maxSolQuantity) + 1;
+            const oraclePriceData = await getOraclePriceData();
+            const askPrice = await getConversionPriceAndVerify(program, oraclePriceData, userKeyPair);
+
+            await buySolFail(
+                program,
+                tokenAccountForUser,
+                askPrice,
+                userKeyPair,
+                oraclePriceData,
+                ErrorMsg.INVALID_SOL_QUANTITY,
+                "",
+                solAmount
+            );
+        });
+    });
+
+    describe("Ed25519 precompile", async () => {
+        it("User does buy SOL with a precompile verified attestation", async () => {
+            const oraclePriceData = await getOraclePriceData();
+            const askPrice = await getConversionPriceAndVerify(program, oraclePriceData, userKeyPair);
+            // Ensure that user has sufficient 2Z.
+            await mint2z(
+                program,
+                tokenAccountForUser,
+                askPrice * Number(currentConfigs.solQuantity) / LAMPORTS_PER_SOL
+            );
+            // Ensure journal has funds.
+            await airdropJournal(program, currentConfigs.solQuantity)
+
+            const tokenBalanceBefore = await getTokenBalance(program.provider.connection, tokenAccountForUser);
+            const fillsCountBefore = (await getFillsRegistryAccount(program)).count;
+
+            const instructions = await prepareBuySolWithPrecompileInstructions(
+                program,
+                tokenAccountForUser,
+                askPrice,
+                userKeyPair,
+                oraclePriceData
+            );
+            const txSig = await program.provider.sendAndConfirm(
+                new anchor.web3.Transaction().add(...instructions),
+                [userKeyPair]
+            );
+
+            const logs = await getTransactionLogs(program.provider, txSig);
+            const event = await findAnchorEventInLogs(logs, program.idl, Events.TRADE);
+            expect(event, "Trade event should be emitted").to.exist;
+
+            const tokenBalanceAfter = await getTokenBalance(program.provider.connection, tokenAccountForUser);
+            assert(tokenBalanceAfter < tokenBalanceBefore, "2Z should be spent on the trade");
+            assert.equal((await getFillsRegistryAccount(program)).count, fillsCountBefore + 1);
+
+            // Ensure that we can trade in the next slot.
+            await new Promise(resolve => setTimeout(resolve, 400));
+        });
+
+        it("Fails buy SOL execution without the precompile instruction", async () => {
+            const oraclePriceData = await getOraclePriceData();
+            const askPrice = await getConversionPriceAndVerify(program, oraclePriceData, userKeyPair);
+
+            try {
+                const instructions = await prepareBuySolWithPrecompileInstructions(
+                    program,
+                    tokenAccountForUser,
+                    askPrice,
+                    userKeyPair,
+                    oraclePriceData,
+                    []
+                );
+                await program.provider.sendAndConfirm(
+                    new anchor.web3.Transaction().add(...instructions),
+                    [userKeyPair]
+                );
+            } catch (error) {
+                expect((new Error(error!.toString())).message).to.include(ErrorMsg.INVALID_ED25519_INSTRUCTION);
+                return;
+            }
+            assert.fail("It was able to do buy SOL");
+        });
+    });
+
+    describe("Attestation replay protection", async () => {
+        after("Restore the default attestation timestamp tolerance", async () => {
+            await updateConfigsAndVerify(program, { ...currentConfigs, attestationTimestampTolerance: 0 });
+        });
+
+        it("Fails to buy SOL with an attestation older than the latest consumed one", async () => {
+            // Consume a fresh attestation first.
+            await buySolSuccess(program, tokenAccountForUser, userKeyPair, currentConfigs, 1.1);
+
+            const oraclePriceData = await getOraclePriceDataFor(20, Math.floor(Date.now() / 1000) - 30);
+            await buySolFail(
+                program,
+                tokenAccountForUser,
+                oraclePriceData.swapRate,
+                userKeyPair,
+                oraclePriceData,
+                ErrorMsg.ATTESTATION_OUT_OF_ORDER
+            );
+        });
+
+        it("User does buy SOL with an older attestation within the tolerance", async () => {
+            await updateConfigsAndVerify(program, { ...currentConfigs, attestationTimestampTolerance: 60 });
+            await buySolSuccess(program, tokenAccountForUser, userKeyPair, currentConfigs, 1.1);
+
+            const oraclePriceData = await getOraclePriceDataFor(20, Math.floor(Date.now() / 1000) - 30);
+            const askPrice = await getConversionPriceAndVerify(program, oraclePriceData, userKeyPair);
+            // Ensure that user has sufficient 2Z.
+            await mint2z(
+                program,
+                tokenAccountForUser,
+                askPrice * Number(currentConfigs.solQuantity) / LAMPORTS_PER_SOL
+            );
+            // Ensure journal has funds.
+            await airdropJournal(program, currentConfigs.solQuantity)
+
+            await buySolAndVerify(
+                program,
+                tokenAccountForUser,
+                askPrice,
+                userKeyPair,
+                oraclePriceData,
+                currentConfigs
+            );
+        });
+    });
+
+    describe("Per wallet limits", async () => {
+        let buyerKeyPair: Keypair;
+        let buyerTokenAccount: PublicKey;
+
+        beforeEach("Create a fresh buyer", async () => {
+            buyerKeyPair = anchor.web3.Keypair.generate();
+            await airdrop(program.provider.connection, buyerKeyPair.publicKey, 10 * LAMPORTS_PER_SOL);
+            buyerTokenAccount = await createTokenAccount(
+                program.provider.connection,
+                mockTransferProgramPDAs.tokenMint,
+                buyerKeyPair.publicKey,
+            );
+        });
+
+        after("Disable the per wallet limits", async () => {
+            await updateConfigsAndVerify(program, {
+                ...currentConfigs,
+                maxTradesPerWalletPerEpoch: new anchor.BN(0),
+                maxSolPerWalletPerEpoch: new anchor.BN(0),
+            });
+        });
+
+        it("Records the trades of the buyer for the current epoch", async () => {
+            await buySolSuccess(program, buyerTokenAccount, buyerKeyPair, currentConfigs, 1.1);
+
+            const tradeHistory = await program.account.tradeHistory.fetch(
+                getTradeHistoryPDA(program.programId, buyerKeyPair.publicKey)
+            );
+            const {epoch} = await program.provider.connection.getEpochInfo();
+            assert.equal(tradeHistory.epoch.toNumber(), epoch);
+            assert.equal(tradeHistory.numOfTrades.toNumber(), 1);
+            assert.equal(tradeHistory.solBought.toString(), currentConfigs.solQuantity.toString());
+        });
+
+        it("Fails to buy SOL above the maximum number of trades per wallet", async () => {
+            await updateConfigsAndVerify(program, {
+                ...currentConfigs,
+                maxTradesPerWalletPerEpoch: new anchor.BN(1),
+                maxSolPerWalletPerEpoch: new anchor.BN(0),
+            });
+            await buySolSuccess(program, buyerTokenAccount, buyerKeyPair, currentConfigs, 1.1);
+
+            const oraclePriceData = await getOraclePriceData();
+            const askPrice = await getConversionPriceAndVerify(program, oraclePriceData, buyerKeyPair);
+            await buySolFail(
+                program,
+                buyerTokenAccount,
+                askPrice,
+                buyerKeyPair,
+                oraclePriceData,
+                ErrorMsg.WALLET_TRADE_LIMIT_EXCEEDED
+            );
+        });
+
+        it("Fails to buy SOL above the maximum SOL amount per wallet", async () => {
+            await updateConfigsAndVerify(program, {
+                ...currentConfigs,
+                maxTradesPerWalletPerEpoch: new anchor.BN(0),
+                maxSolPerWalletPerEpoch: currentConfigs.solQuantity.add(new anchor.BN(LAMPORTS_PER_SOL)),
+            });
+            await buySolSuccess(program, buyerTokenAccount, buyerKeyPair, currentConfigs, 1.1);
+
+            const oraclePriceData = await getOraclePriceData();
+            const askPrice = await getConversionPriceAndVerify(program, oraclePriceData, buyerKeyPair);
+            await buySolFail(
+                program,
+                buyerTokenAccount,
+                askPrice,
+                buyerKeyPair,
+                oraclePriceData,
+                ErrorMsg.WALLET_SOL_LIMIT_EXCEEDED
+            );
+        });
+
+        it("Other wallets are not affected by the limit of a wallet", async () => {
+            await updateConfigsAndVerify(program, {
+                ...currentConfigs,
+                maxTradesPerWalletPerEpoch: new anchor.BN(1),
+                maxSolPerWalletPerEpoch: new anchor.BN(0),
+            });
+            await buySolSuccess(program, buyerTokenAccount, buyerKeyPair, currentConfigs, 1.1);
+
+            const otherBuyerKeyPair = anchor.web3.Keypair.generate();
+            await airdrop(program.provider.connection, otherBuyerKeyPair.publicKey, 10 * LAMPORTS_PER_SOL);
+            const otherBuyerTokenAccount = await createTokenAccount(
+                program.provider.connection,
+                mockTransferProgramPDAs.tokenMint,
+                otherBuyerKeyPair.publicKey,
+            );
+            await buySolSuccess(program, otherBuyerTokenAccount, otherBuyerKeyPair, currentConfigs, 1.1);
+        });
+    });
+
+    describe("Epoch SOL cap", async () => {
+        // SOL already sold in the current epoch, the counter restarts with a new epoch.
+        const getEpochSolSold = async (): Promise<anchor.BN> => {
+            const {currentEpoch, epochSolSold} = await fetchProgramState(program);
+            const {epoch} = await program.provider.connection.getEpochInfo();
+            return currentEpoch.toNumber() === epoch ? epochSolSold : new anchor.BN(0);
+        };
+
+        after("Disable the epoch SOL cap", async () => {
+            await updateConfigsAndVerify(program, { ...currentConfigs, epochSolCap: new anchor.BN(0) });
+        });
+
+        it("Counts the SOL sold in the current epoch", async () => {
+            const epochSolSoldBefore = await getEpochSolSold();
+            await buySolSuccess(program, tokenAccountForUser, userKeyPair, currentConfigs, 1.1);
+
+            const epochSolSoldAfter = await getEpochSolSold();
+            assert.equal(
+                epochSolSoldAfter.sub(epochSolSoldBefore).toString(),
+                currentConfigs.solQuantity.toString()
+            );
+        });
+
+        it("Fails to buy SOL over the epoch SOL cap", async () => {
+            // Leave room for exactly one more lot.
+            await updateConfigsAndVerify(program, {
+                ...currentConfigs,
+                epochSolCap: (await getEpochSolSold()).add(currentConfigs.solQuantity),
+            });
+            await buySolSuccess(program, tokenAccountForUser, userKeyPair, currentConfigs, 1.1);
+
+            const oraclePriceData = await getOraclePriceData();
+            const askPrice = await getConversionPriceAndVerify(program, oraclePriceData, userKeyPair);
+            await buySolFail(
+                program,
+                tokenAccountForUser,
+                askPrice,
+                userKeyPair,
+                oraclePriceData,
+                ErrorMsg.EPOCH_SOL_CAP_EXCEEDED,
+                Events.EPOCH_CAP_REACHED
+            );
+        });
+    });
+
+    describe("Fills registry watermarks", async () => {
+        after("Disable the watermarks", async () => {
+            await updateConfigsAndVerify(program, {
+                ...currentConfigs,
+                fillsHighWatermarkBps: 0,
+                fillsLowWatermarkBps: 0,
+                stopBuysAtHighWatermark: false,
+            });
+        });
+
+        it("Flags the registry once a buy crosses the high watermark", async () => {
+            await updateConfigsAndVerify(program, {
+                ...currentConfigs,
+                fillsHighWatermarkBps: 1,
+                fillsLowWatermarkBps: 0,
+                stopBuysAtHighWatermark: false,
+            });
+
+            // A single basis point of the capacity, buys continue while flagged.
+            const {count, maxCapacity} = await getFillsRegistryAccount(program);
+            const buysNeeded = Math.max(1, Math.ceil(maxCapacity / 10_000) - count);
+            for (let i = 0; i < buysNeeded; i++) {
+                await buySolSuccess(program, tokenAccountForUser, userKeyPair, currentConfigs, 1.1);
+            }
+            assert.isTrue((await fetchProgramState(program)).fillsAboveHighWatermark);
+            await buySolSuccess(program, tokenAccountForUser, userKeyPair, currentConfigs, 1.1);
+        });
+
+        it("Fails to buy SOL above the high watermark when buys stop there", async () => {
+            await updateConfigsAndVerify(program, {
+                ...currentConfigs,
+                fillsHighWatermarkBps: 1,
+                fillsLowWatermarkBps: 0,
+                stopBuysAtHighWatermark: true,
+            });
+
+            const oraclePriceData = await getOraclePriceData();
+            const askPrice = await getConversionPriceAndVerify(program, oraclePriceData, userKeyPair);
+            await buySolFail(
+                program,
+                tokenAccountForUser,
+                askPrice,
+                userKeyPair,
+                oraclePriceData,
+                ErrorMsg.FILLS_REGISTRY_ABOVE_HIGH_WATERMARK
+            );
+        });
+
+        it("Resumes buys once the registry is at the low watermark", async () => {
+            await updateConfigsAndVerify(program, {
+                ...currentConfigs,
+                fillsHighWatermarkBps: 10_000,
+                fillsLowWatermarkBps: 9_999,
+                stopBuysAtHighWatermark: true,
+            });
+            await buySolSuccess(program, tokenAccountForUser, userKeyPair, currentConfigs, 1.1);
+            assert.isFalse((await fetchProgramState(program)).fillsAboveHighWatermark);
+        });
+    });
+
+    describe("Oracle rate sanity band", async () => {
+        const REFERENCE_SWAP_RATE_MAX_AGE = 10; // seconds
+        after("Disable the swap rate band", async () => {
+            await updateConfigsAndVerify(program, {
+                ...currentConfigs,
+                minSwapRate: new anchor.BN(0),
+                maxSwapRate: new anchor.BN(0),
+                maxSwapRateDeviationBps: 0,
+            });
+        });
+
+        it("Fails to buy SOL at a swap rate outside the configured bounds", async () => {
+            await updateConfigsAndVerify(program, {
+                ...currentConfigs,
+                minSwapRate: new anchor.BN(19 * TOKEN_UNITS),
+                maxSwapRate: new anchor.BN(22 * TOKEN_UNITS),
+            });
+
+            const oraclePriceData = await getOraclePriceDataFor(30, Math.floor(Date.now() / 1000));
+            await buySolFail(
+                program,
+                tokenAccountForUser,
+                oraclePriceData.swapRate,
+                userKeyPair,
+                oraclePriceData,
+                ErrorMsg.ORACLE_RATE_OUT_OF_BAND,
+                Events.ORACLE_RATE_REJECTED
+            );
+        });
+
+        it("Fails to buy SOL at a swap rate too far from the reference rate", async () => {
+            await updateConfigsAndVerify(program, {
+                ...currentConfigs,
+                minSwapRate: new anchor.BN(0),
+                maxSwapRate: new anchor.BN(0),
+                maxSwapRateDeviationBps: 20 * BPS,
+                referenceSwapRateMaxAge: REFERENCE_SWAP_RATE_MAX_AGE,
+            });
+
+            // The reference rate follows the 19 - 22 rates of executed trades.
+            await buySolSuccess(program, tokenAccountForUser, userKeyPair, currentConfigs, 1.1);
+            const {referenceSwapRate} = await fetchProgramState(program);
+            assert.isTrue(referenceSwapRate.gt(new anchor.BN(0)));
+
+            const oraclePriceData = await getOraclePriceDataFor(40, Math.floor(Date.now() / 1000));
+            await buySolFail(
+                program,
+                tokenAccountForUser,
+                oraclePriceData.swapRate,
+                userKeyPair,
+                oraclePriceData,
+                ErrorMsg.ORACLE_RATE_OUT_OF_BAND,
+                Events.ORACLE_RATE_REJECTED
+            );
+        });
+
+        it("User does buy SOL at the moved rate once the reference rate expires", async () => {
+            // No trade within the maximum age, the next trade re-seeds the reference rate.
+            await new Promise(resolve => setTimeout(resolve, (REFERENCE_SWAP_RATE_MAX_AGE + 1) * 1000));
+
+            const oraclePriceData = await getOraclePriceDataFor(40, Math.floor(Date.now() / 1000));
+            const askPrice = await getConversionPriceAndVerify(program, oraclePriceData);
+            await mint2z(
+                program,
+                tokenAccountForUser,
+                askPrice * 1.1 * Number(currentConfigs.solQuantity) / LAMPORTS_PER_SOL
+            );
+            await airdropJournal(program, currentConfigs.solQuantity);
+            await buySolAndVerify(
+                program,
+                tokenAccountForUser,
+                Math.floor(askPrice * 1.1),
+                userKeyPair,
+                oraclePriceData,
+                currentConfigs
+            );
+
+            const {referenceSwapRate} = await fetchProgramState(program);
+            assert.equal(referenceSwapRate.toNumber(), oraclePriceData.swapRate);
+        });
+    });
+
+    describe("Posted oracle price", async () => {
+        it("User does buy SOL at the posted oracle price", async () => {
+            const postedPrice = await postPriceAndVerify(program, 20, await nextPostTimestamp(program));
+            const askPrice = await getPostedConversionPriceAndVerify(program, postedPrice, userKeyPair);
+            // Ensure that user has sufficient 2Z.
+            await mint2z(
+                program,
+                tokenAccountForUser,
+                askPrice * 1.1 * Number(currentConfigs.solQuantity) / LAMPORTS_PER_SOL
+            );
+            // Ensure journal has funds.
+            await airdropJournal(program, currentConfigs.solQuantity)
+
+            const fillsCountBefore = (await getFillsRegistryAccount(program)).count;
+            const ix = await prepareBuySolWithPostedPriceInstruction(
+                program,
+                tokenAccountForUser,
+                Math.floor(askPrice * 1.1),
+                userKeyPair
+            );
+            const txSig = await program.provider.sendAndConfirm(new anchor.web3.Transaction().add(ix), [userKeyPair]);
+
+            const logs = await getTransactionLogs(program.provider, txSig);
+            const event = await findAnchorEventInLogs(logs, program.idl, Events.TRADE);
+            expect(event, "Trade event should be emitted").to.exist;
+            assert.equal(event.data.attestationTimestamp.toNumber(), postedPrice.timestamp);
+            assert.equal((await getFillsRegistryAccount(program)).count, fillsCountBefore + 1);
+
+            // Ensure that we can trade in the next slot.
+            await new Promise(resolve => setTimeout(resolve, 400));
+        });
+
+        it("Fails to buy SOL below the ask derived from the posted price", async () => {
+            const postedPrice = await postPriceAndVerify(program, 20, await nextPostTimestamp(program));
+
+            try {
+                const ix = await prepareBuySolWithPostedPriceInstruction(
+                    program,
+                    tokenAccountForUser,
+                    Math.floor(postedPrice.swapRate / 2),
+                    userKeyPair
+                );
+                await program.provider.sendAndConfirm(new anchor.web3.Transaction().add(ix), [userKeyPair]);
+            } catch (error) {
+                expect((new Error(error!.toString())).message).to.include(ErrorMsg.BID_TOO_LOW);
+                return;
+            }
+            assert.fail("It was able to do buy SOL");
+        });
+    });
+
+    describe("Oracle quorum at the maximum size", async () => {
+        const oracles = Array.from({length: MAX_ORACLE_QUORUM_SIZE}, () => Keypair.generate());
+
+        before("Configure a quorum requiring every oracle", async () => {
+            await updateConfigsAndVerify(program, {
+                ...DEFAULT_CONFIGS,
+                oraclePubkeys: oracles.map((oracle) => oracle.publicKey),
+                oracleQuorumThreshold: MAX_ORACLE_QUORUM_SIZE,
+            });
+        });
+
+        after("Disable the oracle quorum", async () => {
+            await updateConfigsAndVerify(program, {
+                ...DEFAULT_CONFIGS,
+                oraclePubkeys: [],
+                oracleQuorumThreshold: 0,
+            });
+        });
+
+        it("User does buy SOL with an attestation from every oracle of the quorum", async () => {
+            const quorumPriceData = await getQuorumPriceDataFor(oracles, [19.8, 19.9, 20, 20.1, 20.2]);
+            const askPrice = await getQuorumConversionPriceAndVerify(program, quorumPriceData, 20 * TOKEN_UNITS, userKeyPair);
+            // Ensure that user has sufficient 2Z.
+            await mint2z(
+                program,
+                tokenAccountForUser,
+                askPrice * 1.1 * Number(currentConfigs.solQuantity) / LAMPORTS_PER_SOL
+            );
+            // Ensure journal has funds.
+            await airdropJournal(program, currentConfigs.solQuantity)
+
+            // The attestations of the whole quorum have to fit in a single transaction.
+            const fillsCountBefore = (await getFillsRegistryAccount(program)).count;
+            const ix = await prepareBuySolWithQuorumInstruction(
+                program,
+                tokenAccountForUser,
+                Math.floor(askPrice * 1.1),
+                userKeyPair,
+                quorumPriceData
+            );
+            const txSig = await program.provider.sendAndConfirm(new anchor.web3.Transaction().add(ix), [userKeyPair]);
+
+            const logs = await getTransactionLogs(program.provider, txSig);
+            const event = await findAnchorEventInLogs(logs, program.idl, Events.TRADE);
+            expect(event, "Trade event should be emitted").to.exist;
+            assert.equal((await getFillsRegistryAccount(program)).count, fillsCountBefore + 1);
+
+            // Ensure that we can trade in the next slot.
+            await new Promise(resolve => setTimeout(resolve, 400));
+        });
+    });
+
     describe("Config change check", async () => {
 
         it("User should be able to do buy SOL with proper rates", async () => {
@@ -1,11 +1,26 @@
 import * as anchor from "@coral-xyz/anchor";
 import { setup } from "./core/setup";
 import {airdrop, getDefaultKeyPair, getRandomKeyPair} from "./core/utils/accounts";
-import { DEFAULT_CONFIGS } from "./core/utils/configuration-registry";
-import { updateConfigsAndVerify, updateConfigsAndVerifyFail } from "./core/test-flow/change-configs";
+import { DEFAULT_CONFIGS, fetchCurrentConfiguration } from "./core/utils/configuration-registry";
+import {
+  cancelConfigChangeAndVerify,
+  cancelConfigChangeAndVerifyFail,
+  executeConfigChangeAndVerify,
+  executeConfigChangeAndVerifyFail,
+  migrateConfigurationRegistryToFail,
+  migrateProgramStateToFail,
+  rotateOracleKeyAndVerify,
+  rotateOracleKeyAndVerifyFail,
+  queueConfigChangeAndVerify,
+  queueConfigChangeAndVerifyFail,
+  updateConfigsAndVerify,
+  updateConfigsAndVerifyFail
+} from "./core/test-flow/change-configs";
 import { initializeSystemIfNeeded } from "./core/test-flow/system-initialize";
 import {setFillsConsumerAndVerify, setFillsConsumerExpectUnauthorized} from "./core/test-flow/set-fills-consumer";
-import {ErrorMsg} from "./core/constants";
+import {BPS, ErrorMsg, MAX_ORACLE_QUORUM_SIZE, TOKEN_UNITS} from "./core/constants";
+import {LAMPORTS_PER_SOL} from "@solana/web3.js";
+import {assert} from "chai";
 
 describe("Configuration Registry Update Tests", async () => {
   const program = await setup();
@@ -122,6 +137,110 @@ describe("Configuration Registry Update Tests", async () => {
     await updateConfigsAndVerify(program, DEFAULT_CONFIGS);
   });
 
+  it("Should fail to update with min SOL quantity above max SOL quantity", async () => {
+    await updateConfigsAndVerifyFail(program, {
+          ...DEFAULT_CONFIGS,
+          minSolQuantity: new anchor.BN(50 * LAMPORTS_PER_SOL),
+          maxSolQuantity: new anchor.BN(10 * LAMPORTS_PER_SOL)
+        },
+        ErrorMsg.INVALID_SOL_QUANTITY
+    );
+
+    // Revert: Set config to default
+    await updateConfigsAndVerify(program, DEFAULT_CONFIGS);
+  });
+
+  it("Should fail to update with a low fills watermark not below the high watermark", async () => {
+    await updateConfigsAndVerifyFail(program, {
+          ...DEFAULT_CONFIGS,
+          fillsHighWatermarkBps: 100,
+          fillsLowWatermarkBps: 100
+        },
+        ErrorMsg.INVALID_FILLS_WATERMARK
+    );
+  });
+
+  it("Should fail to update with a zero length discount curve step", async () => {
+    await updateConfigsAndVerifyFail(program, {
+          ...DEFAULT_CONFIGS,
+          discountCurve: {stepped: {stepLength: new anchor.BN(0), stepRate: new anchor.BN(500)}}
+        },
+        ErrorMsg.INVALID_DISCOUNT_CURVE
+    );
+  });
+
+  it("Should fail to update with a full discount memory", async () => {
+    await updateConfigsAndVerifyFail(program, {
+          ...DEFAULT_CONFIGS,
+          discountMemoryBps: 10_000
+        },
+        ErrorMsg.INVALID_DISCOUNT_MEMORY
+    );
+  });
+
+  it("Should fail to update with a minimum swap rate above the maximum", async () => {
+    await updateConfigsAndVerifyFail(program, {
+          ...DEFAULT_CONFIGS,
+          minSwapRate: new anchor.BN(30 * TOKEN_UNITS),
+          maxSwapRate: new anchor.BN(10 * TOKEN_UNITS)
+        },
+        ErrorMsg.INVALID_SWAP_RATE_BAND
+    );
+  });
+
+  it("Should fail to enable the swap rate deviation guard without a reference rate maximum age", async () => {
+    await updateConfigsAndVerifyFail(program, {
+          ...DEFAULT_CONFIGS,
+          maxSwapRateDeviationBps: 20 * BPS,
+          referenceSwapRateMaxAge: 0
+        },
+        ErrorMsg.INVALID_SWAP_RATE_BAND
+    );
+  });
+
+  it("Should fail to update with zero min SOL quantity", async () => {
+    await updateConfigsAndVerifyFail(program, {
+          ...DEFAULT_CONFIGS,
+          minSolQuantity: new anchor.BN(0)
+        },
+        ErrorMsg.INVALID_SOL_QUANTITY
+    );
+
+    // Revert: Set config to default
+    await updateConfigsAndVerify(program, DEFAULT_CONFIGS);
+  });
+
+  it("Should fail to update with quorum threshold above the oracle count", async () => {
+    await updateConfigsAndVerifyFail(program, {
+          ...DEFAULT_CONFIGS,
+          oraclePubkeys: [anchor.web3.Keypair.generate().publicKey],
+          oracleQuorumThreshold: 2
+        },
+        ErrorMsg.INVALID_ORACLE_QUORUM
+    );
+  });
+
+  it("Should fail to update with more oracle keys than fit in a buy transaction", async () => {
+    await updateConfigsAndVerifyFail(program, {
+          ...DEFAULT_CONFIGS,
+          oraclePubkeys: Array.from({length: MAX_ORACLE_QUORUM_SIZE + 1}, () => anchor.web3.Keypair.generate().publicKey),
+          oracleQuorumThreshold: 1
+        },
+        ErrorMsg.INVALID_ORACLE_QUORUM
+    );
+  });
+
+  it("Should fail to update with duplicate oracle keys", async () => {
+    const oraclePubkey = anchor.web3.Keypair.generate().publicKey;
+    await updateConfigsAndVerifyFail(program, {
+          ...DEFAULT_CONFIGS,
+          oraclePubkeys: [oraclePubkey, oraclePubkey],
+          oracleQuorumThreshold: 1
+        },
+        ErrorMsg.INVALID_ORACLE_QUORUM
+    );
+  });
+
   describe("Set fills consumer Tests", async () => {
       it("Non-admin cannot add a fills consumer", async () => {
           const nonAdmin = anchor.web3.Keypair.generate();
@@ -134,4 +253,142 @@ describe("Configuration Registry Update Tests", async () => {
           await setFillsConsumerAndVerify(program, getDefaultKeyPair(), fillsConsumer);
       });
   });
-});
\ No newline at end of file
+
+  describe("Timelocked config changes", async () => {
+    const CONFIG_CHANGE_DELAY = 3; // seconds
+    const waitForDelay = () => new Promise(resolve => setTimeout(resolve, (CONFIG_CHANGE_DELAY + 1) * 1000));
+
+    before("Enable the config change delay", async () => {
+      // Raising the delay does not need the timelock.
+      await updateConfigsAndVerify(program, {...DEFAULT_CONFIGS, configChangeDelay: CONFIG_CHANGE_DELAY});
+    });
+
+    after("Disable the config change delay", async () => {
+      const changeId = await queueConfigChangeAndVerify(program, {...DEFAULT_CONFIGS, configChangeDelay: 0});
+      await waitForDelay();
+      await executeConfigChangeAndVerify(program, changeId);
+      assert.equal((await fetchCurrentConfiguration(program)).configChangeDelay, 0);
+    });
+
+    it("Economic changes cannot be applied immediately", async () => {
+      await updateConfigsAndVerifyFail(program, {
+            ...DEFAULT_CONFIGS,
+            coefficient: new anchor.BN(2),
+            configChangeDelay: CONFIG_CHANGE_DELAY
+          },
+          ErrorMsg.CONFIG_CHANGE_REQUIRES_TIMELOCK
+      );
+    });
+
+    it("Config change delay cannot be reduced immediately", async () => {
+      await updateConfigsAndVerifyFail(program, {...DEFAULT_CONFIGS, configChangeDelay: 0},
+          ErrorMsg.CONFIG_CHANGE_REQUIRES_TIMELOCK
+      );
+    });
+
+    it("Non admin user should not be able to queue a config change", async () => {
+      const nonAdmin = await getRandomKeyPair(program.provider.connection);
+      await queueConfigChangeAndVerifyFail(program, DEFAULT_CONFIGS, ErrorMsg.UNAUTHORIZED_ADMIN, nonAdmin);
+    });
+
+    it("Invalid config changes are rejected when queued", async () => {
+      await queueConfigChangeAndVerifyFail(program, {
+            ...DEFAULT_CONFIGS,
+            coefficient: new anchor.BN(100_000_001)
+          },
+          ErrorMsg.INVALID_COEFFICIENT
+      );
+    });
+
+    it("Queued config change can be executed by anyone once the delay passed", async () => {
+      const changeId = await queueConfigChangeAndVerify(program, {
+        ...DEFAULT_CONFIGS,
+        coefficient: new anchor.BN(2),
+        configChangeDelay: CONFIG_CHANGE_DELAY
+      });
+      await executeConfigChangeAndVerifyFail(program, changeId, ErrorMsg.CONFIG_CHANGE_NOT_READY);
+
+      await waitForDelay();
+      const executor = await getRandomKeyPair(program.provider.connection);
+      await executeConfigChangeAndVerify(program, changeId, executor);
+      assert.equal((await fetchCurrentConfiguration(program)).coefficient.toString(), "2");
+
+      // Revert: Set coefficient back to default
+      const revertChangeId = await queueConfigChangeAndVerify(program, {
+        ...DEFAULT_CONFIGS,
+        configChangeDelay: CONFIG_CHANGE_DELAY
+      });
+      await waitForDelay();
+      await executeConfigChangeAndVerify(program, revertChangeId);
+    });
+
+    it("Oracle key rotations activate after the config change delay", async () => {
+      const slot = await program.provider.connection.getSlot();
+      const now = await program.provider.connection.getBlockTime(slot);
+      await rotateOracleKeyAndVerifyFail(
+          program,
+          anchor.web3.Keypair.generate().publicKey,
+          now,
+          now + 300,
+          ErrorMsg.ORACLE_ROTATION_NOTICE_TOO_SHORT
+      );
+
+      const activationTimestamp = now + CONFIG_CHANGE_DELAY + 10;
+      await rotateOracleKeyAndVerify(
+          program,
+          anchor.web3.Keypair.generate().publicKey,
+          activationTimestamp,
+          activationTimestamp + 300
+      );
+
+      // Revert: Switching to an unrelated key clears the rotation, then restore the default oracle.
+      const clearChangeId = await queueConfigChangeAndVerify(program, {
+        ...DEFAULT_CONFIGS,
+        oraclePubkey: anchor.web3.Keypair.generate().publicKey,
+        configChangeDelay: CONFIG_CHANGE_DELAY
+      });
+      await waitForDelay();
+      await executeConfigChangeAndVerify(program, clearChangeId);
+      const revertChangeId = await queueConfigChangeAndVerify(program, {
+        ...DEFAULT_CONFIGS,
+        configChangeDelay: CONFIG_CHANGE_DELAY
+      });
+      await waitForDelay();
+      await executeConfigChangeAndVerify(program, revertChangeId);
+    });
+
+    it("Admin can cancel a queued config change", async () => {
+      const changeId = await queueConfigChangeAndVerify(program, {
+        ...DEFAULT_CONFIGS,
+        coefficient: new anchor.BN(3),
+        configChangeDelay: CONFIG_CHANGE_DELAY
+      });
+      await cancelConfigChangeAndVerifyFail(program, changeId, ErrorMsg.UNAUTHORIZED_ADMIN, await getRandomKeyPair(program.provider.connection));
+      await cancelConfigChangeAndVerify(program, changeId);
+      await executeConfigChangeAndVerifyFail(program, changeId, ErrorMsg.ACCOUNT_NOT_INITIALIZED);
+    });
+  });
+
+  describe("Account migration", async () => {
+    it("Non admin user should not be able to migrate the registry", async () => {
+      await migrateConfigurationRegistryToFail(
+        program,
+        ErrorMsg.UNAUTHORIZED_ADMIN,
+        await getRandomKeyPair(program.provider.connection)
+      );
+    });
+
+    it("Registry initialized with the current layout is not migrated again", async () => {
+      await migrateConfigurationRegistryToFail(program, ErrorMsg.ACCOUNT_ALREADY_MIGRATED);
+    });
+
+    it("Program state initialized with the current layout is not migrated again", async () => {
+      await migrateProgramStateToFail(program, ErrorMsg.ACCOUNT_ALREADY_MIGRATED);
+      await migrateProgramStateToFail(
+        program,
+        ErrorMsg.ACCOUNT_ALREADY_MIGRATED,
+        await getRandomKeyPair(program.provider.connection)
+      );
+    });
+  });
+});
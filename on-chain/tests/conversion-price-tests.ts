@@ -1,20 +1,47 @@
+import * as anchor from "@coral-xyz/anchor";
 import { describe } from "mocha";
-import { getConversionPriceAndVerify, getConversionPriceToFail } from "./core/test-flow/conversion-price";
-import { getOraclePriceData, getOraclePriceDataFor } from "./core/utils/price-oracle";
+import {
+    getConversionPriceAndVerify,
+    getConversionPriceToFail,
+    getPrecompileConversionPriceAndVerify,
+    getPrecompileConversionPriceToFail,
+    getQuorumConversionPriceAndVerify,
+    getQuorumConversionPriceToFail
+} from "./core/test-flow/conversion-price";
+import {
+    getEd25519Instruction,
+    getOraclePriceData,
+    getOraclePriceDataFor,
+    getQuorumPriceDataFor,
+    ORACLE_KEYPAIR
+} from "./core/utils/price-oracle";
 import { Keypair, LAMPORTS_PER_SOL } from "@solana/web3.js";
 import { addToDenyListAndVerify, removeFromDenyListAndVerify, setDenyListAuthorityAndVerify } from "./core/test-flow/deny-list";
 import { initializeSystemIfNeeded } from "./core/test-flow/system-initialize";
 import { setup } from "./core/setup";
 import { assert } from "chai";
 import { getDefaultKeyPair, getRandomKeyPair } from "./core/utils/accounts";
-import {BPS, ErrorMsg, TOKEN_UNITS} from "./core/constants";
+import {AttestationVersion, BPS, ErrorMsg, Events, TOKEN_UNITS} from "./core/constants";
 import { DEFAULT_CONFIGS } from "./core/utils/configuration-registry";
 import { getMockDoubleZeroTokenMintPDA } from "./core/utils/pda-helper";
 import { createTokenAccount } from "./core/utils/token-utils";
 import { airdropJournal } from "./core/utils/mock-transfer-program-utils";
 import { initializeMockTransferSystemIfNeeded, mint2z } from "./core/test-flow/mock-transfer-program";
 import { buySolAndVerify } from "./core/test-flow/buy-sol-flow";
-import {updateConfigsAndVerify} from "./core/test-flow/change-configs";
+import {
+    fetchPostedPrice,
+    getPostedConversionPriceAndVerify,
+    getPostedConversionPriceToFail,
+    nextPostTimestamp,
+    postPriceAndVerify,
+    postPriceToFail
+} from "./core/test-flow/oracle-price";
+import { delay } from "./core/utils/return-data";
+import {
+    rotateOracleKeyAndVerify,
+    rotateOracleKeyAndVerifyFail,
+    updateConfigsAndVerify
+} from "./core/test-flow/change-configs";
 
 describe("Conversion Price Tests", async () => {
     const program = await setup();
@@ -252,6 +279,402 @@ describe("Conversion Price Tests", async () => {
         oraclePriceData.signature = "invalid_signature";
 
         // Conversion price fetch should fail for invalid attestation signature.
-        await getConversionPriceToFail(program, oraclePriceData, ErrorMsg.ATTESTATION_INVALID);
+        await getConversionPriceToFail(program, oraclePriceData, ErrorMsg.ATTESTATION_NOT_AUTHENTIC);
+    });
+
+    // Attestation versions ------------------------------------------------------
+
+    it("Should get conversion price for every discount curve", async () => {
+        for (const discountCurve of [
+            {linearWithGrace: {graceLength: new anchor.BN(1_000)}},
+            {exponential: {halfLife: new anchor.BN(100)}},
+            {stepped: {stepLength: new anchor.BN(10), stepRate: new anchor.BN(2 * BPS)}},
+        ]) {
+            await updateConfigsAndVerify(program, { ...DEFAULT_CONFIGS, discountCurve });
+            await getConversionPriceAndVerify(program, await getOraclePriceData());
+        }
+
+        // Revert: Back to the linear curve.
+        await updateConfigsAndVerify(program, { ...DEFAULT_CONFIGS, discountCurve: {linear: {}} });
+    });
+
+    it("Should get conversion price with the discount running on the wall clock", async () => {
+        await updateConfigsAndVerify(program, { ...DEFAULT_CONFIGS, discountClock: {unixTimestamp: {}} });
+        await getConversionPriceAndVerify(program, await getOraclePriceData());
+
+        // Revert: Back to the slot clock.
+        await updateConfigsAndVerify(program, { ...DEFAULT_CONFIGS, discountClock: {slot: {}} });
+    });
+
+    it("Should fail to get conversion price for a swap rate outside the configured bounds", async () => {
+        await updateConfigsAndVerify(program, {
+            ...DEFAULT_CONFIGS,
+            minSwapRate: new anchor.BN(19 * TOKEN_UNITS),
+            maxSwapRate: new anchor.BN(22 * TOKEN_UNITS),
+        });
+
+        const oraclePriceData = await getOraclePriceDataFor(30, Math.floor(Date.now() / 1000));
+        await getConversionPriceToFail(
+            program,
+            oraclePriceData,
+            ErrorMsg.ORACLE_RATE_OUT_OF_BAND,
+            undefined,
+            Events.ORACLE_RATE_REJECTED
+        );
+        await getConversionPriceAndVerify(program, await getOraclePriceData());
+
+        // Revert: Disable the bounds.
+        await updateConfigsAndVerify(program, { ...DEFAULT_CONFIGS, minSwapRate: new anchor.BN(0), maxSwapRate: new anchor.BN(0) });
+    });
+
+    it("Should widen the conversion price with the oracle confidence", async () => {
+        await updateConfigsAndVerify(program, { ...DEFAULT_CONFIGS, confidenceWideningBps: 10_000 });
+
+        // A 20% confidence interval prices from the upper bound.
+        const oraclePriceData = await getOraclePriceDataFor(20, Math.floor(Date.now() / 1000), ORACLE_KEYPAIR, AttestationVersion.V3, 4);
+        await getConversionPriceAndVerify(program, oraclePriceData);
+
+        // Revert: Disable the widening.
+        await updateConfigsAndVerify(program, { ...DEFAULT_CONFIGS, confidenceWideningBps: 0 });
+    });
+
+    it("Should fail to get conversion price for a confidence wider than the maximum", async () => {
+        await updateConfigsAndVerify(program, { ...DEFAULT_CONFIGS, maxConfidenceBps: 1 * BPS });
+
+        const timestamp = Math.floor(Date.now() / 1000);
+        const wideConfidence = await getOraclePriceDataFor(20, timestamp, ORACLE_KEYPAIR, AttestationVersion.V3, 0.5);
+        await getConversionPriceToFail(program, wideConfidence, ErrorMsg.ORACLE_CONFIDENCE_TOO_WIDE);
+
+        // Attestations without a signed confidence are refused while the threshold is set.
+        const withoutConfidence = await getOraclePriceDataFor(20, timestamp);
+        await getConversionPriceToFail(program, withoutConfidence, ErrorMsg.ATTESTATION_VERSION_DISABLED);
+
+        await getConversionPriceAndVerify(
+            program,
+            await getOraclePriceDataFor(20, timestamp, ORACLE_KEYPAIR, AttestationVersion.V3, 0.1)
+        );
+
+        // Revert: Disable the threshold.
+        await updateConfigsAndVerify(program, { ...DEFAULT_CONFIGS, maxConfidenceBps: 0 });
+    });
+
+    it("Should fail to get conversion price for a v2 attestation with an unsigned confidence", async () => {
+        const oraclePriceData = await getOraclePriceData();
+        oraclePriceData.confidence = 1;
+
+        await getConversionPriceToFail(program, oraclePriceData, ErrorMsg.ATTESTATION_NOT_AUTHENTIC);
+    });
+
+    it("Should get valid conversion price for a v1 attestation during migration", async () => {
+        const oraclePriceData = await getOraclePriceData(ORACLE_KEYPAIR, AttestationVersion.V1);
+        await getConversionPriceAndVerify(program, oraclePriceData);
+    });
+
+    it("Should fail to get conversion price for a v1 attestation once disabled", async () => {
+        await updateConfigsAndVerify(program, { ...DEFAULT_CONFIGS, disableV1Attestations: true });
+
+        const oraclePriceData = await getOraclePriceData(ORACLE_KEYPAIR, AttestationVersion.V1);
+        await getConversionPriceToFail(program, oraclePriceData, ErrorMsg.ATTESTATION_VERSION_DISABLED);
+
+        // v2 attestations keep working.
+        await getConversionPriceAndVerify(program, await getOraclePriceData());
+
+        // Revert: Re-enable v1 attestations.
+        await updateConfigsAndVerify(program, { ...DEFAULT_CONFIGS, disableV1Attestations: false });
+    });
+
+    it("Should fail to get conversion price for a v1 signature presented as v2", async () => {
+        const oraclePriceData = await getOraclePriceData(ORACLE_KEYPAIR, AttestationVersion.V1);
+        oraclePriceData.version = AttestationVersion.V2;
+
+        await getConversionPriceToFail(program, oraclePriceData, ErrorMsg.ATTESTATION_NOT_AUTHENTIC);
+    });
+
+    it("Should fail to get conversion price for an unsupported attestation version", async () => {
+        const oraclePriceData = await getOraclePriceData();
+        oraclePriceData.version = 3;
+
+        await getConversionPriceToFail(program, oraclePriceData, ErrorMsg.UNSUPPORTED_ATTESTATION_VERSION);
+    });
+
+    describe("Ed25519 precompile", async () => {
+        it("Should get valid conversion price with a precompile verified attestation", async () => {
+            await getPrecompileConversionPriceAndVerify(program, await getOraclePriceData());
+        });
+
+        it("Should get valid conversion price for a precompile verified v1 attestation", async () => {
+            const oraclePriceData = await getOraclePriceData(ORACLE_KEYPAIR, AttestationVersion.V1);
+            await getPrecompileConversionPriceAndVerify(program, oraclePriceData);
+        });
+
+        it("Should fail to get conversion price without the precompile instruction", async () => {
+            await getPrecompileConversionPriceToFail(
+                program,
+                await getOraclePriceData(),
+                ErrorMsg.INVALID_ED25519_INSTRUCTION,
+                []
+            );
+        });
+
+        it("Should fail to get conversion price when the precompile signer is not the oracle", async () => {
+            const otherSigner = Keypair.generate();
+            const oraclePriceData = await getOraclePriceData(otherSigner);
+
+            await getPrecompileConversionPriceToFail(
+                program,
+                oraclePriceData,
+                ErrorMsg.INVALID_ORACLE_PUBLIC_KEY,
+                [getEd25519Instruction(oraclePriceData, otherSigner.publicKey)]
+            );
+        });
+
+        it("Should fail to get conversion price when the precompile message does not match", async () => {
+            const oraclePriceData = await getOraclePriceData();
+            const ed25519Instruction = getEd25519Instruction(oraclePriceData);
+            oraclePriceData.swapRate += 1;
+
+            await getPrecompileConversionPriceToFail(
+                program,
+                oraclePriceData,
+                ErrorMsg.ATTESTATION_NOT_AUTHENTIC,
+                [ed25519Instruction]
+            );
+        });
+
+        it("Should fail to get conversion price for stale precompile verified oracle data", async () => {
+            const timestamp = Math.floor(Date.now() / 1000) - DEFAULT_CONFIGS.priceMaximumAge.toNumber() - 10;
+            const oraclePriceData = await getOraclePriceDataFor(21, timestamp);
+
+            await getPrecompileConversionPriceToFail(program, oraclePriceData, ErrorMsg.STALE_PRICE);
+        });
+    });
+
+    describe("Oracle quorum", async () => {
+        const oracles = [Keypair.generate(), Keypair.generate(), Keypair.generate()];
+
+        before("Configure a 2 of 3 oracle quorum", async () => {
+            await updateConfigsAndVerify(program, {
+                ...DEFAULT_CONFIGS,
+                oraclePubkeys: oracles.map((oracle) => oracle.publicKey),
+                oracleQuorumThreshold: 2,
+            });
+        });
+
+        after("Disable the oracle quorum", async () => {
+            await updateConfigsAndVerify(program, {
+                ...DEFAULT_CONFIGS,
+                oraclePubkeys: [],
+                oracleQuorumThreshold: 0,
+            });
+        });
+
+        it("Should price from the median of the quorum swap rates", async () => {
+            const quorumPriceData = await getQuorumPriceDataFor(oracles, [19, 21, 40]);
+            await getQuorumConversionPriceAndVerify(program, quorumPriceData, 21 * TOKEN_UNITS);
+        });
+
+        it("Should price from the average of the middle rates for an even quorum", async () => {
+            const quorumPriceData = await getQuorumPriceDataFor(oracles.slice(0, 2), [19, 21]);
+            await getQuorumConversionPriceAndVerify(program, quorumPriceData, 20 * TOKEN_UNITS);
+        });
+
+        it("Should fail to get conversion price with a single oracle attestation", async () => {
+            await getConversionPriceToFail(program, await getOraclePriceData(), ErrorMsg.ORACLE_QUORUM_REQUIRED);
+        });
+
+        it("Should fail to get conversion price with a precompile verified attestation", async () => {
+            await getPrecompileConversionPriceToFail(
+                program,
+                await getOraclePriceData(),
+                ErrorMsg.ORACLE_QUORUM_REQUIRED
+            );
+        });
+
+        it("Should fail to get conversion price below the quorum threshold", async () => {
+            const quorumPriceData = await getQuorumPriceDataFor(oracles.slice(0, 1), [20]);
+            await getQuorumConversionPriceToFail(program, quorumPriceData, ErrorMsg.INSUFFICIENT_ORACLE_QUORUM);
+        });
+
+        it("Should fail to get conversion price with duplicate oracle attestations", async () => {
+            const quorumPriceData = await getQuorumPriceDataFor([oracles[0], oracles[0]], [20, 21], undefined, [0, 0]);
+            await getQuorumConversionPriceToFail(program, quorumPriceData, ErrorMsg.DUPLICATE_ORACLE_ATTESTATION);
+        });
+
+        it("Should fail to get conversion price with an oracle index outside the oracle set", async () => {
+            const quorumPriceData = await getQuorumPriceDataFor(
+                [oracles[0], Keypair.generate()],
+                [20, 21],
+                undefined,
+                [0, oracles.length]
+            );
+            await getQuorumConversionPriceToFail(program, quorumPriceData, ErrorMsg.INVALID_ORACLE_PUBLIC_KEY);
+        });
+
+        it("Should fail to get conversion price with an unknown oracle signing for a member", async () => {
+            const quorumPriceData = await getQuorumPriceDataFor([oracles[0], Keypair.generate()], [20, 21]);
+            await getQuorumConversionPriceToFail(program, quorumPriceData, ErrorMsg.ATTESTATION_NOT_AUTHENTIC);
+        });
+
+        it("Should fail to get conversion price with a forged quorum attestation", async () => {
+            const quorumPriceData = await getQuorumPriceDataFor(oracles.slice(0, 2), [20, 21]);
+            quorumPriceData.attestations[1].swapRate += 1;
+            await getQuorumConversionPriceToFail(program, quorumPriceData, ErrorMsg.ATTESTATION_NOT_AUTHENTIC);
+        });
+    });
+
+    describe("Posted oracle price", async () => {
+        after("Restore the default configuration", async () => {
+            await updateConfigsAndVerify(program, DEFAULT_CONFIGS);
+        });
+
+        it("Should get conversion price from the posted price", async () => {
+            const postedPrice = await postPriceAndVerify(program, 20, await nextPostTimestamp(program));
+            await getPostedConversionPriceAndVerify(program, postedPrice);
+        });
+
+        it("Should widen the conversion price with the posted confidence", async () => {
+            await updateConfigsAndVerify(program, { ...DEFAULT_CONFIGS, confidenceWideningBps: 10_000 });
+
+            const postedPrice = await postPriceAndVerify(program, 20, await nextPostTimestamp(program), ORACLE_KEYPAIR, 4);
+            await getPostedConversionPriceAndVerify(program, postedPrice);
+
+            // Revert: Disable the widening.
+            await updateConfigsAndVerify(program, { ...DEFAULT_CONFIGS, confidenceWideningBps: 0 });
+        });
+
+        it("Should fail to get conversion price without the oracle price account", async () => {
+            await getPostedConversionPriceToFail(program, ErrorMsg.ORACLE_PRICE_ACCOUNT_MISSING, false);
+        });
+
+        it("Should fail to post a price from a key other than the oracle", async () => {
+            await postPriceToFail(
+                program,
+                20,
+                await nextPostTimestamp(program),
+                ErrorMsg.INVALID_ORACLE_PUBLIC_KEY,
+                Keypair.generate()
+            );
+        });
+
+        it("Should fail to post a price older than the posted one", async () => {
+            const {timestamp} = await fetchPostedPrice(program);
+            await postPriceToFail(program, 20, timestamp.toNumber(), ErrorMsg.POSTED_PRICE_OUT_OF_ORDER);
+        });
+
+        it("Should fail to get conversion price from a stale posted price", async () => {
+            await postPriceAndVerify(program, 20, await nextPostTimestamp(program));
+            await updateConfigsAndVerify(program, { ...DEFAULT_CONFIGS, priceMaximumAge: new anchor.BN(1) });
+            await delay(3000);
+
+            await getPostedConversionPriceToFail(program, ErrorMsg.STALE_PRICE);
+
+            // Revert: Back to the default price maximum age.
+            await updateConfigsAndVerify(program, DEFAULT_CONFIGS);
+        });
+
+        it("Should fail to get conversion price from the posted price once a quorum is configured", async () => {
+            await postPriceAndVerify(program, 20, await nextPostTimestamp(program));
+            await updateConfigsAndVerify(program, {
+                ...DEFAULT_CONFIGS,
+                oraclePubkeys: [ORACLE_KEYPAIR.publicKey, Keypair.generate().publicKey],
+                oracleQuorumThreshold: 2,
+            });
+
+            await getPostedConversionPriceToFail(program, ErrorMsg.ORACLE_QUORUM_REQUIRED);
+
+            // Revert: Disable the oracle quorum.
+            await updateConfigsAndVerify(program, { ...DEFAULT_CONFIGS, oraclePubkeys: [], oracleQuorumThreshold: 0 });
+        });
+    });
+
+    describe("Oracle key rotation", async () => {
+        const getChainTimestamp = async () => {
+            const slot = await program.provider.connection.getSlot();
+            return await program.provider.connection.getBlockTime(slot);
+        };
+
+        afterEach("Cancel the rotation and restore the default oracle", async () => {
+            // Switching to an unrelated key clears any scheduled rotation.
+            await updateConfigsAndVerify(program, {
+                ...DEFAULT_CONFIGS,
+                oraclePubkey: Keypair.generate().publicKey,
+            });
+            await updateConfigsAndVerify(program, DEFAULT_CONFIGS);
+        });
+
+        it("Should accept attestations from both keys during the overlap", async () => {
+            const nextOracle = Keypair.generate();
+            const now = await getChainTimestamp();
+            await rotateOracleKeyAndVerify(program, nextOracle.publicKey, now - 10, now + 300);
+
+            await getConversionPriceAndVerify(program, await getOraclePriceData());
+            await getConversionPriceAndVerify(program, await getOraclePriceData(nextOracle));
+        });
+
+        it("Should reject the next key before activation", async () => {
+            const nextOracle = Keypair.generate();
+            const now = await getChainTimestamp();
+            await rotateOracleKeyAndVerify(program, nextOracle.publicKey, now + 300, now + 600);
+
+            await getConversionPriceAndVerify(program, await getOraclePriceData());
+            await getConversionPriceToFail(
+                program,
+                await getOraclePriceData(nextOracle),
+                ErrorMsg.ATTESTATION_NOT_AUTHENTIC
+            );
+        });
+
+        it("Should reject the old key after retirement", async () => {
+            const nextOracle = Keypair.generate();
+            const now = await getChainTimestamp();
+            await rotateOracleKeyAndVerify(program, nextOracle.publicKey, now - 10, now + 2);
+
+            // Wait for the old key to retire.
+            await new Promise(resolve => setTimeout(resolve, 5000));
+
+            await getConversionPriceAndVerify(program, await getOraclePriceData(nextOracle));
+            await getConversionPriceToFail(
+                program,
+                await getOraclePriceData(),
+                ErrorMsg.ATTESTATION_NOT_AUTHENTIC
+            );
+        });
+
+        it("Should fail to reschedule a rotation during the overlap", async () => {
+            const now = await getChainTimestamp();
+            await rotateOracleKeyAndVerify(program, Keypair.generate().publicKey, now - 10, now + 300);
+
+            await rotateOracleKeyAndVerifyFail(
+                program,
+                Keypair.generate().publicKey,
+                now,
+                now + 600,
+                ErrorMsg.ORACLE_ROTATION_IN_PROGRESS
+            );
+        });
+
+        it("Should fail to rotate with an invalid window", async () => {
+            const now = await getChainTimestamp();
+            await rotateOracleKeyAndVerifyFail(
+                program,
+                Keypair.generate().publicKey,
+                now + 600,
+                now + 300,
+                ErrorMsg.INVALID_ORACLE_ROTATION_WINDOW
+            );
+        });
+
+        it("Non admin should not be able to rotate the oracle key", async () => {
+            const nonAdmin = await getRandomKeyPair(program.provider.connection);
+            const now = await getChainTimestamp();
+            await rotateOracleKeyAndVerifyFail(
+                program,
+                Keypair.generate().publicKey,
+                now,
+                now + 300,
+                ErrorMsg.UNAUTHORIZED_ADMIN,
+                nonAdmin
+            );
+        });
     });
 });
@@ -6,11 +6,26 @@ export const TOKEN_UNITS = 10 ** 8;
 export const BPS = 100; // basis points
 
 export const MAX_DENY_LIST_SIZE = 310;
+export const MAX_ORACLE_QUORUM_SIZE = 5;
+export const DEFAULT_FILLS_CAPACITY = 130_000;
+
+export const CONVERTER_PROGRAM_ID = new PublicKey("YrQk4TE5Bi6Hsi4u2LbBNwjZUWEaSUaCDJdapJbCE4z");
+export const ATTESTATION_DOMAIN_TAG = "doublezero:sol-2z-converter:attestation";
+export const QUOTE_DIRECTION_2Z_TO_SOL = 0;
+
+export namespace AttestationVersion {
+    export const V1 = 1;
+    export const V2 = 2;
+    export const V3 = 3;
+}
 
 export namespace Seeds {
     export const CONFIGURATION_REGISTRY_SEED = "system_config";
     export const PROGRAM_STATE_SEED = "state";
     export const DENY_LIST_REGISTRY_SEED = "deny_list";
+    export const TRADE_HISTORY_SEED = "trade_history";
+    export const PENDING_CONFIG_CHANGE_SEED = "pending_config";
+    export const ORACLE_PRICE_SEED = "oracle_price";
     export const WITHDRAW_AUTHORITY_SEED = "withdraw_sol";
     export const MOCK_PROTOCOL_TREASURY_SEED = "protocol_treasury";
     export const MOCK_2Z_TOKEN_MINT_SEED = "double_zero_mint";
@@ -18,6 +33,12 @@ export namespace Seeds {
     export const MOCK_REVENUE_DISTRIBUTION_JOURNAL = "jour";
 }
 
+export namespace PauseFlags {
+    export const BUYS = 1 << 0;
+    export const DEQUEUES = 1 << 1;
+    export const QUOTES = 1 << 2;
+}
+
 export namespace Events {
     export const SYSTEM_INITIALIZED = "systemInitialized";
     export const BID_TOO_LOW = "bidTooLowEvent";
@@ -25,7 +46,24 @@ export namespace Events {
     export const SYSTEM_UNHALTED = "systemUnhalted";
     export const TRADE = "tradeEvent";
     export const FILLS_CONSUMED = "fillsDequeued";
+    export const FILLS_CONSUMED_DETAILED = "fillsDequeuedDetailed";
     export const CONFIG_UPDATED = "configChanged";
+    export const ORACLE_KEY_ROTATED = "oracleKeyRotated";
+    export const EPOCH_CAP_REACHED = "epochCapReached";
+    export const GUARDIAN_SET = "guardianSet";
+    export const PAUSE_FLAGS_SET = "pauseFlagsSet";
+    export const FILLS_MIGRATION_STARTED = "fillsMigrationStarted";
+    export const FILLS_REGISTRY_MIGRATED = "fillsRegistryMigrated";
+    export const CONFIG_CHANGE_QUEUED = "configChangeQueued";
+    export const CONFIG_CHANGE_EXECUTED = "configChangeExecuted";
+    export const CONFIG_CHANGE_CANCELLED = "configChangeCancelled";
+    export const ADMIN_TRANSFER_PROPOSED = "adminTransferProposed";
+    export const ADMIN_TRANSFER_CANCELLED = "adminTransferCancelled";
+    export const DENY_LIST_AUTHORITY_TRANSFER_PROPOSED = "denyListAuthorityTransferProposed";
+    export const DENY_LIST_AUTHORITY_TRANSFER_CANCELLED = "denyListAuthorityTransferCancelled";
+    export const ORACLE_RATE_REJECTED = "oracleRateRejected";
+    export const ORACLE_PRICE_POSTED = "oraclePricePosted";
+    export const ACCOUNT_MIGRATED = "accountMigrated";
 }
 
 export namespace ErrorMsg {
@@ -50,6 +88,45 @@ export namespace ErrorMsg {
     export const UNAUTHORIZED_FILLS_CONSUMER = "User is not authorized to do fills consumption";
     export const ADDRESS_ALREADY_IN_USE = "already in use";
     export const INVALID_SYSTEM_STATE = "Invalid system state";
+    export const INVALID_SOL_QUANTITY = "Invalid SOL quantity";
+    export const INVALID_ORACLE_QUORUM = "Invalid oracle quorum configuration";
+    export const INVALID_ORACLE_PUBLIC_KEY = "Oracle public key is invalid";
+    export const ORACLE_QUORUM_NOT_CONFIGURED = "Oracle quorum is not configured";
+    export const ORACLE_QUORUM_REQUIRED = "Oracle quorum attestation is required";
+    export const INSUFFICIENT_ORACLE_QUORUM = "Not enough distinct oracle attestations";
+    export const DUPLICATE_ORACLE_ATTESTATION = "Oracle signed more than one attestation";
+    export const INVALID_ORACLE_ROTATION_WINDOW = "Invalid oracle key rotation window";
+    export const ORACLE_ROTATION_IN_PROGRESS = "Oracle key rotation is already in progress";
+    export const UNSUPPORTED_ATTESTATION_VERSION = "Unsupported attestation version";
+    export const ATTESTATION_VERSION_DISABLED = "Attestation version is disabled";
+    export const ATTESTATION_OUT_OF_ORDER = "Attestation is older than the latest consumed attestation";
+    export const WALLET_TRADE_LIMIT_EXCEEDED = "Wallet reached the maximum number of trades for this epoch";
+    export const WALLET_SOL_LIMIT_EXCEEDED = "Wallet reached the maximum SOL amount for this epoch";
+    export const EPOCH_SOL_CAP_EXCEEDED = "Trade exceeds the SOL sale cap of the epoch";
+    export const NO_PENDING_AUTHORITY_TRANSFER = "No authority transfer is pending";
+    export const UNAUTHORIZED_PENDING_AUTHORITY = "Signer is not the pending authority";
+    export const INVALID_PROPOSED_AUTHORITY = "Proposed authority is invalid";
+    export const UNAUTHORIZED_DENY_LIST_AUTHORITY = "Unauthorized deny list authority";
+    export const FILLS_MIGRATION_IN_PROGRESS = "Fills registry migration is in progress";
+    export const INVALID_DISCOUNT_CURVE = "Invalid discount curve parameters";
+    export const INVALID_FILLS_WATERMARK = "Fills low watermark must be below the high watermark";
+    export const FILLS_REGISTRY_ABOVE_HIGH_WATERMARK = "Fills registry is above its high watermark";
+    export const INVALID_DISCOUNT_MEMORY = "Discount memory must be below 10000 basis points";
+    export const INVALID_SWAP_RATE_BAND = "Swap rate bounds are inverted or the deviation exceeds 10000 basis points";
+    export const ORACLE_RATE_OUT_OF_BAND = "Oracle swap rate is outside the accepted band";
+    export const ORACLE_CONFIDENCE_TOO_WIDE = "Oracle confidence interval is wider than the maximum";
+    export const POSTED_PRICE_OUT_OF_ORDER = "Posted oracle price must be newer than the current one";
+    export const ORACLE_PRICE_ACCOUNT_MISSING = "Oracle price account is required when no attestation is provided";
+    export const ACCOUNT_ALREADY_MIGRATED = "Account already has the current layout";
+    export const ORACLE_ROTATION_NOTICE_TOO_SHORT = "Oracle key rotation has to activate after the config change delay";
+    export const FILLS_REGISTRY_IN_USE = "Fills registry is still in use";
+    export const CONFIG_CHANGE_REQUIRES_TIMELOCK = "Configuration change has to be queued through the timelock";
+    export const CONFIG_CHANGE_NOT_READY = "Queued configuration change is not executable yet";
+    export const UNAUTHORIZED_GUARDIAN = "Signer lacks the guardian or admin authority for this action";
+    export const FEATURE_PAUSED = "Requested feature is paused";
+    export const INVALID_PAUSE_FLAGS = "Invalid pause flags";
+    export const ACCOUNT_NOT_INITIALIZED = "AccountNotInitialized";
+    export const INVALID_ED25519_INSTRUCTION = "Ed25519 signature verification instruction is missing or invalid";
 }
 
 export namespace MockProgramInstructions {
@@ -0,0 +1,151 @@
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { Program } from "@coral-xyz/anchor";
+import { ConverterProgram } from "../../../target/types/converter_program";
+import { fetchProgramState, getDefaultKeyPair } from "../utils/accounts";
+import { assert, expect } from "chai";
+import { findAnchorEventInLogs, getTransactionLogs } from "../utils/return-data";
+import { Events } from "../constants";
+
+export enum AuthorityRole {
+    Admin,
+    DenyListAuthority,
+}
+
+const proposeIx = (program: Program<ConverterProgram>, role: AuthorityRole, newAuthority: PublicKey) =>
+    role === AuthorityRole.Admin
+        ? program.methods.proposeAdmin(newAuthority)
+        : program.methods.proposeDenyListAuthority(newAuthority);
+
+const acceptIx = (program: Program<ConverterProgram>, role: AuthorityRole) =>
+    role === AuthorityRole.Admin
+        ? program.methods.acceptAdmin()
+        : program.methods.acceptDenyListAuthority();
+
+const cancelIx = (program: Program<ConverterProgram>, role: AuthorityRole) =>
+    role === AuthorityRole.Admin
+        ? program.methods.cancelAdminTransfer()
+        : program.methods.cancelDenyListAuthorityTransfer();
+
+const currentAuthorities = async (program: Program<ConverterProgram>, role: AuthorityRole) => {
+    const programState = await fetchProgramState(program);
+    return role === AuthorityRole.Admin
+        ? { current: programState.admin, pending: programState.pendingAdmin }
+        : { current: programState.denyListAuthority, pending: programState.pendingDenyListAuthority };
+}
+
+export const proposeAuthorityAndVerify = async (
+    program: Program<ConverterProgram>,
+    role: AuthorityRole,
+    newAuthority: PublicKey,
+    signer: Keypair = getDefaultKeyPair(),
+) => {
+    const before = await currentAuthorities(program, role);
+    const txSig = await proposeIx(program, role, newAuthority)
+        .accounts({ signer: signer.publicKey })
+        .signers([signer])
+        .rpc();
+
+    const after = await currentAuthorities(program, role);
+    assert.equal(after.pending.toBase58(), newAuthority.toBase58(), "Pending authority should be recorded");
+    assert.equal(after.current.toBase58(), before.current.toBase58(), "Current authority should not change yet");
+
+    const logs = await getTransactionLogs(program.provider, txSig);
+    const eventName = role === AuthorityRole.Admin
+        ? Events.ADMIN_TRANSFER_PROPOSED
+        : Events.DENY_LIST_AUTHORITY_TRANSFER_PROPOSED;
+    const event = await findAnchorEventInLogs(logs, program.idl, eventName);
+    expect(event, "Transfer proposed event should be emitted").to.exist;
+}
+
+export const acceptAuthorityAndVerify = async (
+    program: Program<ConverterProgram>,
+    role: AuthorityRole,
+    signer: Keypair,
+) => {
+    await acceptIx(program, role)
+        .accounts({ signer: signer.publicKey })
+        .signers([signer])
+        .rpc();
+
+    const after = await currentAuthorities(program, role);
+    assert.equal(after.current.toBase58(), signer.publicKey.toBase58(), "Pending authority should take over");
+    assert.equal(after.pending.toBase58(), PublicKey.default.toBase58(), "Pending authority should be cleared");
+}
+
+export const cancelAuthorityTransferAndVerify = async (
+    program: Program<ConverterProgram>,
+    role: AuthorityRole,
+    signer: Keypair = getDefaultKeyPair(),
+) => {
+    const before = await currentAuthorities(program, role);
+    const txSig = await cancelIx(program, role)
+        .accounts({ signer: signer.publicKey })
+        .signers([signer])
+        .rpc();
+
+    const after = await currentAuthorities(program, role);
+    assert.equal(after.pending.toBase58(), PublicKey.default.toBase58(), "Pending authority should be cleared");
+    assert.equal(after.current.toBase58(), before.current.toBase58(), "Current authority should not change");
+
+    const logs = await getTransactionLogs(program.provider, txSig);
+    const eventName = role === AuthorityRole.Admin
+        ? Events.ADMIN_TRANSFER_CANCELLED
+        : Events.DENY_LIST_AUTHORITY_TRANSFER_CANCELLED;
+    const event = await findAnchorEventInLogs(logs, program.idl, eventName);
+    expect(event, "Transfer cancelled event should be emitted").to.exist;
+}
+
+export const proposeAuthorityShouldFail = async (
+    program: Program<ConverterProgram>,
+    role: AuthorityRole,
+    newAuthority: PublicKey,
+    signer: Keypair,
+    expectedError: string,
+) => {
+    try {
+        await proposeIx(program, role, newAuthority)
+            .accounts({ signer: signer.publicKey })
+            .signers([signer])
+            .rpc();
+    } catch (e) {
+        expect((new Error(e!.toString())).message).to.include(expectedError);
+        return;
+    }
+    assert.fail("It was able to propose authority");
+}
+
+export const acceptAuthorityShouldFail = async (
+    program: Program<ConverterProgram>,
+    role: AuthorityRole,
+    signer: Keypair,
+    expectedError: string,
+) => {
+    try {
+        await acceptIx(program, role)
+            .accounts({ signer: signer.publicKey })
+            .signers([signer])
+            .rpc();
+    } catch (e) {
+        expect((new Error(e!.toString())).message).to.include(expectedError);
+        return;
+    }
+    assert.fail("It was able to accept authority transfer");
+}
+
+export const cancelAuthorityTransferShouldFail = async (
+    program: Program<ConverterProgram>,
+    role: AuthorityRole,
+    signer: Keypair,
+    expectedError: string,
+) => {
+    try {
+        await cancelIx(program, role)
+            .accounts({ signer: signer.publicKey })
+            .signers([signer])
+            .rpc();
+    } catch (e) {
+        expect((new Error(e!.toString())).message).to.include(expectedError);
+        return;
+    }
+    assert.fail("It was able to cancel authority transfer");
+}
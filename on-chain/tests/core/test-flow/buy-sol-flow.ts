@@ -1,12 +1,20 @@
-import {getMockProgramPDAs} from "../utils/pda-helper";
+import {getMockProgramPDAs, getOraclePricePDA} from "../utils/pda-helper";
 import {assert, expect} from "chai";
 import {Keypair, LAMPORTS_PER_SOL, PublicKey, TransactionInstruction, Transaction} from "@solana/web3.js";
-import {BN, Program} from "@coral-xyz/anchor";
+import {Program} from "@coral-xyz/anchor";
 import { ConverterProgram } from "../../../target/types/converter_program";
 import {getTokenBalance} from "../utils/token-utils";
 import * as anchor from "@coral-xyz/anchor";
 import {TOKEN_PROGRAM_ID} from "@solana/spl-token";
-import {getOraclePriceData, OraclePriceData} from "../utils/price-oracle";
+import {
+    getEd25519Instruction,
+    getOraclePriceData,
+    OraclePriceData,
+    QuorumPriceData,
+    toOraclePriceInput,
+    toOraclePriceMessageInput,
+    toQuorumInput
+} from "../utils/price-oracle";
 import {DEFAULT_CONFIGS} from "../utils/configuration-registry";
 import {Fill, FillsRegistry, getFillsRegistryAccount, getFillsRegistryAccountAddress} from "../utils/fills-registry";
 import {getConversionPriceAndVerify} from "./conversion-price";
@@ -23,6 +31,7 @@ export async function buySolAndVerify(
     signer: Keypair,
     oraclePriceData: OraclePriceData,
     currentConfigs = DEFAULT_CONFIGS,
+    solAmount: number = Number(currentConfigs.solQuantity),
 ) {
     const connection = program.provider.connection;
     const pdaList = getMockProgramPDAs();
@@ -41,7 +50,9 @@ export async function buySolAndVerify(
             senderTokenAccount,
             bidPrice,
             signer,
-            oraclePriceData
+            oraclePriceData,
+            MOCK_TRANSFER_PROGRAM,
+            solAmount
         )
         const tx: Transaction = new anchor.web3.Transaction().add(ix);
         txSig = await program.provider.sendAndConfirm(tx, [signer]);
@@ -50,9 +61,9 @@ export async function buySolAndVerify(
         assert.fail("Buy Sol  failed");
     }
 
-    const tokenChange = (BigInt(Number(currentConfigs.solQuantity)) * BigInt(askPrice)) / BigInt(LAMPORTS_PER_SOL);
+    const tokenChange = (BigInt(solAmount) * BigInt(askPrice)) / BigInt(LAMPORTS_PER_SOL);
     const tokenBalanceChange = Number(tokenChange);
-    const solBalanceChange = Number(currentConfigs.solQuantity);
+    const solBalanceChange = solAmount;
 
     const tokenBalanceAfter = await getTokenBalance(program.provider.connection, senderTokenAccount);
     const solBalanceAfter = await program.provider.connection.getBalance(signer.publicKey);
@@ -65,6 +76,11 @@ export async function buySolAndVerify(
     const logs = await getTransactionLogs(program.provider, txSig);
     const event = await findAnchorEventInLogs(logs, program.idl, Events.TRADE);
     expect(event, "Trade event should be emitted").to.exist;
+    assert.equal(event.data.attestationTimestamp.toNumber(), oraclePriceData.timestamp);
+
+    // The consumed attestation becomes the reference for the next trades.
+    const {lastAttestationTimestamp} = await fetchProgramState(program);
+    assert.isAtLeast(lastAttestationTimestamp.toNumber(), oraclePriceData.timestamp);
 
     assert.approximately(
         tokenBalanceBefore - tokenBalanceAfter,
@@ -100,6 +116,13 @@ export async function buySolAndVerify(
     const fillEntry: Fill = fillsRegistryAfter.fills.slice(-1)[0];
     assert.equal(fillEntry.solIn, solBalanceChange);
     assert.approximately(fillEntry.token2ZOut, tokenBalanceChange, 10 ** 4);
+    assert.equal(fillEntry.buyer.toBase58(), signer.publicKey.toBase58(), "Fill should record the buyer");
+    assert.equal(fillEntry.slot, lastTradedSlotAfter, "Fill should record the trade slot");
+    assert.equal(fillEntry.timestamp, event.data.timestamp.toNumber(), "Fill should record the trade time");
+    assert.approximately(fillEntry.askPrice, askPrice, askPrice / 100, "Fill should record the ask price");
+    assert.equal(fillEntry.sequence, fillsRegistryBefore.nextFillSequence, "Fill should take the next sequence number");
+    assert.equal(fillsRegistryAfter.nextFillSequence, fillsRegistryBefore.nextFillSequence + 1);
+    assert.equal(event.data.fillSequence.toNumber(), fillEntry.sequence, "Trade event should carry the fill sequence");
 
     // Ensure that we can trade in the next slot.
     await new Promise(resolve => setTimeout(resolve, 400));
@@ -112,7 +135,8 @@ export async function buySolFail(
     signer: Keypair,
     oraclePriceData: OraclePriceData,
     expectedError: string,
-    expectedEvent: string = ""
+    expectedEvent: string = "",
+    solAmount: number = Number(DEFAULT_CONFIGS.solQuantity)
 ) {
     try {
         const ix: TransactionInstruction = await prepareBuySolInstruction(
@@ -120,7 +144,9 @@ export async function buySolFail(
             senderTokenAccount,
             bidPrice,
             signer,
-            oraclePriceData
+            oraclePriceData,
+            MOCK_TRANSFER_PROGRAM,
+            solAmount
         )
         const tx: Transaction = new anchor.web3.Transaction().add(ix);
         await program.provider.sendAndConfirm(tx, [signer]);
@@ -142,17 +168,15 @@ export async function prepareBuySolInstruction(
     bidPrice: number,
     signer: Keypair,
     oraclePriceData: OraclePriceData,
-    revenueDistributionProgram = MOCK_TRANSFER_PROGRAM
+    revenueDistributionProgram = MOCK_TRANSFER_PROGRAM,
+    solAmount: number = Number(DEFAULT_CONFIGS.solQuantity)
 ): Promise<TransactionInstruction> {
     const mockProgramPDAs = getMockProgramPDAs();
     const fillsRegistryAddress: PublicKey = await getFillsRegistryAccountAddress(program);
     return await program.methods.buySol(
         new anchor.BN(bidPrice),
-        {
-            swapRate: new BN(oraclePriceData.swapRate),
-            timestamp: new BN(oraclePriceData.timestamp),
-            signature: oraclePriceData.signature,
-        }
+        new anchor.BN(solAmount),
+        toOraclePriceInput(oraclePriceData)
     )
         .accountsPartial({
             fillsRegistry: fillsRegistryAddress,
@@ -169,6 +193,100 @@ export async function prepareBuySolInstruction(
         .instruction()
 }
 
+/// Prepares the Ed25519 precompile instruction followed by buy_sol_with_precompile.
+export async function prepareBuySolWithPrecompileInstructions(
+    program: Program<ConverterProgram>,
+    senderTokenAccount: PublicKey,
+    bidPrice: number,
+    signer: Keypair,
+    oraclePriceData: OraclePriceData,
+    preInstructions: TransactionInstruction[] = [getEd25519Instruction(oraclePriceData)],
+    solAmount: number = Number(DEFAULT_CONFIGS.solQuantity)
+): Promise<TransactionInstruction[]> {
+    const mockProgramPDAs = getMockProgramPDAs();
+    const fillsRegistryAddress: PublicKey = await getFillsRegistryAccountAddress(program);
+    const ix = await program.methods.buySolWithPrecompile(
+        new anchor.BN(bidPrice),
+        new anchor.BN(solAmount),
+        toOraclePriceMessageInput(oraclePriceData)
+    )
+        .accountsPartial({
+            fillsRegistry: fillsRegistryAddress,
+            userTokenAccount: senderTokenAccount,
+            protocolTreasuryTokenAccount: mockProgramPDAs.protocolTreasury,
+            doubleZeroMint: mockProgramPDAs.tokenMint,
+            programConfig: mockProgramPDAs.config,
+            journal: mockProgramPDAs.journal,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            revenueDistributionProgram: MOCK_TRANSFER_PROGRAM,
+            signer: signer.publicKey
+        })
+        .signers([signer])
+        .instruction();
+    return [...preInstructions, ix];
+}
+
+/// Prepares buy_sol_with_quorum, priced from the median of the quorum attestations.
+export async function prepareBuySolWithQuorumInstruction(
+    program: Program<ConverterProgram>,
+    senderTokenAccount: PublicKey,
+    bidPrice: number,
+    signer: Keypair,
+    quorumPriceData: QuorumPriceData,
+    solAmount: number = Number(DEFAULT_CONFIGS.solQuantity)
+): Promise<TransactionInstruction> {
+    const mockProgramPDAs = getMockProgramPDAs();
+    const fillsRegistryAddress: PublicKey = await getFillsRegistryAccountAddress(program);
+    return await program.methods.buySolWithQuorum(
+        new anchor.BN(bidPrice),
+        new anchor.BN(solAmount),
+        toQuorumInput(quorumPriceData)
+    )
+        .accountsPartial({
+            fillsRegistry: fillsRegistryAddress,
+            userTokenAccount: senderTokenAccount,
+            protocolTreasuryTokenAccount: mockProgramPDAs.protocolTreasury,
+            doubleZeroMint: mockProgramPDAs.tokenMint,
+            programConfig: mockProgramPDAs.config,
+            journal: mockProgramPDAs.journal,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            revenueDistributionProgram: MOCK_TRANSFER_PROGRAM,
+            signer: signer.publicKey
+        })
+        .signers([signer])
+        .instruction();
+}
+
+/// Prepares buy_sol_with_posted_price, priced from the oracle price account.
+export async function prepareBuySolWithPostedPriceInstruction(
+    program: Program<ConverterProgram>,
+    senderTokenAccount: PublicKey,
+    bidPrice: number,
+    signer: Keypair,
+    solAmount: number = Number(DEFAULT_CONFIGS.solQuantity)
+): Promise<TransactionInstruction> {
+    const mockProgramPDAs = getMockProgramPDAs();
+    const fillsRegistryAddress: PublicKey = await getFillsRegistryAccountAddress(program);
+    return await program.methods.buySolWithPostedPrice(
+        new anchor.BN(bidPrice),
+        new anchor.BN(solAmount)
+    )
+        .accountsPartial({
+            fillsRegistry: fillsRegistryAddress,
+            userTokenAccount: senderTokenAccount,
+            protocolTreasuryTokenAccount: mockProgramPDAs.protocolTreasury,
+            doubleZeroMint: mockProgramPDAs.tokenMint,
+            programConfig: mockProgramPDAs.config,
+            journal: mockProgramPDAs.journal,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            revenueDistributionProgram: MOCK_TRANSFER_PROGRAM,
+            signer: signer.publicKey,
+            oraclePrice: getOraclePricePDA(program.programId)
+        })
+        .signers([signer])
+        .instruction();
+}
+
 /// Prepares success scenario and Calls buySolAndVerify.
 /// gets Oracle Price and set the bid price based on bidFactor.
 /// Mints sufficient 2Z to user and airdrops necessary SOL to journal.
@@ -1,8 +1,13 @@
-import {Keypair} from "@solana/web3.js";
+import {Keypair, PublicKey} from "@solana/web3.js";
 import { accountExists, getDefaultKeyPair } from "../utils/accounts"
 import { DEFAULT_CONFIGS, fetchCurrentConfiguration, SystemConfig } from "../utils/configuration-registry";
-import { getConfigurationRegistryPDA, getDenyListRegistryPDA, getProgramStatePDA } from "../utils/pda-helper";
-import { Program } from "@coral-xyz/anchor";
+import {
+    getConfigurationRegistryPDA,
+    getDenyListRegistryPDA,
+    getPendingConfigChangePDA,
+    getProgramStatePDA
+} from "../utils/pda-helper";
+import { BN, Program } from "@coral-xyz/anchor";
 import { assert, expect } from "chai";
 import { ConverterProgram } from "../../../target/types/converter_program";
 import {findAnchorEventInLogs, getTransactionLogs} from "../utils/return-data";
@@ -52,6 +57,8 @@ export const updateConfigsAndVerify = async (
     assert.equal(updatedConfig.coefficient.toString(), input.coefficient.toString());
     assert.equal(updatedConfig.maxDiscountRate.toString(), input.maxDiscountRate.toString());
     assert.equal(updatedConfig.minDiscountRate.toString(), input.minDiscountRate.toString());
+    assert.equal(updatedConfig.minSolQuantity.toString(), input.minSolQuantity.toString());
+    assert.equal(updatedConfig.maxSolQuantity.toString(), input.maxSolQuantity.toString());
 }
 
 export const updateConfigsAndVerifyFail = async (
@@ -87,4 +94,259 @@ export const updateConfigsAndVerifyFail = async (
         return; // Exit early — test passes.
     }
     assert.fail("It was able to update config");
-}
\ No newline at end of file
+}
+
+export const rotateOracleKeyAndVerify = async (
+    program: Program<ConverterProgram>,
+    nextOraclePubkey: PublicKey,
+    activationTimestamp: number,
+    retireTimestamp: number,
+    adminKeypair: Keypair = getDefaultKeyPair(),
+) => {
+    const txSig = await program.methods.rotateOracleKey(
+        nextOraclePubkey,
+        new BN(activationTimestamp),
+        new BN(retireTimestamp)
+    )
+        .accounts({
+            admin: adminKeypair.publicKey,
+        })
+        .signers([adminKeypair])
+        .rpc();
+
+    // assert whether event has been emitted or not
+    const logs = await getTransactionLogs(program.provider, txSig);
+    const event = await findAnchorEventInLogs(logs, program.idl, Events.ORACLE_KEY_ROTATED);
+    expect(event, "Oracle key rotated event should be emitted").to.exist;
+
+    const config = await program.account.configurationRegistry.fetch(getConfigurationRegistryPDA(program.programId));
+    assert.equal(config.nextOraclePubkey.toString(), nextOraclePubkey.toString());
+    assert.equal(config.nextOracleActivationTimestamp.toNumber(), activationTimestamp);
+    assert.equal(config.oracleRetireTimestamp.toNumber(), retireTimestamp);
+}
+
+export const rotateOracleKeyAndVerifyFail = async (
+    program: Program<ConverterProgram>,
+    nextOraclePubkey: PublicKey,
+    activationTimestamp: number,
+    retireTimestamp: number,
+    expectedError: string,
+    adminKeypair: Keypair = getDefaultKeyPair(),
+) => {
+    try {
+        await program.methods.rotateOracleKey(
+            nextOraclePubkey,
+            new BN(activationTimestamp),
+            new BN(retireTimestamp)
+        )
+            .accounts({
+                admin: adminKeypair.publicKey,
+            })
+            .signers([adminKeypair])
+            .rpc();
+    } catch (e) {
+        expect(e!.toString()).to.include(expectedError);
+        return;
+    }
+    assert.fail("It was able to rotate the oracle key");
+}
+
+export const queueConfigChangeAndVerify = async (
+    program: Program<ConverterProgram>,
+    input: SystemConfig,
+    adminKeypair: Keypair = getDefaultKeyPair(),
+): Promise<number> => {
+    const configBefore = await program.account.configurationRegistry.fetch(getConfigurationRegistryPDA(program.programId));
+    const changeId = configBefore.nextConfigChangeId.toNumber();
+
+    const txSig = await program.methods.queueConfigChange(input)
+        .accountsPartial({
+            pendingConfigChange: getPendingConfigChangePDA(program.programId, changeId),
+            admin: adminKeypair.publicKey,
+        })
+        .signers([adminKeypair])
+        .rpc();
+
+    // assert whether event has been emitted or not
+    const logs = await getTransactionLogs(program.provider, txSig);
+    const event = await findAnchorEventInLogs(logs, program.idl, Events.CONFIG_CHANGE_QUEUED);
+    expect(event, "Config change queued event should be emitted").to.exist;
+
+    const pendingChange = await program.account.pendingConfigChange.fetch(
+        getPendingConfigChangePDA(program.programId, changeId)
+    );
+    assert.equal(pendingChange.changeId.toNumber(), changeId);
+    assert.equal(pendingChange.queuedBy.toString(), adminKeypair.publicKey.toString());
+
+    // Queueing alone must not touch the registry.
+    const configAfter = await program.account.configurationRegistry.fetch(getConfigurationRegistryPDA(program.programId));
+    assert.equal(configAfter.coefficient.toString(), configBefore.coefficient.toString());
+    assert.equal(configAfter.nextConfigChangeId.toNumber(), changeId + 1);
+    return changeId;
+}
+
+export const queueConfigChangeAndVerifyFail = async (
+    program: Program<ConverterProgram>,
+    input: SystemConfig,
+    expectedError: string,
+    adminKeypair: Keypair = getDefaultKeyPair(),
+) => {
+    const config = await program.account.configurationRegistry.fetch(getConfigurationRegistryPDA(program.programId));
+    try {
+        await program.methods.queueConfigChange(input)
+            .accountsPartial({
+                pendingConfigChange: getPendingConfigChangePDA(program.programId, config.nextConfigChangeId.toNumber()),
+                admin: adminKeypair.publicKey,
+            })
+            .signers([adminKeypair])
+            .rpc();
+    } catch (e) {
+        expect(e!.toString()).to.include(expectedError);
+        return;
+    }
+    assert.fail("It was able to queue the config change");
+}
+
+export const executeConfigChangeAndVerify = async (
+    program: Program<ConverterProgram>,
+    changeId: number,
+    signer: Keypair = getDefaultKeyPair(),
+) => {
+    const pendingConfigChangePda = getPendingConfigChangePDA(program.programId, changeId);
+    const pendingChange = await program.account.pendingConfigChange.fetch(pendingConfigChangePda);
+
+    const txSig = await program.methods.executeConfigChange(new BN(changeId))
+        .accountsPartial({
+            pendingConfigChange: pendingConfigChangePda,
+            queuedBy: pendingChange.queuedBy,
+            signer: signer.publicKey,
+        })
+        .signers([signer])
+        .rpc();
+
+    // assert whether events have been emitted or not
+    const logs = await getTransactionLogs(program.provider, txSig);
+    const executedEvent = await findAnchorEventInLogs(logs, program.idl, Events.CONFIG_CHANGE_EXECUTED);
+    expect(executedEvent, "Config change executed event should be emitted").to.exist;
+    const changedEvent = await findAnchorEventInLogs(logs, program.idl, Events.CONFIG_UPDATED);
+    expect(changedEvent, "Config changed event should be emitted").to.exist;
+
+    assert.isFalse(
+        await accountExists(program.provider.connection, pendingConfigChangePda),
+        "Executed config change should be closed"
+    );
+}
+
+export const executeConfigChangeAndVerifyFail = async (
+    program: Program<ConverterProgram>,
+    changeId: number,
+    expectedError: string,
+    signer: Keypair = getDefaultKeyPair(),
+) => {
+    const pendingConfigChangePda = getPendingConfigChangePDA(program.programId, changeId);
+    try {
+        const pendingChange = await program.account.pendingConfigChange.fetchNullable(pendingConfigChangePda);
+        await program.methods.executeConfigChange(new BN(changeId))
+            .accountsPartial({
+                pendingConfigChange: pendingConfigChangePda,
+                queuedBy: pendingChange?.queuedBy ?? signer.publicKey,
+                signer: signer.publicKey,
+            })
+            .signers([signer])
+            .rpc();
+    } catch (e) {
+        expect(e!.toString()).to.include(expectedError);
+        return;
+    }
+    assert.fail("It was able to execute the config change");
+}
+
+export const cancelConfigChangeAndVerify = async (
+    program: Program<ConverterProgram>,
+    changeId: number,
+    adminKeypair: Keypair = getDefaultKeyPair(),
+) => {
+    const pendingConfigChangePda = getPendingConfigChangePDA(program.programId, changeId);
+    const pendingChange = await program.account.pendingConfigChange.fetch(pendingConfigChangePda);
+
+    const txSig = await program.methods.cancelConfigChange(new BN(changeId))
+        .accountsPartial({
+            pendingConfigChange: pendingConfigChangePda,
+            queuedBy: pendingChange.queuedBy,
+            admin: adminKeypair.publicKey,
+        })
+        .signers([adminKeypair])
+        .rpc();
+
+    // assert whether event has been emitted or not
+    const logs = await getTransactionLogs(program.provider, txSig);
+    const event = await findAnchorEventInLogs(logs, program.idl, Events.CONFIG_CHANGE_CANCELLED);
+    expect(event, "Config change cancelled event should be emitted").to.exist;
+
+    assert.isFalse(
+        await accountExists(program.provider.connection, pendingConfigChangePda),
+        "Cancelled config change should be closed"
+    );
+}
+
+export const cancelConfigChangeAndVerifyFail = async (
+    program: Program<ConverterProgram>,
+    changeId: number,
+    expectedError: string,
+    adminKeypair: Keypair,
+) => {
+    const pendingConfigChangePda = getPendingConfigChangePDA(program.programId, changeId);
+    try {
+        const pendingChange = await program.account.pendingConfigChange.fetch(pendingConfigChangePda);
+        await program.methods.cancelConfigChange(new BN(changeId))
+            .accountsPartial({
+                pendingConfigChange: pendingConfigChangePda,
+                queuedBy: pendingChange.queuedBy,
+                admin: adminKeypair.publicKey,
+            })
+            .signers([adminKeypair])
+            .rpc();
+    } catch (e) {
+        expect(e!.toString()).to.include(expectedError);
+        return;
+    }
+    assert.fail("It was able to cancel the config change");
+}
+
+export const migrateConfigurationRegistryToFail = async (
+    program: Program<ConverterProgram>,
+    expectedError: string,
+    adminKeypair: Keypair = getDefaultKeyPair(),
+) => {
+    try {
+        await program.methods.migrateConfigurationRegistry()
+            .accounts({
+                admin: adminKeypair.publicKey,
+            })
+            .signers([adminKeypair])
+            .rpc();
+    } catch (e) {
+        expect(e!.toString()).to.include(expectedError);
+        return;
+    }
+    assert.fail("Configuration registry migration should have failed");
+}
+
+export const migrateProgramStateToFail = async (
+    program: Program<ConverterProgram>,
+    expectedError: string,
+    adminKeypair: Keypair = getDefaultKeyPair(),
+) => {
+    try {
+        await program.methods.migrateProgramState()
+            .accounts({
+                admin: adminKeypair.publicKey,
+            })
+            .signers([adminKeypair])
+            .rpc();
+    } catch (e) {
+        expect(e!.toString()).to.include(expectedError);
+        return;
+    }
+    assert.fail("Program state migration should have failed");
+}
@@ -1,6 +1,13 @@
-import { BN, Program } from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
 import { ConverterProgram } from "../../../target/types/converter_program";
-import { OraclePriceData } from "../utils/price-oracle";
+import {
+    getEd25519Instruction,
+    OraclePriceData,
+    QuorumPriceData,
+    toOraclePriceInput,
+    toOraclePriceMessageInput,
+    toQuorumInput
+} from "../utils/price-oracle";
 import { assert, expect } from "chai";
 import {
     decodeAndValidateReturnData, delay,
@@ -8,32 +15,40 @@ import {
     getUint64FromBuffer,
     ReturnData
 } from "../utils/return-data";
-import { Keypair } from "@solana/web3.js";
+import { Keypair, TransactionInstruction } from "@solana/web3.js";
 import { getDefaultKeyPair } from "../utils/accounts";
-import { getProgramStatePDA } from "../utils/pda-helper";
-import { fetchCurrentConfiguration } from "../utils/configuration-registry";
+import { getMockDoubleZeroTokenMintPDA, getProgramStatePDA } from "../utils/pda-helper";
+import { expectedDiscountRate, fetchCurrentConfiguration } from "../utils/configuration-registry";
+
+// Discount rate expected right now, on the clock the discount ramp runs on.
+export const getExpectedDiscountRate = async (program: Program<ConverterProgram>): Promise<number> => {
+    const {lastTradeSlot, lastTradeTimestamp, carriedDiscountRate} = await program.account.programStateAccount.fetch(getProgramStatePDA(program.programId));
+    const config = await fetchCurrentConfiguration(program);
+    const currentSlot = await program.provider.connection.getSlot();
+    if (config.discountClock && "unixTimestamp" in config.discountClock) {
+        const currentTimestamp = await program.provider.connection.getBlockTime(currentSlot);
+        // No time has passed until a trade timestamp is recorded.
+        const elapsed = lastTradeTimestamp.toNumber() > 0 ? Math.max(0, currentTimestamp - lastTradeTimestamp.toNumber()) : 0;
+        return expectedDiscountRate(config, elapsed, carriedDiscountRate.toNumber());
+    }
+    return expectedDiscountRate(config, currentSlot - lastTradeSlot.toNumber(), carriedDiscountRate.toNumber());
+};
 
 export const getConversionPriceAndVerify = async (
     program: Program<ConverterProgram>,
     oraclePriceData: OraclePriceData,
     signer: Keypair = getDefaultKeyPair()
 ) => {
-    const {lastTradeSlot} = await program.account.programStateAccount.fetch(getProgramStatePDA(program.programId));
-    const currentSlot = await program.provider.connection.getSlot();
-    const {coefficient, maxDiscountRate, minDiscountRate} = await fetchCurrentConfiguration(program);
-    let discountRate = ((coefficient.toNumber() / 100000000) * (currentSlot - lastTradeSlot.toNumber())) + (minDiscountRate.toNumber() / 10000);
-    if (discountRate > (maxDiscountRate.toNumber() / 10000)) {
-        discountRate = maxDiscountRate.toNumber() / 10000;
-    }
-    const expectedAskPrice = oraclePriceData.swapRate * (1 - discountRate);
+    const discountRate = await getExpectedDiscountRate(program);
+    const {confidenceWideningBps} = await fetchCurrentConfiguration(program);
+    const widenedSwapRate = oraclePriceData.swapRate
+        + (oraclePriceData.confidence ?? 0) * (confidenceWideningBps ?? 0) / 10000;
+    const expectedAskPrice = widenedSwapRate * (1 - discountRate);
 
-    const signature = await program.methods.getConversionRate({
-        swapRate: new BN(oraclePriceData.swapRate),
-        timestamp: new BN(oraclePriceData.timestamp),
-        signature: oraclePriceData.signature,
-    })
+    const signature = await program.methods.getConversionRate(toOraclePriceInput(oraclePriceData))
         .accounts({
             signer: signer.publicKey,
+            doubleZeroMint: getMockDoubleZeroTokenMintPDA(),
         })
         .signers([signer])
         .rpc();
@@ -87,13 +102,10 @@ export const getConversionPriceToFail = async (
     expectedEvent = ""
 ) => {
     try {
-        await program.methods.getConversionRate({
-            swapRate: new BN(oraclePriceData.swapRate),
-            timestamp: new BN(oraclePriceData.timestamp),
-            signature: oraclePriceData.signature,
-        })
+        await program.methods.getConversionRate(toOraclePriceInput(oraclePriceData))
             .accounts({
                 signer: signer.publicKey,
+                doubleZeroMint: getMockDoubleZeroTokenMintPDA(),
             })
             .signers([signer])
             .rpc();
@@ -112,4 +124,143 @@ export const getConversionPriceToFail = async (
         }
     }
     assert.fail("Transaction should have failed");
-}
\ No newline at end of file
+}
+export const getQuorumConversionPriceAndVerify = async (
+    program: Program<ConverterProgram>,
+    quorumPriceData: QuorumPriceData,
+    expectedSwapRate: number,
+    signer: Keypair = getDefaultKeyPair()
+) => {
+    const discountRate = await getExpectedDiscountRate(program);
+    const expectedAskPrice = expectedSwapRate * (1 - discountRate);
+
+    const signature = await program.methods.getConversionRateWithQuorum(toQuorumInput(quorumPriceData))
+        .accounts({
+            signer: signer.publicKey,
+            doubleZeroMint: getMockDoubleZeroTokenMintPDA(),
+        })
+        .signers([signer])
+        .rpc();
+
+    // Retry 5 times
+    for (let i = 0; i < 5; i++) {
+        const transaction: any = await program.provider.connection.getTransaction(signature, {
+            commitment: "confirmed",
+            maxSupportedTransactionVersion: 1,
+        });
+
+        if (!transaction || !transaction.meta || !transaction.meta.returnData) {
+            if (i === 4) {
+                assert.fail("Transaction not found");
+            }
+            await delay(500);
+            continue;
+        }
+
+        const decodedReturnData = decodeAndValidateReturnData(
+            transaction.meta.returnData as ReturnData,
+            program.programId.toString()
+        );
+        const actualAskPrice = getUint64FromBuffer(decodedReturnData);
+
+        // Ask price has to be derived from the median swap rate.
+        const errorMargin = 0.01;
+        assert(
+            actualAskPrice >= expectedAskPrice * (1 - errorMargin) && actualAskPrice <= expectedAskPrice * (1 + errorMargin),
+            `actualAskPrice (${actualAskPrice}) is not within ${errorMargin * 100}% of expectedAskPrice (${expectedAskPrice})`
+        );
+        return Number(actualAskPrice);
+    }
+}
+
+export const getQuorumConversionPriceToFail = async (
+    program: Program<ConverterProgram>,
+    quorumPriceData: QuorumPriceData,
+    expectedError: string,
+    signer: Keypair = getDefaultKeyPair(),
+) => {
+    try {
+        await program.methods.getConversionRateWithQuorum(toQuorumInput(quorumPriceData))
+            .accounts({
+                signer: signer.publicKey,
+                doubleZeroMint: getMockDoubleZeroTokenMintPDA(),
+            })
+            .signers([signer])
+            .rpc();
+    } catch (e) {
+        expect(e!.toString()).to.include(expectedError);
+        return;
+    }
+    assert.fail("Transaction should have failed");
+}
+
+export const getPrecompileConversionPriceAndVerify = async (
+    program: Program<ConverterProgram>,
+    oraclePriceData: OraclePriceData,
+    preInstructions: TransactionInstruction[] = [getEd25519Instruction(oraclePriceData)],
+    signer: Keypair = getDefaultKeyPair()
+) => {
+    const discountRate = await getExpectedDiscountRate(program);
+    const expectedAskPrice = oraclePriceData.swapRate * (1 - discountRate);
+
+    const signature = await program.methods.getConversionRateWithPrecompile(toOraclePriceMessageInput(oraclePriceData))
+        .accounts({
+            signer: signer.publicKey,
+            doubleZeroMint: getMockDoubleZeroTokenMintPDA(),
+        })
+        .preInstructions(preInstructions)
+        .signers([signer])
+        .rpc();
+
+    // Retry 5 times
+    for (let i = 0; i < 5; i++) {
+        const transaction: any = await program.provider.connection.getTransaction(signature, {
+            commitment: "confirmed",
+            maxSupportedTransactionVersion: 1,
+        });
+
+        if (!transaction || !transaction.meta || !transaction.meta.returnData) {
+            if (i === 4) {
+                assert.fail("Transaction not found");
+            }
+            await delay(500);
+            continue;
+        }
+
+        const decodedReturnData = decodeAndValidateReturnData(
+            transaction.meta.returnData as ReturnData,
+            program.programId.toString()
+        );
+        const actualAskPrice = getUint64FromBuffer(decodedReturnData);
+
+        const errorMargin = 0.1;
+        assert(
+            actualAskPrice >= expectedAskPrice * (1 - errorMargin) && actualAskPrice <= expectedAskPrice * (1 + errorMargin),
+            `actualAskPrice (${actualAskPrice}) is not within ${errorMargin * 100}% of expectedAskPrice (${expectedAskPrice})`
+        );
+        return Number(actualAskPrice);
+    }
+}
+
+export const getPrecompileConversionPriceToFail = async (
+    program: Program<ConverterProgram>,
+    oraclePriceData: OraclePriceData,
+    expectedError: string,
+    preInstructions: TransactionInstruction[] = [getEd25519Instruction(oraclePriceData)],
+    signer: Keypair = getDefaultKeyPair(),
+) => {
+    try {
+        await program.methods.getConversionRateWithPrecompile(toOraclePriceMessageInput(oraclePriceData))
+            .accounts({
+                signer: signer.publicKey,
+                doubleZeroMint: getMockDoubleZeroTokenMintPDA(),
+            })
+            .preInstructions(preInstructions)
+            .signers([signer])
+            .rpc();
+    } catch (e) {
+        expect(e!.toString()).to.include(expectedError);
+        return;
+    }
+    assert.fail("Transaction should have failed");
+}
@@ -93,6 +93,16 @@ export async function consumeFillsSuccess(
         (fillsRegistryBefore.head + expectedHeadChange) % fillsRegistryBefore.maxCapacity,
         "Head pointer should move by expected amount in circular buffer manner"
     );
+    // A partially dequeued fill keeps its buyer, slot, time, price and sequence.
+    if (fillsRegistryAfter.count > 0) {
+        const headBefore = fillsRegistryBefore.fills[expectedHeadChange];
+        const headAfter = fillsRegistryAfter.fills[0];
+        assert.equal(headAfter.sequence, headBefore.sequence, "Head fill should keep its sequence");
+        assert.equal(headAfter.buyer.toBase58(), headBefore.buyer.toBase58(), "Head fill should keep its buyer");
+        assert.equal(headAfter.slot, headBefore.slot, "Head fill should keep its slot");
+        assert.equal(headAfter.timestamp, headBefore.timestamp, "Head fill should keep its timestamp");
+        assert.equal(headAfter.askPrice, headBefore.askPrice, "Head fill should keep its ask price");
+    }
 
     // assert whether event has been emitted or not
     const logs = await getTransactionLogs(program.provider, signature);
@@ -146,3 +156,145 @@ export async function clearUpFillsRegistry(
         )
     }
 }
+
+export async function consumeFillsBy2zSuccess(
+    program: Program<ConverterProgram>,
+    max2zAmount: number,
+    signer: Keypair,
+    expectedSolConsumed: number,
+    expectedFillsConsumed: number,
+    expectedFinalCount: number
+): Promise<void> {
+    const fillsRegistryAddress: PublicKey = await getFillsRegistryAccountAddress(program);
+    const fillsRegistryBefore: FillsRegistry = await getFillsRegistryAccount(program);
+
+    let signature: string;
+    try {
+        signature = await program.methods.dequeueFillsBy2Z(new BN(max2zAmount))
+            .accounts({
+                fillsRegistry: fillsRegistryAddress,
+                signer: signer.publicKey
+            })
+            .signers([signer])
+            .rpc();
+    } catch (e) {
+        console.error("Consume fills by 2Z failed:", e);
+        assert.fail("Consume fills by 2Z failed");
+    }
+
+    const logs = await getTransactionLogs(program.provider, signature);
+    const event = await findAnchorEventInLogs(logs, program.idl, Events.FILLS_CONSUMED);
+    expect(event, "Fills dequeued event should be emitted").to.exist;
+
+    // The 2Z target is met exactly, the SOL side of the head fill is rounded down.
+    assert.equal(event.data.token2ZDequeued.toNumber(), max2zAmount);
+    assert.approximately(event.data.solDequeued.toNumber(), expectedSolConsumed, 1);
+    assert.equal(event.data.fillsConsumed.toNumber(), expectedFillsConsumed);
+
+    const fillsRegistryAfter: FillsRegistry = await getFillsRegistryAccount(program);
+    assert.equal(fillsRegistryAfter.count, expectedFinalCount, "Count should change by correct amount");
+    assert.equal(
+        fillsRegistryBefore.total2ZPending - fillsRegistryAfter.total2ZPending,
+        max2zAmount,
+        "Pending 2Z should decrease by the dequeued amount"
+    );
+}
+
+export async function consumeFillsDetailedSuccess(
+    program: Program<ConverterProgram>,
+    maxSolAmount: number,
+    signer: Keypair
+): Promise<void> {
+    const fillsRegistryAddress: PublicKey = await getFillsRegistryAccountAddress(program);
+    const fillsRegistryBefore: FillsRegistry = await getFillsRegistryAccount(program);
+
+    let signature: string;
+    try {
+        signature = await program.methods.dequeueFillsDetailed({sol: [new BN(maxSolAmount)]})
+            .accounts({
+                fillsRegistry: fillsRegistryAddress,
+                signer: signer.publicKey
+            })
+            .signers([signer])
+            .rpc();
+    } catch (e) {
+        console.error("Detailed consume fills failed:", e);
+        assert.fail("Detailed consume fills failed");
+    }
+
+    const logs = await getTransactionLogs(program.provider, signature);
+    const event = await findAnchorEventInLogs(logs, program.idl, Events.FILLS_CONSUMED_DETAILED);
+    expect(event, "Detailed fills dequeued event should be emitted").to.exist;
+
+    // Every slice matches the fill it was taken from, in queue order.
+    let solLeft = maxSolAmount;
+    const slices = event.data.slices;
+    for (let i = 0; i < slices.length; i++) {
+        const fill = fillsRegistryBefore.fills[i];
+        assert.equal(slices[i].sequence.toNumber(), fill.sequence, "Slice should carry the fill sequence");
+        assert.equal(slices[i].solIn.toNumber(), Math.min(fill.solIn, solLeft));
+        solLeft -= slices[i].solIn.toNumber();
+    }
+    assert.equal(solLeft, 0, "Slices should add up to the dequeued SOL");
+
+    const fillsRegistryAfter: FillsRegistry = await getFillsRegistryAccount(program);
+    const lastFill = fillsRegistryBefore.fills[slices.length - 1];
+    const lastSlice = slices[slices.length - 1];
+    if (lastSlice.solIn.toNumber() < lastFill.solIn) {
+        const remainder = event.data.headRemainder;
+        assert.equal(remainder.sequence.toNumber(), lastFill.sequence, "Remainder should belong to the head fill");
+        assert.equal(remainder.solIn.toNumber(), fillsRegistryAfter.fills[0].solIn);
+        assert.equal(remainder.token2ZOut.toNumber(), fillsRegistryAfter.fills[0].token2ZOut);
+    } else {
+        assert.isNull(event.data.headRemainder, "No remainder should be reported");
+    }
+}
+
+export async function peekFillsSuccess(
+    program: Program<ConverterProgram>,
+    offset: number,
+    limit: number
+): Promise<void> {
+    const fillsRegistryAddress: PublicKey = await getFillsRegistryAccountAddress(program);
+    const fillsRegistryBefore: FillsRegistry = await getFillsRegistryAccount(program);
+
+    const page = await program.methods.peekFills(new BN(offset), limit)
+        .accounts({
+            fillsRegistry: fillsRegistryAddress,
+        })
+        .view();
+
+    assert.equal(page.count.toNumber(), fillsRegistryBefore.count, "Page should report the pending fills");
+    const expected = fillsRegistryBefore.fills.slice(offset, offset + limit);
+    assert.equal(page.fills.length, expected.length, "Page should hold the requested fills");
+    for (let i = 0; i < expected.length; i++) {
+        assert.equal(page.fills[i].sequence.toNumber(), expected[i].sequence, "Fills should be listed from head");
+        assert.equal(page.fills[i].solIn.toNumber(), expected[i].solIn);
+        assert.equal(page.fills[i].token2ZOut.toNumber(), expected[i].token2ZOut);
+    }
+
+    // Peeking does not consume anything
+    const fillsRegistryAfter: FillsRegistry = await getFillsRegistryAccount(program);
+    assert.equal(fillsRegistryAfter.count, fillsRegistryBefore.count);
+    assert.equal(fillsRegistryAfter.head, fillsRegistryBefore.head);
+}
+
+export async function peekFillsFail(
+    program: Program<ConverterProgram>,
+    offset: number,
+    limit: number,
+    expectedError: string
+): Promise<void> {
+    const fillsRegistryAddress: PublicKey = await getFillsRegistryAccountAddress(program);
+    try {
+        await program.methods.peekFills(new BN(offset), limit)
+            .accounts({
+                fillsRegistry: fillsRegistryAddress,
+            })
+            .view();
+    } catch (error) {
+        expect((new Error(error!.toString())).message).to.include(expectedError);
+        return;
+    }
+    assert.fail("It was able to peek fills");
+}
@@ -0,0 +1,127 @@
+import {Program} from "@coral-xyz/anchor";
+import {ConverterProgram} from "../../../target/types/converter_program";
+import {Keypair, PublicKey} from "@solana/web3.js";
+import {assert, expect} from "chai";
+import {getDefaultKeyPair} from "../utils/accounts";
+import {getProgramStatePDA} from "../utils/pda-helper";
+import {FillsRegistry, getFillsRegistryAccount, getFillsRegistryAccountAddress} from "../utils/fills-registry";
+import {findAnchorEventInLogs, getTransactionLogs} from "../utils/return-data";
+import {initializeFillRegistry} from "./system-initialize";
+import {Events} from "../constants";
+
+export async function beginFillsMigrationAndVerify(
+    program: Program<ConverterProgram>,
+    capacity: number,
+    adminKeyPair: Keypair = getDefaultKeyPair()
+): Promise<PublicKey> {
+    const fillsRegistryBefore: FillsRegistry = await getFillsRegistryAccount(program);
+    const previousFillsRegistry = await getFillsRegistryAccountAddress(program);
+    const newFillsRegistry = await initializeFillRegistry(program, adminKeyPair, capacity);
+
+    const txSig = await program.methods.beginFillsMigration()
+        .accounts({
+            admin: adminKeyPair.publicKey,
+            previousFillsRegistry,
+            fillsRegistry: newFillsRegistry,
+        })
+        .signers([adminKeyPair])
+        .rpc();
+
+    const programState = await program.account.programStateAccount.fetch(getProgramStatePDA(program.programId));
+    assert.equal(programState.pendingFillsRegistry.toBase58(), newFillsRegistry.toBase58());
+    assert.equal(programState.fillsRegistryAddress.toBase58(), previousFillsRegistry.toBase58());
+
+    const newRegistry: FillsRegistry = await getFillsRegistryAccount(program, newFillsRegistry);
+    assert.equal(
+        newRegistry.nextFillSequence,
+        fillsRegistryBefore.nextFillSequence,
+        "Sequence numbers should carry on in the new registry"
+    );
+    assert.equal(newRegistry.maxCapacity, capacity, "Capacity should follow from the account size");
+
+    const logs = await getTransactionLogs(program.provider, txSig);
+    const event = await findAnchorEventInLogs(logs, program.idl, Events.FILLS_MIGRATION_STARTED);
+    expect(event, "Fills migration started event should be emitted").to.exist;
+    return newFillsRegistry;
+}
+
+export async function migrateFillsAndVerify(
+    program: Program<ConverterProgram>,
+    maxFills: number,
+    recipient: PublicKey,
+    adminKeyPair: Keypair = getDefaultKeyPair()
+): Promise<void> {
+    const programStatePDA = getProgramStatePDA(program.programId);
+    const programStateBefore = await program.account.programStateAccount.fetch(programStatePDA);
+    const previousFillsRegistry: PublicKey = programStateBefore.fillsRegistryAddress;
+    const newFillsRegistry: PublicKey = programStateBefore.pendingFillsRegistry;
+    const previousRegistryBefore: FillsRegistry = await getFillsRegistryAccount(program);
+    const previousRegistryLamports = await program.provider.connection.getBalance(previousFillsRegistry);
+    const recipientBalanceBefore = await program.provider.connection.getBalance(recipient);
+
+    const txSig = await program.methods.migrateFills(maxFills)
+        .accounts({
+            admin: adminKeyPair.publicKey,
+            previousFillsRegistry,
+            fillsRegistry: newFillsRegistry,
+            recipient,
+        })
+        .signers([adminKeyPair])
+        .rpc();
+
+    const fillsMoved = Math.min(maxFills, previousRegistryBefore.count);
+    const newRegistry: FillsRegistry = await getFillsRegistryAccount(program, newFillsRegistry);
+    const movedFills = previousRegistryBefore.fills.slice(0, fillsMoved);
+    const receivedFills = newRegistry.fills.slice(newRegistry.count - fillsMoved);
+    for (let i = 0; i < fillsMoved; i++) {
+        assert.equal(receivedFills[i].sequence, movedFills[i].sequence, "Moved fill should keep its sequence");
+        assert.equal(receivedFills[i].solIn, movedFills[i].solIn, "Moved fill should keep its SOL amount");
+        assert.equal(receivedFills[i].buyer.toBase58(), movedFills[i].buyer.toBase58(), "Moved fill should keep its buyer");
+    }
+
+    const programStateAfter = await program.account.programStateAccount.fetch(programStatePDA);
+    if (fillsMoved < previousRegistryBefore.count) {
+        assert.equal(programStateAfter.fillsRegistryAddress.toBase58(), previousFillsRegistry.toBase58());
+        return;
+    }
+
+    // The last chunk switches the program over and closes the previous registry.
+    assert.equal(programStateAfter.fillsRegistryAddress.toBase58(), newFillsRegistry.toBase58());
+    assert.equal(programStateAfter.pendingFillsRegistry.toBase58(), PublicKey.default.toBase58());
+    assert.isNull(
+        await program.provider.connection.getAccountInfo(previousFillsRegistry),
+        "Previous fills registry should be closed"
+    );
+    assert.equal(
+        await program.provider.connection.getBalance(recipient) - recipientBalanceBefore,
+        previousRegistryLamports,
+        "Recipient should receive the lamports of the previous registry"
+    );
+
+    const logs = await getTransactionLogs(program.provider, txSig);
+    const event = await findAnchorEventInLogs(logs, program.idl, Events.FILLS_REGISTRY_MIGRATED);
+    expect(event, "Fills registry migrated event should be emitted").to.exist;
+}
+
+export async function closeFillsRegistryAndVerifyFail(
+    program: Program<ConverterProgram>,
+    fillsRegistry: PublicKey,
+    recipient: PublicKey,
+    expectedError: string,
+    adminKeyPair: Keypair = getDefaultKeyPair()
+): Promise<void> {
+    try {
+        await program.methods.closeFillsRegistry()
+            .accounts({
+                admin: adminKeyPair.publicKey,
+                fillsRegistry,
+                recipient,
+            })
+            .signers([adminKeyPair])
+            .rpc();
+    } catch (error) {
+        expect((new Error(error!.toString())).message).to.include(expectedError);
+        return;
+    }
+    assert.fail("Close fills registry should be rejected");
+}
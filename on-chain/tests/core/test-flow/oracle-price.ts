@@ -0,0 +1,168 @@
+import { Program } from "@coral-xyz/anchor";
+import BN from "bn.js";
+import { assert, expect } from "chai";
+import { Keypair } from "@solana/web3.js";
+import { ConverterProgram } from "../../../target/types/converter_program";
+import { ORACLE_KEYPAIR, OraclePriceData } from "../utils/price-oracle";
+import { airdropToActivateAccount, getDefaultKeyPair } from "../utils/accounts";
+import { getMockDoubleZeroTokenMintPDA, getOraclePricePDA } from "../utils/pda-helper";
+import { fetchCurrentConfiguration } from "../utils/configuration-registry";
+import { getExpectedDiscountRate } from "./conversion-price";
+import {
+    decodeAndValidateReturnData,
+    delay,
+    findAnchorEventInLogs,
+    getTransactionLogs,
+    getUint64FromBuffer,
+    ReturnData
+} from "../utils/return-data";
+import { Events, TOKEN_UNITS } from "../constants";
+
+export const fetchPostedPrice = async (program: Program<ConverterProgram>) =>
+    await program.account.oraclePriceAccount.fetchNullable(getOraclePricePDA(program.programId));
+
+// Posted timestamps have to move forward, even for posts within the same second.
+export const nextPostTimestamp = async (program: Program<ConverterProgram>): Promise<number> => {
+    const postedPrice = await fetchPostedPrice(program);
+    const now = Math.floor(Date.now() / 1000);
+    return postedPrice ? Math.max(now, postedPrice.timestamp.toNumber() + 1) : now;
+};
+
+export async function postPriceAndVerify(
+    program: Program<ConverterProgram>,
+    swapRate: number,
+    timestamp: number,
+    oracle: Keypair = ORACLE_KEYPAIR,
+    confidence: number = 0
+): Promise<OraclePriceData> {
+    // The oracle pays for the account on its first post.
+    await airdropToActivateAccount(program.provider.connection, oracle.publicKey);
+
+    const swapRateInt = Math.floor(swapRate * TOKEN_UNITS);
+    const confidenceInt = Math.floor(confidence * TOKEN_UNITS);
+    try {
+        const txSig = await program.methods
+            .postPrice(new BN(swapRateInt), new BN(timestamp), new BN(confidenceInt))
+            .accounts({
+                oracle: oracle.publicKey,
+            })
+            .signers([oracle])
+            .rpc();
+
+        await delay(100);
+        const logs = await getTransactionLogs(program.provider, txSig);
+        const event = await findAnchorEventInLogs(logs, program.idl, Events.ORACLE_PRICE_POSTED);
+        expect(event, "Oracle price posted event should be emitted").to.exist;
+    } catch (e) {
+        console.error("Post price failed:", e);
+        assert.fail("Post price failed");
+    }
+
+    const postedPrice = await fetchPostedPrice(program);
+    assert.equal(postedPrice.swapRate.toNumber(), swapRateInt);
+    assert.equal(postedPrice.timestamp.toNumber(), timestamp);
+    assert.equal(postedPrice.confidence.toNumber(), confidenceInt);
+    assert.isTrue(postedPrice.oraclePubkey.equals(oracle.publicKey));
+
+    return {
+        swapRate: swapRateInt,
+        timestamp,
+        confidence: confidenceInt,
+    } as OraclePriceData;
+}
+
+export async function postPriceToFail(
+    program: Program<ConverterProgram>,
+    swapRate: number,
+    timestamp: number,
+    expectedError: string,
+    oracle: Keypair = ORACLE_KEYPAIR
+) {
+    await airdropToActivateAccount(program.provider.connection, oracle.publicKey);
+    try {
+        await program.methods
+            .postPrice(new BN(Math.floor(swapRate * TOKEN_UNITS)), new BN(timestamp), new BN(0))
+            .accounts({
+                oracle: oracle.publicKey,
+            })
+            .signers([oracle])
+            .rpc();
+    } catch (e) {
+        expect(e!.toString()).to.include(expectedError);
+        return;
+    }
+    assert.fail("Post price should have failed");
+}
+
+/// Quotes from the posted price, the expected ask follows the same discount and widening.
+export const getPostedConversionPriceAndVerify = async (
+    program: Program<ConverterProgram>,
+    postedPrice: OraclePriceData,
+    signer: Keypair = getDefaultKeyPair()
+) => {
+    const discountRate = await getExpectedDiscountRate(program);
+    const config = await fetchCurrentConfiguration(program);
+    const widenedSwapRate = postedPrice.swapRate
+        + (postedPrice.confidence ?? 0) * (config.confidenceWideningBps ?? 0) / 10000;
+    const expectedAskPrice = widenedSwapRate * (1 - discountRate);
+
+    const signature = await program.methods.getConversionRateWithPostedPrice()
+        .accounts({
+            signer: signer.publicKey,
+            doubleZeroMint: getMockDoubleZeroTokenMintPDA(),
+            oraclePrice: getOraclePricePDA(program.programId),
+        })
+        .signers([signer])
+        .rpc();
+
+    // Retry 5 times
+    for (let i = 0; i < 5; i++) {
+        const transaction: any = await program.provider.connection.getTransaction(signature, {
+            commitment: "confirmed",
+            maxSupportedTransactionVersion: 1,
+        });
+
+        if (!transaction || !transaction.meta || !transaction.meta.returnData) {
+            if (i === 4) {
+                assert.fail("Transaction not found");
+            }
+            await delay(500);
+            continue;
+        }
+
+        const decodedReturnData = decodeAndValidateReturnData(
+            transaction.meta.returnData as ReturnData,
+            program.programId.toString()
+        );
+        const actualAskPrice = getUint64FromBuffer(decodedReturnData);
+
+        const errorMargin = 0.1;
+        assert(
+            actualAskPrice >= expectedAskPrice * (1 - errorMargin) && actualAskPrice <= expectedAskPrice * (1 + errorMargin),
+            `actualAskPrice (${actualAskPrice}) is not within ${errorMargin * 100}% of expectedAskPrice (${expectedAskPrice})`
+        );
+        return Number(actualAskPrice);
+    }
+}
+
+export const getPostedConversionPriceToFail = async (
+    program: Program<ConverterProgram>,
+    expectedError: string,
+    withOraclePrice: boolean = true,
+    signer: Keypair = getDefaultKeyPair()
+) => {
+    try {
+        await program.methods.getConversionRateWithPostedPrice()
+            .accounts({
+                signer: signer.publicKey,
+                doubleZeroMint: getMockDoubleZeroTokenMintPDA(),
+                oraclePrice: withOraclePrice ? getOraclePricePDA(program.programId) : null,
+            })
+            .signers([signer])
+            .rpc();
+    } catch (e) {
+        expect(e!.toString()).to.include(expectedError);
+        return;
+    }
+    assert.fail("Transaction should have failed");
+}
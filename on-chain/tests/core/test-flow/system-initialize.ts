@@ -13,7 +13,8 @@ import { ConverterProgram } from "../../../target/types/converter_program";
 import {toggleSystemStateAndVerify} from "./system-state";
 import * as anchor from "@coral-xyz/anchor";
 import {findAnchorEventInLogs, getTransactionLogs} from "../utils/return-data";
-import {Events} from "../constants";
+import {DEFAULT_FILLS_CAPACITY, Events} from "../constants";
+import {getFillsRegistryAccount, getFillsRegistrySpace} from "../utils/fills-registry";
 
 export async function systemInitializeAndVerify(
     program: Program<ConverterProgram>,
@@ -44,14 +45,16 @@ export async function systemInitializeAndVerify(
     const programDataAccount: PublicKey = getProgramDataAccountPDA(program.programId);
     let tx: string;
     try {
-        tx = await program.methods.initializeSystem(
-            inputConfigs.oraclePubkey,
-            inputConfigs.solQuantity,
-            inputConfigs.priceMaximumAge,
-            inputConfigs.coefficient,
-            inputConfigs.maxDiscountRate,
-            inputConfigs.minDiscountRate
-        )
+        tx = await program.methods.initializeSystem({
+            oraclePubkey: inputConfigs.oraclePubkey,
+            solQuantity: inputConfigs.solQuantity,
+            priceMaximumAge: inputConfigs.priceMaximumAge,
+            coefficient: inputConfigs.coefficient,
+            maxDiscountRate: inputConfigs.maxDiscountRate,
+            minDiscountRate: inputConfigs.minDiscountRate,
+            minSolQuantity: inputConfigs.minSolQuantity,
+            maxSolQuantity: inputConfigs.maxSolQuantity,
+        })
             .accounts({
                 fillsRegistry: fillsRegistryAddress,
                 authority: adminKeyPair.publicKey,
@@ -78,6 +81,10 @@ export async function systemInitializeAndVerify(
     assert.isTrue(fillsRegistryExists, "Fills Registry should exist after initialization");
     assert.isTrue(denyRegistryExists, "Deny List Registry should exist after initialization");
 
+    // The fills registry capacity follows from its account size.
+    const fillsRegistry = await getFillsRegistryAccount(program, fillsRegistryAddress);
+    assert.equal(fillsRegistry.maxCapacity, DEFAULT_FILLS_CAPACITY, "Fills Registry capacity should be stored");
+
     // Verify config values are initialized as given.
     const configInConfigRegistry = await fetchCurrentConfiguration(program);
     assert.equal(configInConfigRegistry.oraclePubkey.toString(), inputConfigs.oraclePubkey.toString());
@@ -86,6 +93,8 @@ export async function systemInitializeAndVerify(
     assert.equal(configInConfigRegistry.coefficient.toString(), inputConfigs.coefficient.toString());
     assert.equal(configInConfigRegistry.maxDiscountRate.toString(), inputConfigs.maxDiscountRate.toString());
     assert.equal(configInConfigRegistry.minDiscountRate.toString(), inputConfigs.minDiscountRate.toString());
+    assert.equal(configInConfigRegistry.minSolQuantity.toString(), inputConfigs.minSolQuantity.toString());
+    assert.equal(configInConfigRegistry.maxSolQuantity.toString(), inputConfigs.maxSolQuantity.toString());
 
     // assert whether event has been emitted or not
     const logs = await getTransactionLogs(program.provider, tx);
@@ -104,14 +113,16 @@ export async function systemInitializeFail(
     const fillsRegistryAddress: PublicKey = await initializeFillRegistry(program);
 
     try {
-        await program.methods.initializeSystem(
-            configRegistryValues.oraclePubkey,
-            configRegistryValues.solQuantity,
-            configRegistryValues.priceMaximumAge,
-            configRegistryValues.coefficient,
-            configRegistryValues.maxDiscountRate,
-            configRegistryValues.minDiscountRate
-        )
+        await program.methods.initializeSystem({
+            oraclePubkey: configRegistryValues.oraclePubkey,
+            solQuantity: configRegistryValues.solQuantity,
+            priceMaximumAge: configRegistryValues.priceMaximumAge,
+            coefficient: configRegistryValues.coefficient,
+            maxDiscountRate: configRegistryValues.maxDiscountRate,
+            minDiscountRate: configRegistryValues.minDiscountRate,
+            minSolQuantity: configRegistryValues.minSolQuantity,
+            maxSolQuantity: configRegistryValues.maxSolQuantity,
+        })
             .accounts({
                 fillsRegistry: fillsRegistryAddress,
                 authority: adminKeyPair.publicKey,
@@ -143,11 +154,12 @@ export async function initializeSystemIfNeeded(program: Program<ConverterProgram
 
 export async function initializeFillRegistry(
     program: Program<ConverterProgram>,
-    adminKeyPair: Keypair = getDefaultKeyPair()
+    adminKeyPair: Keypair = getDefaultKeyPair(),
+    capacity: number = DEFAULT_FILLS_CAPACITY
 ): Promise<PublicKey> {
     // initializing fills registry.
     const fillsRegistryKeyPair = Keypair.generate();
-    const space = 10_485_760; // 10MB max account size
+    const space = getFillsRegistrySpace(capacity);
     const lamports = await program.provider.connection.getMinimumBalanceForRentExemption(space);
 
     const tx = new anchor.web3.Transaction().add(
@@ -2,7 +2,7 @@ import { Program } from "@coral-xyz/anchor";
 import { ConverterProgram } from "../../../target/types/converter_program";
 import { getProgramStatePDA } from "../utils/pda-helper";
 import { accountExists, getDefaultKeyPair } from "../utils/accounts";
-import { Keypair } from "@solana/web3.js";
+import { Keypair, PublicKey } from "@solana/web3.js";
 import { assert, expect } from "chai";
 import {findAnchorEventInLogs, getTransactionLogs} from "../utils/return-data";
 import {Events} from "../constants";
@@ -45,6 +45,9 @@ export const toggleSystemStateAndVerify = async (
     const logs = await getTransactionLogs(program.provider, txSig);
     const event = await findAnchorEventInLogs(logs, program.idl, eventName);
     expect(event, "Appropriate Event should be emitted").to.exist;
+    if (set_to) {
+        expect(event.data.role, "Halt should be attributed to the admin").to.have.property("admin");
+    }
 }
 
 export const toggleSystemStateAndVerifyFail = async (
@@ -76,4 +79,125 @@ export const toggleSystemStateAndVerifyFail = async (
     }
 
     assert.fail("Toggle system state should be rejected as expected");
-}
\ No newline at end of file
+}
+
+export const setGuardianAndVerify = async (
+    program: Program<ConverterProgram>,
+    guardian: PublicKey,
+    adminKeypair: Keypair = getDefaultKeyPair(),
+) => {
+    const txSig = await program.methods.setGuardian(guardian)
+        .accounts({
+            admin: adminKeypair.publicKey,
+        })
+        .signers([adminKeypair])
+        .rpc();
+
+    const programState = await program.account.programStateAccount.fetch(getProgramStatePDA(program.programId));
+    assert.equal(programState.guardian.toBase58(), guardian.toBase58(), "Guardian should be set");
+
+    const logs = await getTransactionLogs(program.provider, txSig);
+    const event = await findAnchorEventInLogs(logs, program.idl, Events.GUARDIAN_SET);
+    expect(event, "Guardian set event should be emitted").to.exist;
+}
+
+export const setGuardianAndVerifyFail = async (
+    program: Program<ConverterProgram>,
+    guardian: PublicKey,
+    expectedError: string,
+    adminKeypair: Keypair,
+) => {
+    try {
+        await program.methods.setGuardian(guardian)
+            .accounts({
+                admin: adminKeypair.publicKey,
+            })
+            .signers([adminKeypair])
+            .rpc();
+    } catch (error) {
+        expect((new Error(error!.toString())).message).to.include(expectedError);
+        return;
+    }
+    assert.fail("Set guardian should be rejected");
+}
+
+export const haltSystemAndVerify = async (
+    program: Program<ConverterProgram>,
+    signer: Keypair,
+    expectedRole: "guardian" | "admin",
+) => {
+    const txSig = await program.methods.haltSystem()
+        .accounts({
+            signer: signer.publicKey,
+        })
+        .signers([signer])
+        .rpc();
+
+    const programState = await program.account.programStateAccount.fetch(getProgramStatePDA(program.programId));
+    assert.isTrue(programState.isHalted, "System should be halted");
+
+    const logs = await getTransactionLogs(program.provider, txSig);
+    const event = await findAnchorEventInLogs(logs, program.idl, Events.SYSTEM_HALTED);
+    expect(event, "System halted event should be emitted").to.exist;
+    assert.equal(event.data.haltedBy.toBase58(), signer.publicKey.toBase58());
+    expect(event.data.role, "Halt should be attributed to the signing role").to.have.property(expectedRole);
+}
+
+export const haltSystemAndVerifyFail = async (
+    program: Program<ConverterProgram>,
+    signer: Keypair,
+    expectedError: string,
+) => {
+    try {
+        await program.methods.haltSystem()
+            .accounts({
+                signer: signer.publicKey,
+            })
+            .signers([signer])
+            .rpc();
+    } catch (error) {
+        expect((new Error(error!.toString())).message).to.include(expectedError);
+        return;
+    }
+    assert.fail("Halt system should be rejected");
+}
+
+export const setPauseFlagsAndVerify = async (
+    program: Program<ConverterProgram>,
+    pauseFlags: number,
+    signer: Keypair = getDefaultKeyPair(),
+) => {
+    const txSig = await program.methods.setPauseFlags(pauseFlags)
+        .accounts({
+            signer: signer.publicKey,
+        })
+        .signers([signer])
+        .rpc();
+
+    const programState = await program.account.programStateAccount.fetch(getProgramStatePDA(program.programId));
+    assert.equal(programState.pauseFlags, pauseFlags, "Pause flags should be set");
+
+    const logs = await getTransactionLogs(program.provider, txSig);
+    const event = await findAnchorEventInLogs(logs, program.idl, Events.PAUSE_FLAGS_SET);
+    expect(event, "Pause flags set event should be emitted").to.exist;
+}
+
+export const setPauseFlagsAndVerifyFail = async (
+    program: Program<ConverterProgram>,
+    pauseFlags: number,
+    expectedError: string,
+    signer: Keypair = getDefaultKeyPair(),
+) => {
+    try {
+        await program.methods.setPauseFlags(pauseFlags)
+            .accounts({
+                signer: signer.publicKey,
+            })
+            .signers([signer])
+            .rpc();
+    } catch (error) {
+        expect((new Error(error!.toString())).message).to.include(expectedError);
+        return;
+    }
+    assert.fail("Set pause flags should be rejected");
+}
@@ -13,8 +13,40 @@ export interface SystemConfig {
     coefficient: BN,
     maxDiscountRate: BN,
     minDiscountRate: BN,
+    minSolQuantity: BN,
+    maxSolQuantity: BN,
+    oraclePubkeys?: PublicKey[] | null,
+    oracleQuorumThreshold?: number | null,
+    disableV1Attestations?: boolean | null,
+    attestationTimestampTolerance?: number | null,
+    maxTradesPerWalletPerEpoch?: BN | null,
+    maxSolPerWalletPerEpoch?: BN | null,
+    epochSolCap?: BN | null,
+    configChangeDelay?: number | null,
+    fillsHighWatermarkBps?: number | null,
+    fillsLowWatermarkBps?: number | null,
+    stopBuysAtHighWatermark?: boolean | null,
+    discountCurve?: DiscountCurve | null,
+    discountClock?: DiscountClock | null,
+    discountMemoryBps?: number | null,
+    minSwapRate?: BN | null,
+    maxSwapRate?: BN | null,
+    maxSwapRateDeviationBps?: number | null,
+    referenceSwapRateMaxAge?: number | null,
+    maxConfidenceBps?: number | null,
+    confidenceWideningBps?: number | null,
 }
 
+// Anchor enum layout of the on-chain DiscountCurve.
+export type DiscountCurve =
+    { linear: {} }
+    | { linearWithGrace: { graceLength: BN } }
+    | { exponential: { halfLife: BN } }
+    | { stepped: { stepLength: BN, stepRate: BN } };
+
+// Anchor enum layout of the on-chain DiscountClock.
+export type DiscountClock = { slot: {} } | { unixTimestamp: {} };
+
 // Default Configurations.
 export const DEFAULT_CONFIGS: SystemConfig = {
     oraclePubkey: ORACLE_KEYPAIR.publicKey,
@@ -23,6 +55,8 @@ export const DEFAULT_CONFIGS: SystemConfig = {
     coefficient: new anchor.BN(1),
     maxDiscountRate: new anchor.BN(50 * BPS),
     minDiscountRate: new anchor.BN(10 * BPS),
+    minSolQuantity: new anchor.BN(LAMPORTS_PER_SOL),
+    maxSolQuantity: new anchor.BN(100 * LAMPORTS_PER_SOL),
 };
 
 export async function fetchCurrentConfiguration(program: anchor.Program<ConverterProgram>): Promise<SystemConfig> {
@@ -35,5 +69,52 @@ export async function fetchCurrentConfiguration(program: anchor.Program<Converte
         coefficient: configurationRegistry.coefficient,
         maxDiscountRate: configurationRegistry.maxDiscountRate,
         minDiscountRate: configurationRegistry.minDiscountRate,
+        minSolQuantity: configurationRegistry.minSolQuantity,
+        maxSolQuantity: configurationRegistry.maxSolQuantity,
+        oraclePubkeys: configurationRegistry.oraclePubkeys,
+        oracleQuorumThreshold: configurationRegistry.oracleQuorumThreshold,
+        disableV1Attestations: configurationRegistry.disableV1Attestations,
+        attestationTimestampTolerance: configurationRegistry.attestationTimestampTolerance,
+        maxTradesPerWalletPerEpoch: configurationRegistry.maxTradesPerWalletPerEpoch,
+        maxSolPerWalletPerEpoch: configurationRegistry.maxSolPerWalletPerEpoch,
+        epochSolCap: configurationRegistry.epochSolCap,
+        configChangeDelay: configurationRegistry.configChangeDelay,
+        fillsHighWatermarkBps: configurationRegistry.fillsHighWatermarkBps,
+        fillsLowWatermarkBps: configurationRegistry.fillsLowWatermarkBps,
+        stopBuysAtHighWatermark: configurationRegistry.stopBuysAtHighWatermark,
+        discountCurve: configurationRegistry.discountCurve as DiscountCurve,
+        discountClock: configurationRegistry.discountClock as DiscountClock,
+        discountMemoryBps: configurationRegistry.discountMemoryBps,
+        minSwapRate: configurationRegistry.minSwapRate,
+        maxSwapRate: configurationRegistry.maxSwapRate,
+        maxSwapRateDeviationBps: configurationRegistry.maxSwapRateDeviationBps,
+        referenceSwapRateMaxAge: configurationRegistry.referenceSwapRateMaxAge,
+        maxConfidenceBps: configurationRegistry.maxConfidenceBps,
+        confidenceWideningBps: configurationRegistry.confidenceWideningBps,
+    }
+}
+// Discount rate the program applies `slotDiff` slots (or seconds on the wall clock)
+// after the last trade, as a fraction. `carriedDiscountRate` (basis points) is what
+// the last trade carried over in discount memory mode.
+export function expectedDiscountRate(config: SystemConfig, slotDiff: number, carriedDiscountRate: number = 0): number {
+    const coefficient = config.coefficient.toNumber() / 100000000;
+    const minDiscountRate = config.minDiscountRate.toNumber() / 10000;
+    const maxDiscountRate = config.maxDiscountRate.toNumber() / 10000;
+    const curve = config.discountCurve ?? {linear: {}};
+
+    let discountRate: number;
+    if ("linearWithGrace" in curve) {
+        const graceLength = curve.linearWithGrace.graceLength.toNumber();
+        discountRate = slotDiff < graceLength ? 0 : coefficient * (slotDiff - graceLength) + minDiscountRate;
+    } else if ("exponential" in curve) {
+        const halfLife = curve.exponential.halfLife.toNumber();
+        const gap = (maxDiscountRate - minDiscountRate) / Math.pow(2, Math.floor(slotDiff / halfLife));
+        discountRate = maxDiscountRate - gap * (1 - (slotDiff % halfLife) / (2 * halfLife));
+    } else if ("stepped" in curve) {
+        const steps = Math.floor(slotDiff / curve.stepped.stepLength.toNumber());
+        discountRate = minDiscountRate + steps * curve.stepped.stepRate.toNumber() / 10000;
+    } else {
+        discountRate = coefficient * slotDiff + minDiscountRate;
     }
-}
\ No newline at end of file
+    return Math.min(discountRate + carriedDiscountRate / 10000, maxDiscountRate);
+}
@@ -1,6 +1,10 @@
 import {PublicKey} from "@solana/web3.js";
 import {getProgramStatePDA} from "./pda-helper";
 
+// Account layout: 8 byte discriminator, 7 u64 header fields, then `capacity` fills.
+const FILLS_OFFSET = 8 + 7 * 8;
+const FILL_SIZE = 6 * 8 + 32;
+
 export interface FillsRegistry {
     totalSolPending: number,
     total2ZPending: number,
@@ -8,12 +12,18 @@ export interface FillsRegistry {
     tail: number,
     count: number,
     maxCapacity: number,
+    nextFillSequence: number,
     fills: Fill[]
 }
 
 export interface Fill {
     solIn: number,
-    token2ZOut: number
+    token2ZOut: number,
+    askPrice: number,
+    sequence: number,
+    slot: number,
+    timestamp: number,
+    buyer: PublicKey
 }
 
 export async function getFillsRegistryAccountAddress(program) : Promise<PublicKey> {
@@ -22,8 +32,15 @@ export async function getFillsRegistryAccountAddress(program) : Promise<PublicKe
     return stateAccount.fillsRegistryAddress;
 }
 
-export async function getFillsRegistryAccount(program): Promise<FillsRegistry> {
-    const fillsRegistryAddress = await getFillsRegistryAccountAddress(program);
+export function getFillsRegistrySpace(capacity: number): number {
+    return FILLS_OFFSET + capacity * FILL_SIZE;
+}
+
+export async function getFillsRegistryAccount(
+    program,
+    fillsRegistryAddress?: PublicKey
+): Promise<FillsRegistry> {
+    fillsRegistryAddress = fillsRegistryAddress ?? await getFillsRegistryAccountAddress(program);
     const rawFillsRegistry = await program.account.fillsRegistry.fetch(fillsRegistryAddress);
     const fillsRegistry: FillsRegistry = {
         totalSolPending: Number(rawFillsRegistry.totalSolPending),
@@ -31,16 +48,25 @@ export async function getFillsRegistryAccount(program): Promise<FillsRegistry> {
         count: Number(rawFillsRegistry.count),
         head: Number(rawFillsRegistry.head),
         tail: Number(rawFillsRegistry.tail),
-        maxCapacity: rawFillsRegistry.fills.length,
+        maxCapacity: Number(rawFillsRegistry.capacity),
+        nextFillSequence: Number(rawFillsRegistry.nextFillSequence),
         fills: []
     }
+
+    // Fills follow the header, so they are decoded from the raw account data.
+    const {data} = await program.provider.connection.getAccountInfo(fillsRegistryAddress);
     for (let i = 0; i < fillsRegistry.count; i++) {
         const idx = (fillsRegistry.head + i) % fillsRegistry.maxCapacity;
-        const fill = rawFillsRegistry.fills[idx];
+        const offset = FILLS_OFFSET + idx * FILL_SIZE;
         fillsRegistry.fills.push({
-            solIn: Number(fill.solIn),
-            token2ZOut: Number(fill.token2ZOut)
+            solIn: Number(data.readBigUInt64LE(offset)),
+            token2ZOut: Number(data.readBigUInt64LE(offset + 8)),
+            askPrice: Number(data.readBigUInt64LE(offset + 16)),
+            sequence: Number(data.readBigUInt64LE(offset + 24)),
+            slot: Number(data.readBigUInt64LE(offset + 32)),
+            timestamp: Number(data.readBigInt64LE(offset + 40)),
+            buyer: new PublicKey(data.subarray(offset + 48, offset + 80))
         })
     }
     return fillsRegistry;
-}
\ No newline at end of file
+}
@@ -1,4 +1,5 @@
 import {PublicKey} from "@solana/web3.js";
+import BN from "bn.js";
 import {BPF_UPGRADEABLE_LOADER_ID, MOCK_TRANSFER_PROGRAM, Seeds} from "../constants";
 import CONFIGURATION_REGISTRY_SEED = Seeds.CONFIGURATION_REGISTRY_SEED;
 import PROGRAM_STATE_SEED = Seeds.PROGRAM_STATE_SEED;
@@ -8,6 +9,9 @@ import MOCK_PROTOCOL_TREASURY_SEED = Seeds.MOCK_PROTOCOL_TREASURY_SEED;
 import WITHDRAW_AUTHORITY_SEED = Seeds.WITHDRAW_AUTHORITY_SEED;
 import MOCK_CONFIG_ACCOUNT = Seeds.MOCK_CONFIG_ACCOUNT;
 import MOCK_REVENUE_DISTRIBUTION_JOURNAL = Seeds.MOCK_REVENUE_DISTRIBUTION_JOURNAL;
+import TRADE_HISTORY_SEED = Seeds.TRADE_HISTORY_SEED;
+import PENDING_CONFIG_CHANGE_SEED = Seeds.PENDING_CONFIG_CHANGE_SEED;
+import ORACLE_PRICE_SEED = Seeds.ORACLE_PRICE_SEED;
 
 export function getConfigurationRegistryPDA(programId: PublicKey) {
     return PublicKey.findProgramAddressSync(
@@ -29,6 +33,27 @@ export function getDenyListRegistryPDA(programId: PublicKey) {
     )[0]
 }
 
+export function getOraclePricePDA(programId: PublicKey) {
+    return PublicKey.findProgramAddressSync(
+        [Buffer.from(ORACLE_PRICE_SEED)],
+        programId
+    )[0]
+}
+
+export function getTradeHistoryPDA(programId: PublicKey, buyer: PublicKey) {
+    return PublicKey.findProgramAddressSync(
+        [Buffer.from(TRADE_HISTORY_SEED), buyer.toBuffer()],
+        programId
+    )[0]
+}
+
+export function getPendingConfigChangePDA(programId: PublicKey, changeId: number) {
+    return PublicKey.findProgramAddressSync(
+        [Buffer.from(PENDING_CONFIG_CHANGE_SEED), new BN(changeId).toArrayLike(Buffer, "le", 8)],
+        programId
+    )[0]
+}
+
 export function getWithdrawAuthorityPDA(programId: PublicKey) {
     return PublicKey.findProgramAddressSync(
         [Buffer.from(WITHDRAW_AUTHORITY_SEED)],
@@ -1,43 +1,171 @@
-import {TOKEN_UNITS} from "../constants";
-import { Keypair } from "@solana/web3.js";
+import {
+    ATTESTATION_DOMAIN_TAG,
+    AttestationVersion,
+    CONVERTER_PROGRAM_ID,
+    QUOTE_DIRECTION_2Z_TO_SOL,
+    TOKEN_UNITS
+} from "../constants";
+import { Ed25519Program, Keypair, PublicKey, TransactionInstruction } from "@solana/web3.js";
+import BN from "bn.js";
 import nacl from "tweetnacl";
+import { getMockDoubleZeroTokenMintPDA } from "./pda-helper";
 
 export const ORACLE_KEYPAIR = Keypair.generate();
 
 export interface OraclePriceData {
+    version: number;
     swapRate: number;
     timestamp: number;
+    confidence?: number; // only signed by v3 attestations
     signature: string;
     // solPriceUsd?: string;
     // twozPriceUsd?: string;
 }
 
+export interface QuorumAttestation extends OraclePriceData {
+    oracleIndex: number; // position of the signer in the configured oracle set
+}
+
+export interface QuorumPriceData {
+    attestations: QuorumAttestation[];
+}
+
 // Mock the price oracle data
-export const getOraclePriceDataFor = async (swapRate: number, timestamp: number): Promise<OraclePriceData> => {
+export const getOraclePriceDataFor = async (
+    swapRate: number,
+    timestamp: number,
+    oracle: Keypair = ORACLE_KEYPAIR,
+    version: number = AttestationVersion.V2,
+    confidence: number = 0
+): Promise<OraclePriceData> => {
     const swapRateInt = Math.floor(swapRate * TOKEN_UNITS);
-    const attestation = await generateRandomAttestation(swapRateInt, timestamp);
+    const confidenceInt = Math.floor(confidence * TOKEN_UNITS);
+    const attestation = await generateRandomAttestation(swapRateInt, timestamp, oracle, version, confidenceInt);
 
     return {
+        version,
         swapRate: swapRateInt,
         timestamp,
+        confidence: confidenceInt,
         signature: attestation,
     } as OraclePriceData;
 }
 
-export const getOraclePriceData = async (): Promise<OraclePriceData> => {
+export const getOraclePriceData = async (
+    oracle: Keypair = ORACLE_KEYPAIR,
+    version: number = AttestationVersion.V2
+): Promise<OraclePriceData> => {
     const swapRate = (Math.random() * 3) + 19; // 19 - 22 range
     const timestamp = Math.floor(Date.now() / 1000); // convert to seconds as solana clock is in seconds
 
-    return getOraclePriceDataFor(swapRate, timestamp);
+    return getOraclePriceDataFor(swapRate, timestamp, oracle, version);
+}
+
+// Mock a quorum where each oracle attests its own swap rate.
+// Oracles are referenced by their position in the configured set, by default their position in `oracles`.
+export const getQuorumPriceDataFor = async (
+    oracles: Keypair[],
+    swapRates: number[],
+    timestamp: number = Math.floor(Date.now() / 1000),
+    oracleIndexes: number[] = oracles.map((_, i) => i),
+): Promise<QuorumPriceData> => {
+    const attestations = await Promise.all(oracles.map(async (oracle, i) => {
+        const swapRateInt = Math.floor(swapRates[i] * TOKEN_UNITS);
+        return {
+            oracleIndex: oracleIndexes[i],
+            version: AttestationVersion.V2,
+            swapRate: swapRateInt,
+            timestamp,
+            signature: await generateRandomAttestation(swapRateInt, timestamp, oracle, AttestationVersion.V2),
+        } as QuorumAttestation;
+    }));
+    return { attestations };
+}
+
+// Signatures are passed to the program as fixed 64 bytes, malformed ones are zero padded.
+export const toSignatureBytes = (signature: string): number[] => {
+    const signatureBytes = new Uint8Array(64);
+    signatureBytes.set(Buffer.from(signature, "base64").subarray(0, 64));
+    return Array.from(signatureBytes);
+}
+
+// Program instruction argument for the oracle price data.
+export const toOraclePriceInput = (oraclePriceData: OraclePriceData) => ({
+    version: oraclePriceData.version,
+    swapRate: new BN(oraclePriceData.swapRate),
+    timestamp: new BN(oraclePriceData.timestamp),
+    confidence: new BN(oraclePriceData.confidence ?? 0),
+    signature: toSignatureBytes(oraclePriceData.signature),
+});
+
+// Program instruction argument for the quorum attestations.
+export const toQuorumInput = (quorumPriceData: QuorumPriceData) => ({
+    attestations: quorumPriceData.attestations.map((attestation) => ({
+        oracleIndex: attestation.oracleIndex,
+        ...toOraclePriceInput(attestation),
+    })),
+});
+
+// Program instruction argument when the signature is checked by the Ed25519 precompile.
+export const toOraclePriceMessageInput = (oraclePriceData: OraclePriceData) => ({
+    version: oraclePriceData.version,
+    swapRate: new BN(oraclePriceData.swapRate),
+    timestamp: new BN(oraclePriceData.timestamp),
+    confidence: new BN(oraclePriceData.confidence ?? 0),
+});
+
+// Ed25519SigVerify instruction over the attestation message, placed before the program instruction.
+export const getEd25519Instruction = (
+    oraclePriceData: OraclePriceData,
+    oraclePubkey: PublicKey = ORACLE_KEYPAIR.publicKey
+): TransactionInstruction => {
+    return Ed25519Program.createInstructionWithPublicKey({
+        publicKey: oraclePubkey.toBytes(),
+        message: attestationMessage(
+            oraclePriceData.swapRate,
+            oraclePriceData.timestamp,
+            oraclePriceData.version,
+            oraclePriceData.confidence ?? 0
+        ),
+        signature: Uint8Array.from(toSignatureBytes(oraclePriceData.signature)),
+    });
+}
+
+export const attestationMessage = (
+    swapRate: number,
+    timestamp: number,
+    version: number,
+    confidence: number = 0
+): Uint8Array => {
+    if (version === AttestationVersion.V1) {
+        return new TextEncoder().encode(`${swapRate}|${timestamp}`);
+    }
+
+    return Buffer.concat([
+        Buffer.from(ATTESTATION_DOMAIN_TAG),
+        Buffer.from([version]),
+        CONVERTER_PROGRAM_ID.toBuffer(),
+        getMockDoubleZeroTokenMintPDA().toBuffer(),
+        Buffer.from([QUOTE_DIRECTION_2Z_TO_SOL]),
+        new BN(swapRate).toArrayLike(Buffer, "le", 8),
+        new BN(timestamp).toTwos(64).toArrayLike(Buffer, "le", 8),
+        // v3 extends the v2 message with the confidence
+        version === AttestationVersion.V3 ? new BN(confidence).toArrayLike(Buffer, "le", 8) : Buffer.alloc(0),
+    ]);
 }
 
-const generateRandomAttestation = async (swapRate: number, timestamp: number): Promise<string> => {
-    const messageString = `${swapRate}|${timestamp}`;
-    const message = new TextEncoder().encode(messageString);
+const generateRandomAttestation = async (
+    swapRate: number,
+    timestamp: number,
+    oracle: Keypair = ORACLE_KEYPAIR,
+    version: number = AttestationVersion.V2,
+    confidence: number = 0
+): Promise<string> => {
+    const message = attestationMessage(swapRate, timestamp, version, confidence);
 
     // Directly sign using tweetnacl + Keypair
-    const signedBytes = nacl.sign.detached(message, ORACLE_KEYPAIR.secretKey);
+    const signedBytes = nacl.sign.detached(message, oracle.secretKey);
 
     const base64SignedBytes = Buffer.from(signedBytes).toString("base64");
     return base64SignedBytes;
-};
\ No newline at end of file
+};
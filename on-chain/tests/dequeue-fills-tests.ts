@@ -5,15 +5,32 @@ import {initializeMockTransferSystemIfNeeded} from "./core/test-flow/mock-transf
 import {createTokenAccount} from "./core/utils/token-utils";
 import {getMockProgramPDAs} from "./core/utils/pda-helper";
 import {Keypair, LAMPORTS_PER_SOL, PublicKey} from "@solana/web3.js";
-import {buySolSuccess} from "./core/test-flow/buy-sol-flow";
 import {ConverterProgram} from "../target/types/converter_program";
 import {initializeSystemIfNeeded} from "./core/test-flow/system-initialize";
 import {DEFAULT_CONFIGS, SystemConfig} from "./core/utils/configuration-registry";
 import {updateConfigsAndVerify} from "./core/test-flow/change-configs";
 import { setDenyListAuthorityAndVerify} from "./core/test-flow/deny-list";
-import {clearUpFillsRegistry, consumeFillsFail, consumeFillsSuccess} from "./core/test-flow/dequeue-fills-flow";
+import {
+    clearUpFillsRegistry,
+    consumeFillsBy2zSuccess,
+    consumeFillsDetailedSuccess,
+    peekFillsSuccess,
+    peekFillsFail,
+    consumeFillsFail,
+    consumeFillsSuccess
+} from "./core/test-flow/dequeue-fills-flow";
 import {setFillsConsumerAndVerify} from "./core/test-flow/set-fills-consumer";
-import {ErrorMsg} from "./core/constants";
+import {ErrorMsg, PauseFlags} from "./core/constants";
+import {setPauseFlagsAndVerify} from "./core/test-flow/system-state";
+import {
+    beginFillsMigrationAndVerify,
+    closeFillsRegistryAndVerifyFail,
+    migrateFillsAndVerify
+} from "./core/test-flow/fills-migration";
+import {buySolFail, buySolSuccess} from "./core/test-flow/buy-sol-flow";
+import {getOraclePriceData} from "./core/utils/price-oracle";
+import {getConversionPriceAndVerify} from "./core/test-flow/conversion-price";
+import {getFillsRegistryAccount, getFillsRegistryAccountAddress} from "./core/utils/fills-registry";
 
 describe("Consume fills tests", () => {
     // Configure the client to use the local cluster.
@@ -149,6 +166,17 @@ describe("Consume fills tests", () => {
     });
 
     describe("Edge cases", async () => {
+        it("Fails when dequeues are paused", async () => {
+            await setPauseFlagsAndVerify(program, PauseFlags.DEQUEUES);
+            await consumeFillsFail(
+                program,
+                DEFAULT_CONFIGS.solQuantity,
+                userKeyPair,
+                ErrorMsg.FEATURE_PAUSED
+            );
+            await setPauseFlagsAndVerify(program, 0);
+        });
+
         it("Fails when max_sol_amount is zero", async () => {
             await consumeFillsFail(
                 program,
@@ -377,4 +405,130 @@ describe("Consume fills tests", () => {
             await clearUpFillsRegistry(program, userKeyPair);
         });
     });
-});
\ No newline at end of file
+
+    describe("Consume fills by 2Z amount", async() => {
+        before("Enqueue fills", async () => {
+            for (let i = 0; i < 2; i++) {
+                await buySolSuccess(program, tokenAccountForUser, userKeyPair, currentConfigs, 1);
+            }
+        });
+
+        it("Consumes the first fill and splits the second one pro rata", async () => {
+            const {fills} = await getFillsRegistryAccount(program);
+            const half2zOfSecond = Math.floor(fills[1].token2ZOut / 2);
+            const expectedSolConsumed = fills[0].solIn
+                + Math.floor(fills[1].solIn * half2zOfSecond / fills[1].token2ZOut);
+
+            await consumeFillsBy2zSuccess(
+                program,
+                fills[0].token2ZOut + half2zOfSecond,
+                userKeyPair,
+                expectedSolConsumed,
+                1,
+                1
+            );
+        });
+
+        after("Clear up fills registry", async () => {
+            await clearUpFillsRegistry(program, userKeyPair);
+        });
+    });
+
+    describe("Detailed consumption", async() => {
+        before("Enqueue fills", async () => {
+            for (let i = 0; i < 2; i++) {
+                await buySolSuccess(program, tokenAccountForUser, userKeyPair, currentConfigs, 1);
+            }
+        });
+
+        it("Lists the consumed slices and the remainder at head", async () => {
+            const {fills} = await getFillsRegistryAccount(program);
+            await consumeFillsDetailedSuccess(
+                program,
+                fills[0].solIn + Math.floor(fills[1].solIn / 2),
+                userKeyPair
+            );
+        });
+
+        after("Clear up fills registry", async () => {
+            await clearUpFillsRegistry(program, userKeyPair);
+        });
+    });
+
+    describe("Peek fills", async() => {
+        before("Enqueue fills", async () => {
+            for (let i = 0; i < 3; i++) {
+                await buySolSuccess(program, tokenAccountForUser, userKeyPair, currentConfigs, 1);
+            }
+        });
+
+        it("Returns a page of fills without consuming them", async () => {
+            await peekFillsSuccess(program, 0, 2);
+            await peekFillsSuccess(program, 1, 12);
+        });
+
+        it("Returns an empty page past the last fill", async () => {
+            await peekFillsSuccess(program, 5, 2);
+        });
+
+        after("Clear up fills registry", async () => {
+            await clearUpFillsRegistry(program, userKeyPair);
+        });
+    });
+
+    describe("Fills registry migration", async() => {
+        const recipient = anchor.web3.Keypair.generate().publicKey;
+
+        before("Enqueue fills", async () => {
+            for (let i = 0; i < 3; i++) {
+                await buySolSuccess(program, tokenAccountForUser, userKeyPair, currentConfigs, 1);
+            }
+        });
+
+        it("Refuses to close the registry in use", async () => {
+            await closeFillsRegistryAndVerifyFail(
+                program,
+                await getFillsRegistryAccountAddress(program),
+                recipient,
+                ErrorMsg.FILLS_REGISTRY_IN_USE
+            );
+        });
+
+        it("Moves fills in chunks, then closes the previous registry", async () => {
+            // The new registry is much smaller than the default one.
+            await beginFillsMigrationAndVerify(program, 1_000);
+
+            // Buys wait until the migration is over.
+            const oraclePriceData = await getOraclePriceData();
+            const askPrice = await getConversionPriceAndVerify(program, oraclePriceData);
+            await buySolFail(
+                program,
+                tokenAccountForUser,
+                askPrice,
+                userKeyPair,
+                oraclePriceData,
+                ErrorMsg.FILLS_MIGRATION_IN_PROGRESS
+            );
+
+            await migrateFillsAndVerify(program, 2, recipient);
+
+            // The oldest fills already sit in the new registry, the previous one cannot be consumed or listed.
+            await consumeFillsFail(
+                program,
+                DEFAULT_CONFIGS.solQuantity,
+                userKeyPair,
+                ErrorMsg.FILLS_MIGRATION_IN_PROGRESS
+            );
+            await peekFillsFail(program, 0, 2, ErrorMsg.FILLS_MIGRATION_IN_PROGRESS);
+
+            await migrateFillsAndVerify(program, 2, recipient);
+
+            // The new registry takes the next buys.
+            await buySolSuccess(program, tokenAccountForUser, userKeyPair, currentConfigs, 1);
+        });
+
+        after("Clear up fills registry", async () => {
+            await clearUpFillsRegistry(program, userKeyPair);
+        });
+    });
+});
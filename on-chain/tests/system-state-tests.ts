@@ -1,9 +1,22 @@
 import { describe } from "mocha";
-import { toggleSystemStateAndVerify, toggleSystemStateAndVerifyFail } from "./core/test-flow/system-state";
+import {
+    haltSystemAndVerify,
+    haltSystemAndVerifyFail,
+    setGuardianAndVerify,
+    setGuardianAndVerifyFail,
+    setPauseFlagsAndVerify,
+    setPauseFlagsAndVerifyFail,
+    toggleSystemStateAndVerify,
+    toggleSystemStateAndVerifyFail
+} from "./core/test-flow/system-state";
 import * as anchor from "@coral-xyz/anchor";
 import { setup } from "./core/setup";
 import { initializeSystemIfNeeded } from "./core/test-flow/system-initialize";
-import {ErrorMsg} from "./core/constants";
+import {ErrorMsg, PauseFlags} from "./core/constants";
+import { getConversionPriceAndVerify, getConversionPriceToFail } from "./core/test-flow/conversion-price";
+import { getOraclePriceData } from "./core/utils/price-oracle";
+import { getDefaultKeyPair } from "./core/utils/accounts";
+import { PublicKey } from "@solana/web3.js";
 
 describe("System State Tests", async () => {
     const program = await setup();
@@ -33,4 +46,85 @@ describe("System State Tests", async () => {
         await toggleSystemStateAndVerify(program, false);
         await toggleSystemStateAndVerifyFail(program, false, ErrorMsg.INVALID_SYSTEM_STATE);
     });
-});
\ No newline at end of file
+
+    describe("Guardian", () => {
+        const guardian = anchor.web3.Keypair.generate();
+
+        after("Remove the guardian", async () => {
+            await setGuardianAndVerify(program, PublicKey.default);
+        });
+
+        it("Should fail to set guardian if not admin", async () => {
+            const nonAdmin = anchor.web3.Keypair.generate();
+            await setGuardianAndVerifyFail(program, guardian.publicKey, ErrorMsg.UNAUTHORIZED_ADMIN, nonAdmin);
+        });
+
+        it("Should fail to halt if neither guardian nor admin", async () => {
+            await setGuardianAndVerify(program, guardian.publicKey);
+            const stranger = anchor.web3.Keypair.generate();
+            await haltSystemAndVerifyFail(program, stranger, ErrorMsg.UNAUTHORIZED_GUARDIAN);
+        });
+
+        it("Guardian can halt but not unhalt the system", async () => {
+            await haltSystemAndVerify(program, guardian, "guardian");
+            await haltSystemAndVerifyFail(program, guardian, ErrorMsg.INVALID_SYSTEM_STATE);
+            await toggleSystemStateAndVerifyFail(program, false, ErrorMsg.UNAUTHORIZED_ADMIN, guardian);
+
+            // Unhalting stays with the admin.
+            await toggleSystemStateAndVerify(program, false);
+        });
+
+        it("Admin can halt through the halt instruction", async () => {
+            await haltSystemAndVerify(program, getDefaultKeyPair(), "admin");
+            await toggleSystemStateAndVerify(program, false);
+        });
+    });
+
+    describe("Pause flags", () => {
+        const guardian = anchor.web3.Keypair.generate();
+
+        before("Set the guardian", async () => {
+            await setGuardianAndVerify(program, guardian.publicKey);
+        });
+
+        after("Resume every feature and remove the guardian", async () => {
+            await setPauseFlagsAndVerify(program, 0);
+            await setGuardianAndVerify(program, PublicKey.default);
+        });
+
+        it("Should fail to set unknown pause flags", async () => {
+            await setPauseFlagsAndVerifyFail(program, 1 << 7, ErrorMsg.INVALID_PAUSE_FLAGS);
+        });
+
+        it("Should fail to set pause flags if neither guardian nor admin", async () => {
+            const stranger = anchor.web3.Keypair.generate();
+            await setPauseFlagsAndVerifyFail(program, PauseFlags.QUOTES, ErrorMsg.UNAUTHORIZED_GUARDIAN, stranger);
+        });
+
+        it("Paused quotes are rejected until resumed", async () => {
+            await setPauseFlagsAndVerify(program, PauseFlags.QUOTES);
+            await getConversionPriceToFail(program, await getOraclePriceData(), ErrorMsg.FEATURE_PAUSED);
+
+            await setPauseFlagsAndVerify(program, 0);
+            await getConversionPriceAndVerify(program, await getOraclePriceData());
+        });
+
+        it("Guardian can pause more features but not resume them", async () => {
+            await setPauseFlagsAndVerify(program, PauseFlags.DEQUEUES, guardian);
+            await setPauseFlagsAndVerify(program, PauseFlags.DEQUEUES | PauseFlags.BUYS, guardian);
+            await setPauseFlagsAndVerifyFail(program, PauseFlags.BUYS, ErrorMsg.UNAUTHORIZED_GUARDIAN, guardian);
+
+            // Resuming stays with the admin.
+            await setPauseFlagsAndVerify(program, 0);
+        });
+
+        it("Admin set as the guardian can still resume features", async () => {
+            await setGuardianAndVerify(program, getDefaultKeyPair().publicKey);
+            await setPauseFlagsAndVerify(program, PauseFlags.BUYS);
+            await setPauseFlagsAndVerify(program, 0);
+
+            // Revert: Restore the guardian of this suite.
+            await setGuardianAndVerify(program, guardian.publicKey);
+        });
+    });
+});
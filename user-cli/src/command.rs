@@ -10,6 +10,10 @@ pub enum Commands {
         #[arg(short = 'p', long, required = true)]
         bid_price: String,
 
+        /// SOL amount to buy. Defaults to the configured SOL quantity.
+        #[arg(short = 's', long)]
+        sol_amount: Option<String>,
+
         #[arg(short = 'f')]
         from_address: Option<String>,
     },
@@ -17,9 +21,20 @@ pub enum Commands {
     /// Retrieves current 2Z-to-SOL conversion price.
     GetPrice,
 
-    /// Retrieves SOL quantity available per transaction (admin-configured parameter).
+    /// Retrieves default SOL quantity and accepted bounds per transaction (admin-configured parameters).
     GetQuantity,
 
     /// View Fills Registry, which tracks individual fill records and overall aggregate statistics.
-    GetFillsInfo
+    GetFillsInfo {
+        /// Number of pending fills skipped from the head.
+        #[arg(long, default_value_t = 0)]
+        offset: u64,
+
+        /// Maximum number of fills listed, all pending fills if not set.
+        #[arg(long)]
+        limit: Option<u64>,
+    },
+
+    /// Displays the trades and SOL bought by the signer in the current epoch against the per wallet limits.
+    GetMyLimits
 }
\ No newline at end of file
@@ -1,2 +1,4 @@
-pub const BUY_SOL_INSTRUCTION: &[u8] = b"global:buy_sol";
-pub const GET_PRICE_INSTRUCTION: &[u8] = b"global:get_conversion_rate";
\ No newline at end of file
+pub const BUY_SOL_WITH_PRECOMPILE_INSTRUCTION: &[u8] = b"global:buy_sol_with_precompile";
+pub const GET_PRICE_WITH_PRECOMPILE_INSTRUCTION: &[u8] = b"global:get_conversion_rate_with_precompile";
+pub const BUY_SOL_WITH_QUORUM_INSTRUCTION: &[u8] = b"global:buy_sol_with_quorum";
+pub const GET_PRICE_WITH_QUORUM_INSTRUCTION: &[u8] = b"global:get_conversion_rate_with_quorum";
@@ -1,14 +1,77 @@
 use anchor_client::anchor_lang::prelude::*;
+use base64::{engine::general_purpose::STANDARD, Engine};
 use serde::Deserialize;
 
-#[derive(AnchorSerialize, AnchorDeserialize)]
+/// Price attestation as served by the swap-oracle-service.
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct OraclePriceData {
+pub struct OraclePriceResponse {
+    // Services predating versioned attestations sign the v1 message.
+    #[serde(default = "default_attestation_version")]
+    pub version: u8,
     pub swap_rate: u64,
     pub timestamp: i64,
+    // Only v3 attestations sign a confidence.
+    #[serde(default)]
+    pub confidence: u64,
     pub signature: String,
     // uncomment if needed
     // pub sol_price_usd: String,
     // pub twoz_price_usd: String,
-}
\ No newline at end of file
+}
+
+fn default_attestation_version() -> u8 {
+    1
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Debug)]
+pub struct OraclePriceData {
+    pub version: u8,
+    pub swap_rate: u64,
+    pub timestamp: i64,
+    pub confidence: u64,
+    pub signature: [u8; 64],
+}
+
+impl TryFrom<OraclePriceResponse> for OraclePriceData {
+    type Error = Box<dyn std::error::Error>;
+
+    fn try_from(response: OraclePriceResponse) -> std::result::Result<Self, Self::Error> {
+        let signature: [u8; 64] = STANDARD
+            .decode(&response.signature)?
+            .try_into()
+            .map_err(|_| "Oracle signature must be 64 bytes")?;
+
+        Ok(OraclePriceData {
+            version: response.version,
+            swap_rate: response.swap_rate,
+            timestamp: response.timestamp,
+            confidence: response.confidence,
+            signature,
+        })
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Debug)]
+pub struct QuorumAttestation {
+    pub oracle_index: u8, // Position of the signer in the on-chain oracle_pubkeys
+    pub version: u8,
+    pub swap_rate: u64,
+    pub timestamp: i64,
+    pub confidence: u64,
+    pub signature: [u8; 64],
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Debug)]
+pub struct QuorumPriceData {
+    pub attestations: Vec<QuorumAttestation>,
+}
+
+/// Oracle price whose signature is checked by the Ed25519 precompile instruction.
+#[derive(AnchorSerialize, AnchorDeserialize, Debug)]
+pub struct OraclePriceMessage {
+    pub version: u8,
+    pub swap_rate: u64,
+    pub timestamp: i64,
+    pub confidence: u64,
+}
@@ -1,4 +1,8 @@
-use std::error::Error;
+use std::{
+    error::Error,
+    str::FromStr
+};
+use anchor_client::anchor_lang::prelude::Pubkey;
 use reqwest::Url;
 use cli_common::config::Config;
 
@@ -7,17 +11,32 @@ pub struct UserConfig {
     pub price_oracle_end_point: Url,
     pub rpc_url: String,
     pub double_zero_program_id: String,
+    // Quorum oracles, each endpoint paired with the oracle key signing its prices.
+    pub quorum_oracles: Vec<(Pubkey, Url)>,
 }
 
 impl UserConfig {
     pub fn load_user_config() -> Result<Self, Box<dyn Error>> {
         let raw_config = Config::load()?;
         let oracle_price_end_point = raw_config.price_oracle_end_point.ok_or("Missing oracle end point in config file")?;
+
+        let oracle_pubkeys = raw_config.oracle_pubkeys.unwrap_or_default();
+        let oracle_end_points = raw_config.price_oracle_end_points.unwrap_or_default();
+        if oracle_pubkeys.len() != oracle_end_points.len() {
+            return Err(Box::from("oracle_pubkeys and price_oracle_end_points must have the same length in config file"));
+        }
+        let quorum_oracles = oracle_pubkeys
+            .iter()
+            .zip(oracle_end_points.iter())
+            .map(|(key, end_point)| Ok((Pubkey::from_str(key)?, Url::parse(end_point)?)))
+            .collect::<Result<Vec<_>, Box<dyn Error>>>()?;
+
         Ok(UserConfig {
             program_id: raw_config.program_id,
             double_zero_program_id: raw_config.double_zero_program_id,
             price_oracle_end_point: Url::parse(&oracle_price_end_point)?,
             rpc_url: raw_config.rpc_url,
+            quorum_oracles,
         })
     }
-}
\ No newline at end of file
+}
@@ -6,31 +6,37 @@ use anchor_client::{
     solana_sdk::{
         hash::hash,
         instruction::Instruction,
-        signature::{ Signer }
+        signature::{ Signer },
+        sysvar
     },
     anchor_lang::{
-        AnchorSerialize,
         prelude::{AccountMeta, Pubkey},
-    }
+        system_program
+    },
 };
 use cli_common::{
-    transaction_executor,
+    structs::ConfigurationRegistry,
+    transaction_executor::{self, get_account_data},
     utils::{
         env_var::load_payer_from_env,
         pda_helper,
-        fixed_point_utils::parse_token_value,
+        fixed_point_utils::{convert_sol_value, parse_sol_value, parse_token_value},
         token_utils::find_or_initialize_associated_token_account,
         ui::{BULLET, LABEL}
     },
 };
 // Internal modules
 use crate::core::{
-    common::instruction::BUY_SOL_INSTRUCTION,
+    common::instruction::{BUY_SOL_WITH_PRECOMPILE_INSTRUCTION, BUY_SOL_WITH_QUORUM_INSTRUCTION},
     config::UserConfig,
-    utils::price_utils::fetch_oracle_price,
+    utils::price_utils::fetch_price_argument,
 };
 
-pub async fn buy_sol(bid_price: String, from_address: Option<String>) -> Result<(), Box<dyn Error>> {
+pub async fn buy_sol(
+    bid_price: String,
+    sol_amount: Option<String>,
+    from_address: Option<String>
+) -> Result<(), Box<dyn Error>> {
     let user_config = UserConfig::load_user_config()?;
     let program_id = Pubkey::from_str(&user_config.program_id)?;
     let revenue_distribution_program = Pubkey::from_str(&user_config.double_zero_program_id)?;
@@ -49,12 +55,32 @@ pub async fn buy_sol(bid_price: String, from_address: Option<String>) -> Result<
         )?
     };
 
-    let oracle_price_data = fetch_oracle_price(user_config.price_oracle_end_point).await?;
-    let mut data = hash(BUY_SOL_INSTRUCTION).to_bytes()[..8].to_vec();
-    data = [data, bid_price_parsed.to_le_bytes().to_vec(), oracle_price_data.try_to_vec()?].concat();
-
     // Getting necessary accounts
     let configuration_registry_pda = pda_helper::get_configuration_registry_pda(program_id).0;
+    let config_registry: ConfigurationRegistry =
+        get_account_data(user_config.rpc_url.clone(), configuration_registry_pda)?;
+
+    // Falls back to the configured SOL quantity when no amount is given.
+    let sol_amount_parsed = match sol_amount {
+        Some(ref amount) => parse_sol_value(amount)?,
+        None => config_registry.sol_quantity
+    };
+
+    let price_argument =
+        fetch_price_argument(&user_config, &config_registry, &token_mint_account_pda).await?;
+    let instruction_name = if price_argument.with_quorum {
+        BUY_SOL_WITH_QUORUM_INSTRUCTION
+    } else {
+        BUY_SOL_WITH_PRECOMPILE_INSTRUCTION
+    };
+    let mut data = hash(instruction_name).to_bytes()[..8].to_vec();
+    data = [
+        data,
+        bid_price_parsed.to_le_bytes().to_vec(),
+        sol_amount_parsed.to_le_bytes().to_vec(),
+        price_argument.data
+    ].concat();
+
     let program_state_pda = pda_helper::get_program_state_pda(program_id).0;
     let deny_list_registry_pda = pda_helper::get_deny_list_registry_pda(program_id).0;
     let withdraw_authority = pda_helper::get_withdraw_authority_pda(program_id).0;
@@ -63,6 +89,7 @@ pub async fn buy_sol(bid_price: String, from_address: Option<String>) -> Result<
     let protocol_treasury_token_account_pda =
         pda_helper::get_protocol_treasury_token_account_pda(revenue_distribution_program).0;
     let fills_registry = pda_helper::get_fills_registry_address(program_id, user_config.rpc_url)?;
+    let trade_history_pda = pda_helper::get_trade_history_pda(program_id, payer_pub_key).0;
 
     println!("{LABEL} Fills registry address: {}", fills_registry);
     println!("{LABEL} Configuration registry PDA: {}", configuration_registry_pda);
@@ -72,6 +99,7 @@ pub async fn buy_sol(bid_price: String, from_address: Option<String>) -> Result<
     println!("{LABEL} Journal account: {}", journal_pda);
     println!("{LABEL} Protocol treasury PDA: {}", protocol_treasury_token_account_pda);
     println!("{LABEL} Mock config account: {}", config_pda);
+    println!("{LABEL} Trade history PDA: {}", trade_history_pda);
 
     let accounts = vec![
         AccountMeta::new(configuration_registry_pda, false),
@@ -86,7 +114,12 @@ pub async fn buy_sol(bid_price: String, from_address: Option<String>) -> Result<
         AccountMeta::new(journal_pda, false),
         AccountMeta::new(spl_token::id(), false),
         AccountMeta::new(revenue_distribution_program, false),
+        AccountMeta::new_readonly(sysvar::instructions::id(), false),
         AccountMeta::new(payer_pub_key, true),
+        AccountMeta::new(trade_history_pda, false),
+        AccountMeta::new_readonly(system_program::ID, false),
+        // The posted oracle price is not read, the price comes with the instruction
+        AccountMeta::new_readonly(program_id, false),
     ];
 
     let buy_sol_ix = Instruction {
@@ -95,8 +128,10 @@ pub async fn buy_sol(bid_price: String, from_address: Option<String>) -> Result<
         accounts,
     };
 
-    transaction_executor::send_batch_instructions(vec![buy_sol_ix])?;
+    let mut instructions = price_argument.pre_instructions;
+    instructions.push(buy_sol_ix);
+    transaction_executor::send_batch_instructions(instructions)?;
 
-    println!("{BULLET} Buying SOL for {}", bid_price);
+    println!("{BULLET} Buying {} SOL for {}", convert_sol_value(sol_amount_parsed), bid_price);
     Ok(())
 }
\ No newline at end of file
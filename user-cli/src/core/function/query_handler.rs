@@ -1,17 +1,21 @@
 use std::{error::Error, str::FromStr};
 
 use anchor_client::{
-    anchor_lang::{prelude::AccountMeta, AnchorSerialize},
+    anchor_lang::{prelude::AccountMeta, AccountDeserialize},
+    solana_client::rpc_client::RpcClient,
     solana_sdk::{
-        hash::hash, instruction::Instruction, pubkey::Pubkey, signer::Signer,
+        commitment_config::CommitmentConfig, hash::hash, instruction::Instruction, pubkey::Pubkey,
+        signer::Signer, sysvar,
     },
 };
 use cli_common::{
-    structs::ConfigurationRegistry,
-    transaction_executor::{get_account_data, send_instruction_with_return_data},
+    common_functions::epoch_capacity::print_epoch_sol_capacity,
+    structs::{ConfigurationRegistry, TradeHistory},
+    transaction_executor::{get_account_data, send_batch_instructions_with_return_data},
     utils::{
         pda_helper::{
-            get_configuration_registry_pda, get_program_state_pda,
+            get_configuration_registry_pda, get_program_state_pda, get_token_mint_pda,
+            get_trade_history_pda,
         },
         ui,
         env_var::load_payer_from_env,
@@ -19,8 +23,9 @@ use cli_common::{
     },
 };
 use crate::core::{
-    common::instruction::GET_PRICE_INSTRUCTION, config::UserConfig,
-    utils::price_utils::fetch_oracle_price,
+    common::instruction::{GET_PRICE_WITH_PRECOMPILE_INSTRUCTION, GET_PRICE_WITH_QUORUM_INSTRUCTION},
+    config::UserConfig,
+    utils::price_utils::fetch_price_argument,
 };
 
 pub fn get_quantity() -> Result<(), Box<dyn Error>> {
@@ -30,7 +35,7 @@ pub fn get_quantity() -> Result<(), Box<dyn Error>> {
     println!("{}, Reading configuration registry...", ui::WAITING);
     let config_registry_pda = get_configuration_registry_pda(program_id).0;
     let config_registry: ConfigurationRegistry =
-        get_account_data(user_config.rpc_url, config_registry_pda)?;
+        get_account_data(user_config.rpc_url.clone(), config_registry_pda)?;
     let sol_quantity_in_sol = convert_sol_value(config_registry.sol_quantity);
 
     println!(
@@ -39,6 +44,13 @@ pub fn get_quantity() -> Result<(), Box<dyn Error>> {
         config_registry.sol_quantity,
         sol_quantity_in_sol
     );
+    println!(
+        "{} Accepted SOL amount per trade \n Min: {} SOL \n Max: {} SOL",
+        ui::OK,
+        convert_sol_value(config_registry.min_sol_quantity),
+        convert_sol_value(config_registry.max_sol_quantity)
+    );
+    print_epoch_sol_capacity(user_config.rpc_url, program_id)?;
     Ok(())
 }
 
@@ -46,18 +58,32 @@ pub async fn get_price() -> Result<(), Box<dyn Error>> {
     let payer = load_payer_from_env()?;
     let user_config = UserConfig::load_user_config()?;
 
-    let oracle_price_data = fetch_oracle_price(user_config.price_oracle_end_point).await?;
-    let mut data = hash(GET_PRICE_INSTRUCTION).to_bytes()[..8].to_vec();
-    data = [data, oracle_price_data.try_to_vec()?].concat();
-
     let program_id = Pubkey::from_str(&user_config.program_id)?;
+    let revenue_distribution_program = Pubkey::from_str(&user_config.double_zero_program_id)?;
+    let token_mint_account_pda = get_token_mint_pda(revenue_distribution_program).0;
     let program_state_pda = get_program_state_pda(program_id).0;
     let configuration_registry_pda = get_configuration_registry_pda(program_id).0;
+    let config_registry: ConfigurationRegistry =
+        get_account_data(user_config.rpc_url.clone(), configuration_registry_pda)?;
+
+    let price_argument =
+        fetch_price_argument(&user_config, &config_registry, &token_mint_account_pda).await?;
+    let instruction_name = if price_argument.with_quorum {
+        GET_PRICE_WITH_QUORUM_INSTRUCTION
+    } else {
+        GET_PRICE_WITH_PRECOMPILE_INSTRUCTION
+    };
+    let mut data = hash(instruction_name).to_bytes()[..8].to_vec();
+    data = [data, price_argument.data].concat();
 
     let accounts = vec![
         AccountMeta::new(payer.pubkey(), true),
         AccountMeta::new(program_state_pda, false),
         AccountMeta::new(configuration_registry_pda, false),
+        AccountMeta::new_readonly(token_mint_account_pda, false),
+        AccountMeta::new_readonly(sysvar::instructions::id(), false),
+        // The posted oracle price is not read, the price comes with the instruction
+        AccountMeta::new_readonly(program_id, false),
     ];
 
     let ix = Instruction {
@@ -65,8 +91,65 @@ pub async fn get_price() -> Result<(), Box<dyn Error>> {
         accounts,
         data,
     };
-    let result_bps: u64 = send_instruction_with_return_data(ix)?;
+    let mut instructions = price_argument.pre_instructions;
+    instructions.push(ix);
+    let result_bps: u64 = send_batch_instructions_with_return_data(instructions)?;
     let result = convert_token_value(result_bps);
     println!("{} Current estimated conversion rate: {} 2Z per SOL", ui::OK, result);
     Ok(())
-}
\ No newline at end of file
+}
+
+pub fn get_my_limits() -> Result<(), Box<dyn Error>> {
+    let payer = load_payer_from_env()?;
+    let user_config = UserConfig::load_user_config()?;
+    let program_id = Pubkey::from_str(&user_config.program_id)?;
+
+    println!("{}, Reading configuration registry...", ui::WAITING);
+    let config_registry_pda = get_configuration_registry_pda(program_id).0;
+    let config_registry: ConfigurationRegistry =
+        get_account_data(user_config.rpc_url.clone(), config_registry_pda)?;
+
+    let client = RpcClient::new_with_commitment(user_config.rpc_url, CommitmentConfig::confirmed());
+    let epoch = client.get_epoch_info()?.epoch;
+
+    // The trade history is created on the first buy of the wallet, only its absence means no trades.
+    let trade_history_pda = get_trade_history_pda(program_id, payer.pubkey()).0;
+    let (num_of_trades, sol_bought) =
+        match client.get_account_with_commitment(&trade_history_pda, CommitmentConfig::confirmed())?.value {
+            Some(account) => {
+                let trade_history = TradeHistory::try_deserialize(&mut account.data.as_slice())?;
+                if trade_history.epoch == epoch {
+                    (trade_history.num_of_trades, trade_history.sol_bought)
+                } else {
+                    (0, 0)
+                }
+            }
+            None => (0, 0),
+        };
+
+    let max_trades = config_registry.max_trades_per_wallet_per_epoch;
+    let max_sol = config_registry.max_sol_per_wallet_per_epoch;
+
+    println!("{} Trade limits of {} in epoch {}", ui::OK, payer.pubkey(), epoch);
+    if max_trades == 0 {
+        println!(" Trades: {} (unlimited)", num_of_trades);
+    } else {
+        println!(
+            " Trades: {} of {} ({} remaining)",
+            num_of_trades,
+            max_trades,
+            max_trades.saturating_sub(num_of_trades)
+        );
+    }
+    if max_sol == 0 {
+        println!(" SOL bought: {} SOL (unlimited)", convert_sol_value(sol_bought));
+    } else {
+        println!(
+            " SOL bought: {} of {} SOL ({} SOL remaining)",
+            convert_sol_value(sol_bought),
+            convert_sol_value(max_sol),
+            convert_sol_value(max_sol.saturating_sub(sol_bought))
+        );
+    }
+    Ok(())
+}
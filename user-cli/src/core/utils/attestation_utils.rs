@@ -0,0 +1,66 @@
+use std::error::Error;
+use anchor_client::{
+    anchor_lang::prelude::Pubkey,
+    solana_sdk::{instruction::Instruction, signature::Signature},
+};
+use cli_common::constant::{
+    ATTESTATION_DOMAIN_TAG,
+    ATTESTATION_V1,
+    ATTESTATION_V2,
+    ATTESTATION_V3,
+    QUOTE_DIRECTION_2Z_TO_SOL,
+};
+use solana_ed25519_program::new_ed25519_instruction_with_signature;
+
+use crate::core::common::structs::OraclePriceData;
+
+/// Rebuilds the message the oracle signed, matching the converter program.
+pub fn attestation_message(
+    oracle_price_data: &OraclePriceData,
+    program_id: &Pubkey,
+    double_zero_mint: &Pubkey,
+) -> Result<Vec<u8>, Box<dyn Error>> {
+    match oracle_price_data.version {
+        ATTESTATION_V1 => Ok(
+            format!("{}|{}", oracle_price_data.swap_rate, oracle_price_data.timestamp).into_bytes()
+        ),
+        ATTESTATION_V2 | ATTESTATION_V3 => {
+            let mut message = Vec::with_capacity(ATTESTATION_DOMAIN_TAG.len() + 1 + 32 + 32 + 1 + 8 + 8 + 8);
+            message.extend_from_slice(ATTESTATION_DOMAIN_TAG);
+            message.push(oracle_price_data.version);
+            message.extend_from_slice(program_id.as_ref());
+            message.extend_from_slice(double_zero_mint.as_ref());
+            message.push(QUOTE_DIRECTION_2Z_TO_SOL);
+            message.extend_from_slice(&oracle_price_data.swap_rate.to_le_bytes());
+            message.extend_from_slice(&oracle_price_data.timestamp.to_le_bytes());
+            if oracle_price_data.version == ATTESTATION_V3 {
+                message.extend_from_slice(&oracle_price_data.confidence.to_le_bytes());
+            }
+            Ok(message)
+        }
+        version => Err(Box::from(format!("Unsupported attestation version {}", version))),
+    }
+}
+
+/// Builds the Ed25519SigVerify instruction for the attestation. The signer is
+/// picked from the candidate oracle keys by checking the signature locally.
+pub fn build_ed25519_instruction(
+    oracle_price_data: &OraclePriceData,
+    oracle_pubkeys: &[Pubkey],
+    program_id: &Pubkey,
+    double_zero_mint: &Pubkey,
+) -> Result<Instruction, Box<dyn Error>> {
+    let message = attestation_message(oracle_price_data, program_id, double_zero_mint)?;
+    let signature = Signature::from(oracle_price_data.signature);
+
+    let oracle_pubkey = oracle_pubkeys
+        .iter()
+        .find(|oracle_pubkey| signature.verify(oracle_pubkey.as_ref(), &message))
+        .ok_or("Oracle signature does not match any accepted oracle key")?;
+
+    Ok(new_ed25519_instruction_with_signature(
+        &message,
+        &oracle_price_data.signature,
+        &oracle_pubkey.to_bytes(),
+    ))
+}
@@ -1 +1,2 @@
-pub mod price_utils;
\ No newline at end of file
+pub mod price_utils;
+pub mod attestation_utils;
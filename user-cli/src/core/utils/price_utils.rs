@@ -1,7 +1,22 @@
-use std::error::Error;
+use std::{error::Error, str::FromStr};
+use anchor_client::{
+    anchor_lang::{prelude::Pubkey, AnchorSerialize},
+    solana_sdk::instruction::Instruction,
+};
+use cli_common::{structs::ConfigurationRegistry, utils::ui};
 use reqwest::{Client, Url};
 
-use crate::core::common::structs::OraclePriceData;
+use crate::core::{
+    common::structs::{
+        OraclePriceData,
+        OraclePriceMessage,
+        OraclePriceResponse,
+        QuorumAttestation,
+        QuorumPriceData
+    },
+    config::UserConfig,
+    utils::attestation_utils::build_ed25519_instruction,
+};
 
 pub async fn fetch_oracle_price(price_oracle_end_point: Url) ->  Result<OraclePriceData, Box<dyn Error>>  {
     let client = Client::new();
@@ -10,9 +25,100 @@ pub async fn fetch_oracle_price(price_oracle_end_point: Url) ->  Result<OraclePr
         .header("User-Agent", "Admin CLI")
         .send()
         .await?
-        .json::<OraclePriceData>()
+        .json::<OraclePriceResponse>()
         .await?;
 
     println!("{:#?}", response);
-    Ok(response)
-}
\ No newline at end of file
+    OraclePriceData::try_from(response)
+}
+
+/// Collects attestations from the quorum oracles. Unreachable oracles and oracles
+/// missing from the on-chain set are skipped, the program rejects the quorum if too
+/// few signers remain.
+pub async fn fetch_quorum_price(
+    quorum_oracles: &[(Pubkey, Url)],
+    config_registry: &ConfigurationRegistry,
+) -> Result<QuorumPriceData, Box<dyn Error>> {
+    let oracle_quorum_threshold = config_registry.oracle_quorum_threshold;
+    let mut attestations = Vec::with_capacity(quorum_oracles.len());
+
+    for (oracle_pubkey, end_point) in quorum_oracles {
+        // Attestations reference their signer by its position in the on-chain oracle set.
+        let Some(oracle_index) = config_registry.oracle_pubkeys.iter().position(|key| key == oracle_pubkey) else {
+            println!("{} Oracle {} is not part of the on-chain oracle set", ui::FAIL, oracle_pubkey);
+            continue;
+        };
+
+        match fetch_oracle_price(end_point.clone()).await {
+            Ok(price) => attestations.push(QuorumAttestation {
+                oracle_index: oracle_index as u8,
+                version: price.version,
+                swap_rate: price.swap_rate,
+                timestamp: price.timestamp,
+                confidence: price.confidence,
+                signature: price.signature,
+            }),
+            Err(e) => println!("{} Failed to fetch price from {}: {}", ui::FAIL, end_point, e),
+        }
+    }
+
+    if attestations.len() < oracle_quorum_threshold as usize {
+        return Err(Box::from(format!(
+            "Only {} of {} required oracle attestations could be fetched",
+            attestations.len(),
+            oracle_quorum_threshold
+        )));
+    }
+    Ok(QuorumPriceData { attestations })
+}
+
+/// Price argument for the program together with the instructions that have to
+/// precede it in the same transaction.
+pub struct PriceArgument {
+    pub with_quorum: bool,
+    pub data: Vec<u8>,
+    pub pre_instructions: Vec<Instruction>,
+}
+
+/// Quorum attestations are sent once a quorum is configured on-chain. Otherwise the
+/// single oracle price is sent and its signature is verified by the Ed25519 precompile.
+pub async fn fetch_price_argument(
+    user_config: &UserConfig,
+    config_registry: &ConfigurationRegistry,
+    double_zero_mint: &Pubkey,
+) -> Result<PriceArgument, Box<dyn Error>> {
+    if config_registry.oracle_quorum_threshold == 0 {
+        let oracle_price_data = fetch_oracle_price(user_config.price_oracle_end_point.clone()).await?;
+
+        // Both keys are candidates while an oracle key rotation is pending.
+        let mut oracle_pubkeys = vec![config_registry.oracle_pubkey];
+        if config_registry.next_oracle_pubkey != Pubkey::default() {
+            oracle_pubkeys.push(config_registry.next_oracle_pubkey);
+        }
+
+        let ed25519_instruction = build_ed25519_instruction(
+            &oracle_price_data,
+            &oracle_pubkeys,
+            &Pubkey::from_str(&user_config.program_id)?,
+            double_zero_mint,
+        )?;
+        let oracle_price_message = OraclePriceMessage {
+            version: oracle_price_data.version,
+            swap_rate: oracle_price_data.swap_rate,
+            timestamp: oracle_price_data.timestamp,
+            confidence: oracle_price_data.confidence,
+        };
+        return Ok(PriceArgument {
+            with_quorum: false,
+            data: oracle_price_message.try_to_vec()?,
+            pre_instructions: vec![ed25519_instruction],
+        });
+    }
+
+    let quorum_price_data = fetch_quorum_price(&user_config.quorum_oracles, config_registry).await?;
+    Ok(PriceArgument {
+        with_quorum: true,
+        data: quorum_price_data.try_to_vec()?,
+        pre_instructions: Vec::new(),
+    })
+}
@@ -24,8 +24,8 @@ pub async fn handle() -> Result<(), Box<dyn Error>> {
     match cli.command {
 
         // Triggering SOL transaction.
-        Some(Commands::BuySol { bid_price, from_address}) => {
-            buy_sol(bid_price, from_address).await
+        Some(Commands::BuySol { bid_price, sol_amount, from_address}) => {
+            buy_sol(bid_price, sol_amount, from_address).await
         }
 
         // Displays SOL quantity available per transaction.
@@ -39,8 +39,13 @@ pub async fn handle() -> Result<(), Box<dyn Error>> {
         }
 
         // View Fills Registry.
-        Some(Commands::GetFillsInfo) => {
-            view_fills_registry()
+        Some(Commands::GetFillsInfo { offset, limit }) => {
+            view_fills_registry(offset, limit)
+        }
+
+        // Displays the per wallet limits of the signer.
+        Some(Commands::GetMyLimits) => {
+            query_handler::get_my_limits()
         }
 
         // Toggles system between active and paused states.